@@ -0,0 +1,221 @@
+use crate::attestation::sha256_hex;
+use crate::finding::{report_path, Finding};
+use crate::signature::ManifestEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Default approvals file, meant to be committed next to the skills it covers.
+pub const APPROVALS_FILE: &str = ".skill-approvals.json";
+
+/// Known-good snapshots of skills, keyed by target: a path relative to the
+/// approvals file, or a remote specifier.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Approvals {
+    pub version: u32,
+    pub approvals: BTreeMap<String, Approval>,
+}
+
+/// What a reviewer signed off on: every file's hash and the findings the
+/// skill had at the time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Approval {
+    pub approved_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+    pub tool_version: String,
+    pub ruleset_version: String,
+    /// SHA-256 per file, keyed by `/`-separated path within the skill.
+    pub files: BTreeMap<String, String>,
+    pub findings: Vec<ApprovedFinding>,
+}
+
+/// Rule and file are kept for people reviewing the file; comparisons use
+/// only the fingerprint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovedFinding {
+    pub fingerprint: String,
+    pub rule_id: String,
+    pub file: String,
+}
+
+/// How a skill differs from its approved snapshot.
+#[derive(Debug, Default, PartialEq)]
+pub struct Drift {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+    /// Findings not present at approval, such as those from newer rules.
+    pub new_findings: Vec<ApprovedFinding>,
+}
+
+impl Drift {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.new_findings.is_empty()
+    }
+}
+
+impl Approvals {
+    /// The approvals in `path`, or none when the file does not exist yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Approvals {
+                version: 1,
+                approvals: BTreeMap::new(),
+            }),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json + "\n").map_err(|e| format!("{}: {e}", path.display()))
+    }
+}
+
+/// Snapshot a skill's files and current findings.
+pub fn snapshot(
+    entries: &[ManifestEntry],
+    findings: &[Finding],
+    ruleset_version: String,
+    approved_by: Option<String>,
+) -> Approval {
+    let findings: BTreeMap<(String, String), ApprovedFinding> = findings
+        .iter()
+        .map(|f| {
+            let file = report_path(&f.location.file);
+            let fingerprint = f.fingerprint();
+            (
+                (file.clone(), fingerprint.clone()),
+                ApprovedFinding {
+                    fingerprint,
+                    rule_id: f.rule_id.clone(),
+                    file,
+                },
+            )
+        })
+        .collect();
+    Approval {
+        approved_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        approved_by,
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        ruleset_version,
+        files: entries
+            .iter()
+            .map(|(path, bytes)| (path.clone(), sha256_hex(bytes)))
+            .collect(),
+        findings: findings.into_values().collect(),
+    }
+}
+
+/// Compare a fresh snapshot of a skill against the approved one.
+pub fn drift(approved: &Approval, current: &Approval) -> Drift {
+    let mut drift = Drift::default();
+    for (path, hash) in &current.files {
+        match approved.files.get(path) {
+            None => drift.added.push(path.clone()),
+            Some(old) if old != hash => drift.modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    drift.removed = approved
+        .files
+        .keys()
+        .filter(|path| !current.files.contains_key(*path))
+        .cloned()
+        .collect();
+    drift.new_findings = current
+        .findings
+        .iter()
+        .filter(|f| {
+            !approved
+                .findings
+                .iter()
+                .any(|a| a.fingerprint == f.fingerprint)
+        })
+        .cloned()
+        .collect();
+    drift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Severity;
+    use crate::rules;
+
+    fn entries(files: &[(&str, &str)]) -> Vec<ManifestEntry> {
+        files
+            .iter()
+            .map(|(p, c)| (p.to_string(), c.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_drift_lists_file_and_finding_changes() {
+        let approved = snapshot(
+            &entries(&[("SKILL.md", "# A"), ("run.sh", "curl a"), ("old.txt", "x")]),
+            &[Finding {
+                matched_text: "curl a".into(),
+                ..Finding::test("SL-NET-002", Severity::Warning, "run.sh", 1)
+            }],
+            rules::ruleset_version(),
+            Some("alice".into()),
+        );
+        let same = snapshot(
+            &entries(&[("run.sh", "curl a"), ("old.txt", "x"), ("SKILL.md", "# A")]),
+            &[Finding {
+                matched_text: "curl a".into(),
+                ..Finding::test("SL-NET-002", Severity::Warning, "run.sh", 1)
+            }],
+            rules::ruleset_version(),
+            None,
+        );
+        assert!(drift(&approved, &same).is_empty());
+
+        let current = snapshot(
+            &entries(&[("SKILL.md", "# A"), ("run.sh", "curl b"), ("new.txt", "y")]),
+            &[Finding {
+                matched_text: "curl b".into(),
+                ..Finding::test("SL-NET-002", Severity::Warning, "run.sh", 1)
+            }],
+            rules::ruleset_version(),
+            None,
+        );
+        let d = drift(&approved, &current);
+        assert_eq!(d.added, ["new.txt"]);
+        assert_eq!(d.removed, ["old.txt"]);
+        assert_eq!(d.modified, ["run.sh"]);
+        assert_eq!(d.new_findings.len(), 1);
+        assert_eq!(d.new_findings[0].rule_id, "SL-NET-002");
+    }
+
+    #[test]
+    fn test_approvals_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(APPROVALS_FILE);
+        let mut approvals = Approvals::load(&path).unwrap();
+        assert!(approvals.approvals.is_empty());
+        approvals.approvals.insert(
+            "skills/a".into(),
+            snapshot(
+                &entries(&[("SKILL.md", "# A")]),
+                &[],
+                rules::ruleset_version(),
+                None,
+            ),
+        );
+        approvals.save(&path).unwrap();
+
+        let loaded = Approvals::load(&path).unwrap();
+        let approval = &loaded.approvals["skills/a"];
+        assert_eq!(approval.files["SKILL.md"], sha256_hex(b"# A"));
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("approved_by"));
+    }
+}
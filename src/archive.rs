@@ -0,0 +1,468 @@
+use crate::scanner::{self, SkipReason, MAX_FILE_SIZE};
+use flate2::read::{DeflateDecoder, MultiGzDecoder};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// Archives larger than this on disk are not opened.
+pub const MAX_ARCHIVE_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Bounds on what an archive may expand to, so a zip bomb fails the scan
+/// instead of exhausting memory.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub max_entries: usize,
+    /// Total bytes of all entries (and of a decompressed tarball).
+    pub max_total_size: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_entries: 10_000,
+            max_total_size: 200 * 1024 * 1024,
+        }
+    }
+}
+
+/// A file inside an archive: its archive-relative path and its text, or why
+/// it was not read.
+pub type Entry = (PathBuf, Result<String, SkipReason>);
+
+/// Whether `path` names an archive this module can open, by extension.
+/// Claude's `.skill` packages are zip files.
+pub fn is_archive(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    [".zip", ".skill", ".tar", ".tar.gz", ".tgz"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
+}
+
+/// Read every regular file in a zip or (gzipped) tar archive, in memory.
+pub fn read_entries(path: &Path, limits: Limits) -> Result<Vec<Entry>, String> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("{}: {e}", path.display()))?
+        .len();
+    if size > MAX_ARCHIVE_SIZE {
+        return Err(format!(
+            "{}: {size} bytes, over the {MAX_ARCHIVE_SIZE}-byte archive limit",
+            path.display()
+        ));
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    entries(&bytes, limits).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Entries of an archive held in memory; the format is told by its magic
+/// bytes rather than its name.
+pub fn entries(bytes: &[u8], limits: Limits) -> Result<Vec<Entry>, String> {
+    if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+        zip_entries(bytes, limits)
+    } else if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut tar = Vec::new();
+        MultiGzDecoder::new(bytes)
+            .take(limits.max_total_size + 1)
+            .read_to_end(&mut tar)
+            .map_err(|e| format!("not a valid gzip stream: {e}"))?;
+        if tar.len() as u64 > limits.max_total_size {
+            return Err(too_big(limits));
+        }
+        tar_entries(&tar, limits)
+    } else if bytes.len() >= 262 && &bytes[257..262] == b"ustar" {
+        tar_entries(bytes, limits)
+    } else {
+        Err("not a zip or tar archive".to_string())
+    }
+}
+
+fn too_big(limits: Limits) -> String {
+    format!(
+        "archive expands to more than {} bytes; refusing to extract it",
+        limits.max_total_size
+    )
+}
+
+/// Keeps the entry count and expanded size within the limits.
+struct Budget {
+    limits: Limits,
+    entries: usize,
+    total: u64,
+}
+
+impl Budget {
+    fn new(limits: Limits) -> Self {
+        Budget {
+            limits,
+            entries: 0,
+            total: 0,
+        }
+    }
+
+    fn add(&mut self, size: u64) -> Result<(), String> {
+        self.entries += 1;
+        self.total += size;
+        if self.entries > self.limits.max_entries {
+            return Err(format!(
+                "archive has more than {} entries; refusing to extract it",
+                self.limits.max_entries
+            ));
+        }
+        if self.total > self.limits.max_total_size {
+            return Err(too_big(self.limits));
+        }
+        Ok(())
+    }
+}
+
+/// An entry name as a relative path, or why it cannot be one. Absolute
+/// paths and `..` would point outside the archive.
+fn entry_path(name: &str) -> Result<PathBuf, SkipReason> {
+    let path = PathBuf::from(name.trim_start_matches("./"));
+    if path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(SkipReason::Unreadable(
+            "path points outside the archive".to_string(),
+        ));
+    }
+    Ok(path)
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<usize> {
+    Some(u16::from_le_bytes(bytes.get(offset..offset + 2)?.try_into().ok()?) as usize)
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u64> {
+    Some(u32::from_le_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?) as u64)
+}
+
+/// Files listed in a zip's central directory. Stored and deflated entries
+/// are read; encrypted ones and other methods are skipped.
+fn zip_entries(bytes: &[u8], limits: Limits) -> Result<Vec<Entry>, String> {
+    let corrupt = || "corrupt zip archive".to_string();
+    // The end of central directory record, before a comment of up to 64 KiB.
+    let search_from = bytes.len().saturating_sub(22 + 0xFFFF);
+    let eocd = (search_from..bytes.len().saturating_sub(21))
+        .rev()
+        .find(|&i| bytes[i..].starts_with(b"PK\x05\x06"))
+        .ok_or_else(corrupt)?;
+    let count = u16_at(bytes, eocd + 10).ok_or_else(corrupt)?;
+    let directory = u32_at(bytes, eocd + 16).ok_or_else(corrupt)?;
+    if count == 0xFFFF || directory == 0xFFFF_FFFF {
+        return Err("zip64 archives are not supported".to_string());
+    }
+    if count > limits.max_entries {
+        return Err(format!(
+            "archive has more than {} entries; refusing to extract it",
+            limits.max_entries
+        ));
+    }
+
+    let mut budget = Budget::new(limits);
+    let mut entries = Vec::new();
+    let mut at = directory as usize;
+    for _ in 0..count {
+        if !bytes
+            .get(at..)
+            .is_some_and(|b| b.starts_with(b"PK\x01\x02"))
+        {
+            return Err(corrupt());
+        }
+        let flags = u16_at(bytes, at + 8).ok_or_else(corrupt)?;
+        let method = u16_at(bytes, at + 10).ok_or_else(corrupt)?;
+        let compressed = u32_at(bytes, at + 20).ok_or_else(corrupt)? as usize;
+        let size = u32_at(bytes, at + 24).ok_or_else(corrupt)?;
+        let name_len = u16_at(bytes, at + 28).ok_or_else(corrupt)?;
+        let extra_len = u16_at(bytes, at + 30).ok_or_else(corrupt)?;
+        let comment_len = u16_at(bytes, at + 32).ok_or_else(corrupt)?;
+        let mode = u32_at(bytes, at + 38).ok_or_else(corrupt)? >> 16;
+        let local = u32_at(bytes, at + 42).ok_or_else(corrupt)? as usize;
+        let name = bytes.get(at + 46..at + 46 + name_len).ok_or_else(corrupt)?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        at += 46 + name_len + extra_len + comment_len;
+
+        // Directories and symbolic links, which a directory walk skips too.
+        if name.ends_with('/') || mode & 0o170000 == 0o120000 {
+            continue;
+        }
+        let path = match entry_path(&name) {
+            Ok(p) => p,
+            Err(reason) => {
+                entries.push((PathBuf::from(name), Err(reason)));
+                continue;
+            }
+        };
+        if size > MAX_FILE_SIZE {
+            budget.add(0)?;
+            entries.push((path, Err(SkipReason::TooLarge(size))));
+            continue;
+        }
+        if flags & 1 != 0 {
+            budget.add(0)?;
+            entries.push((path, Err(SkipReason::Unreadable("encrypted".to_string()))));
+            continue;
+        }
+
+        if !bytes
+            .get(local..)
+            .is_some_and(|b| b.starts_with(b"PK\x03\x04"))
+        {
+            return Err(corrupt());
+        }
+        let local_name = u16_at(bytes, local + 26).ok_or_else(corrupt)?;
+        let local_extra = u16_at(bytes, local + 28).ok_or_else(corrupt)?;
+        let data_start = local + 30 + local_name + local_extra;
+        let data = bytes
+            .get(data_start..data_start + compressed)
+            .ok_or_else(corrupt)?;
+        let content = match method {
+            0 => data.to_vec(),
+            8 => {
+                // The declared size may lie; never inflate past the limit.
+                let mut out = Vec::new();
+                DeflateDecoder::new(data)
+                    .take(MAX_FILE_SIZE + 1)
+                    .read_to_end(&mut out)
+                    .map_err(|e| format!("{name}: {e}"))?;
+                out
+            }
+            other => {
+                budget.add(0)?;
+                entries.push((
+                    path,
+                    Err(SkipReason::Unreadable(format!(
+                        "unsupported compression method {other}"
+                    ))),
+                ));
+                continue;
+            }
+        };
+        budget.add(content.len() as u64)?;
+        if content.len() as u64 > MAX_FILE_SIZE {
+            entries.push((path, Err(SkipReason::TooLarge(content.len() as u64))));
+            continue;
+        }
+        entries.push((path, scanner::decode_text(content)));
+    }
+    Ok(entries)
+}
+
+/// Regular files in a tar stream, with long names from GNU (`L`) and pax
+/// (`x`) headers.
+fn tar_entries(bytes: &[u8], limits: Limits) -> Result<Vec<Entry>, String> {
+    let corrupt = || "corrupt tar archive".to_string();
+    let mut budget = Budget::new(limits);
+    let mut entries = Vec::new();
+    let mut long_name: Option<String> = None;
+    let mut at = 0;
+    while let Some(header) = bytes.get(at..at + 512) {
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+        let size = octal(&header[124..136]).ok_or_else(corrupt)?;
+        let data_start = at + 512;
+        at = data_start + (size as usize).div_ceil(512) * 512;
+        let data = bytes
+            .get(data_start..data_start + size as usize)
+            .ok_or_else(corrupt)?;
+
+        match header[156] {
+            b'L' => {
+                long_name = Some(field(data));
+                continue;
+            }
+            b'x' => {
+                long_name = pax_path(data).or(long_name);
+                continue;
+            }
+            b'0' | 0 => {}
+            // Directories, links, devices and global pax headers.
+            _ => {
+                long_name = None;
+                continue;
+            }
+        }
+        let name = long_name.take().unwrap_or_else(|| {
+            let prefix = field(&header[345..500]);
+            let name = field(&header[..100]);
+            if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            }
+        });
+        let path = match entry_path(&name) {
+            Ok(p) => p,
+            Err(reason) => {
+                entries.push((PathBuf::from(name), Err(reason)));
+                continue;
+            }
+        };
+        if size > MAX_FILE_SIZE {
+            budget.add(0)?;
+            entries.push((path, Err(SkipReason::TooLarge(size))));
+            continue;
+        }
+        budget.add(size)?;
+        entries.push((path, scanner::decode_text(data.to_vec())));
+    }
+    Ok(entries)
+}
+
+/// A NUL-terminated header field.
+fn field(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// A numeric header field: octal text. The base-256 form used for huge
+/// files is reported as larger than any file we would read.
+fn octal(bytes: &[u8]) -> Option<u64> {
+    if bytes[0] & 0x80 != 0 {
+        return Some(u64::MAX);
+    }
+    let text = field(bytes);
+    let text = text.trim();
+    if text.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(text, 8).ok()
+}
+
+/// The `path` record of a pax extended header (`<len> path=<value>\n`).
+fn pax_path(data: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(data).lines().find_map(|record| {
+        let (_, kv) = record.split_once(' ')?;
+        kv.strip_prefix("path=").map(str::to_string)
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use flate2::write::{DeflateEncoder, GzEncoder};
+    use flate2::Compression;
+    use std::io::Write;
+
+    /// A zip archive with deflated entries. CRCs are left at zero; the
+    /// reader does not check them.
+    pub(crate) fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut directory = Vec::new();
+        for (name, content) in files {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(content).unwrap();
+            let data = encoder.finish().unwrap();
+            let local = out.len() as u32;
+            let header = |sig: &[u8], central: bool| {
+                let mut h = sig.to_vec();
+                if central {
+                    h.extend_from_slice(&20u16.to_le_bytes());
+                }
+                h.extend_from_slice(&20u16.to_le_bytes());
+                h.extend_from_slice(&0u16.to_le_bytes());
+                h.extend_from_slice(&8u16.to_le_bytes());
+                h.extend_from_slice(&[0; 8]);
+                h.extend_from_slice(&(data.len() as u32).to_le_bytes());
+                h.extend_from_slice(&(content.len() as u32).to_le_bytes());
+                h.extend_from_slice(&(name.len() as u16).to_le_bytes());
+                h.extend_from_slice(&0u16.to_le_bytes());
+                if central {
+                    h.extend_from_slice(&[0; 6]);
+                    h.extend_from_slice(&0o100644u32.wrapping_shl(16).to_le_bytes());
+                    h.extend_from_slice(&local.to_le_bytes());
+                }
+                h.extend_from_slice(name.as_bytes());
+                h
+            };
+            out.extend(header(b"PK\x03\x04", false));
+            out.extend_from_slice(&data);
+            directory.extend(header(b"PK\x01\x02", true));
+        }
+        let offset = out.len() as u32;
+        out.extend_from_slice(&directory);
+        out.extend_from_slice(b"PK\x05\x06");
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        out.extend_from_slice(&offset.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out
+    }
+
+    /// A gzipped ustar archive.
+    pub(crate) fn tar_gz(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut tar = Vec::new();
+        for (name, content) in files {
+            let mut header = [0u8; 512];
+            header[..name.len()].copy_from_slice(name.as_bytes());
+            header[100..107].copy_from_slice(b"0000644");
+            header[124..135].copy_from_slice(format!("{:011o}", content.len()).as_bytes());
+            header[156] = b'0';
+            header[257..263].copy_from_slice(b"ustar\0");
+            tar.extend_from_slice(&header);
+            tar.extend_from_slice(content);
+            tar.resize(tar.len().div_ceil(512) * 512, 0);
+        }
+        tar.extend_from_slice(&[0; 1024]);
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(&tar).unwrap();
+        gz.finish().unwrap()
+    }
+
+    fn names(entries: &[Entry]) -> Vec<String> {
+        entries
+            .iter()
+            .map(|(p, r)| format!("{} {}", p.display(), r.is_ok()))
+            .collect()
+    }
+
+    #[test]
+    fn test_reads_zip_and_tar_gz_entries() {
+        let files: &[(&str, &[u8])] = &[
+            ("pdf/SKILL.md", b"# PDF\n"),
+            ("pdf/scripts/run.sh", b"echo hi\n"),
+            ("pdf/logo.png", b"\x89PNG\xff\xfe"),
+            ("../escape.sh", b"rm -rf ~\n"),
+        ];
+        for archive in [zip(files), tar_gz(files)] {
+            let entries = entries(&archive, Limits::default()).unwrap();
+            assert_eq!(
+                names(&entries),
+                [
+                    "pdf/SKILL.md true",
+                    "pdf/scripts/run.sh true",
+                    "pdf/logo.png false",
+                    "../escape.sh false",
+                ]
+            );
+            assert_eq!(entries[0].1.as_deref().unwrap(), "# PDF\n");
+            assert_eq!(entries[2].1, Err(SkipReason::Binary));
+        }
+        assert!(entries(b"plain text", Limits::default()).is_err());
+    }
+
+    #[test]
+    fn test_refuses_archives_that_expand_too_far() {
+        let zeros = vec![b'0'; 64 * 1024];
+        let limits = Limits {
+            max_entries: 10,
+            max_total_size: 100 * 1024,
+        };
+        let bomb: Vec<(&str, &[u8])> = vec![("a.txt", &zeros), ("b.txt", &zeros)];
+        for archive in [zip(&bomb), tar_gz(&bomb)] {
+            let err = entries(&archive, limits).unwrap_err();
+            assert!(err.contains("expands to more than"), "{err}");
+        }
+
+        let many: Vec<(&str, &[u8])> = (0..11).map(|_| ("x.txt", &b"x"[..])).collect();
+        assert!(entries(&zip(&many), limits)
+            .unwrap_err()
+            .contains("more than 10 entries"));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::finding::{Finding, Severity};
+use crate::scanner::{self, FileType, ScannedFile};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+/// A conventional subdirectory of a skill. Findings in it may get a different
+/// severity: an `eval(` quoted in a reference document is not the same as one
+/// in a script the agent runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Area {
+    References,
+    Assets,
+    Scripts,
+}
+
+impl Area {
+    pub const ALL: [Area; 3] = [Area::References, Area::Assets, Area::Scripts];
+
+    /// The area a file is in: the first directory of its path inside its
+    /// skill (per `roots`), or inside the scan target when it is in no skill.
+    pub fn of(path: &Path, roots: &[PathBuf]) -> Option<Area> {
+        let inner = match scanner::skill_root_for(path, roots) {
+            Some(root) => path.strip_prefix(root).ok()?,
+            None => path,
+        };
+        let mut components = inner.components();
+        let Some(Component::Normal(dir)) = components.next() else {
+            return None;
+        };
+        // The file itself must be below the directory.
+        components.next()?;
+        Area::ALL
+            .into_iter()
+            .find(|area| dir.to_str() == Some(area.dir()))
+    }
+
+    pub fn dir(self) -> &'static str {
+        match self {
+            Area::References => "references",
+            Area::Assets => "assets",
+            Area::Scripts => "scripts",
+        }
+    }
+}
+
+impl fmt::Display for Area {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/", self.dir())
+    }
+}
+
+/// Severity per category name (`execution`) or rule ID (`SL-EXEC-002`), for
+/// findings in each area. A rule ID entry wins over its category.
+pub type AreaPolicy = BTreeMap<Area, BTreeMap<String, Severity>>;
+
+/// Command execution in a document under `references/` or `assets/` is an
+/// example, and in `scripts/` the real thing. Used for each area the config
+/// file's `[policy.areas]` leaves out.
+pub fn defaults() -> AreaPolicy {
+    let execution = |severity| BTreeMap::from([("execution".to_string(), severity)]);
+    BTreeMap::from([
+        (Area::References, execution(Severity::Info)),
+        (Area::Assets, execution(Severity::Info)),
+        (Area::Scripts, execution(Severity::Error)),
+    ])
+}
+
+/// Set the severity of findings in an area per `policy`, unless
+/// `overridden(rule_id)` says the config file sets the rule's severity
+/// itself. In `references/` and `assets/` only documents are affected, since
+/// a script there runs as readily as one in `scripts/`; in `scripts/` the
+/// policy is a floor and never lowers a finding. Runs before escalation,
+/// which may raise them again.
+pub fn apply(
+    findings: &mut [Finding],
+    policy: &AreaPolicy,
+    files: &[ScannedFile],
+    roots: &[PathBuf],
+    overridden: impl Fn(&str) -> bool,
+) {
+    let documents: HashSet<&Path> = files
+        .iter()
+        .filter(|f| is_document(f))
+        .map(|f| f.relative_path.as_path())
+        .collect();
+    for f in findings.iter_mut() {
+        let Some(area) = Area::of(&f.location.file, roots) else {
+            continue;
+        };
+        let Some(entries) = policy.get(&area) else {
+            continue;
+        };
+        if area != Area::Scripts && !documents.contains(f.location.file.as_path()) {
+            continue;
+        }
+        let severity = entries
+            .get(&f.rule_id)
+            .or_else(|| entries.get(&f.category().to_string()));
+        match severity {
+            Some(&severity)
+                if severity != f.severity
+                    && !(area == Area::Scripts && severity < f.severity)
+                    && !overridden(&f.rule_id) =>
+            {
+                f.message = format!("{} ({} in {area})", f.message, severity);
+                f.severity = severity;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Markdown, or a text file of no known type that is not a script with a
+/// `#!` line.
+fn is_document(file: &ScannedFile) -> bool {
+    match file.file_type {
+        FileType::Markdown => true,
+        FileType::Unknown => !file.content.starts_with("#!"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_area_severities_per_skill() {
+        let roots = vec![PathBuf::from("skills/pdf")];
+        assert_eq!(
+            Area::of(Path::new("skills/pdf/scripts/run.sh"), &roots),
+            Some(Area::Scripts)
+        );
+        assert_eq!(
+            Area::of(Path::new("references/api.md"), &[]),
+            Some(Area::References)
+        );
+        assert_eq!(Area::of(Path::new("skills/pdf/SKILL.md"), &roots), None);
+        assert_eq!(Area::of(Path::new("skills/pdf/scripts"), &roots), None);
+        assert_eq!(
+            Area::of(Path::new("skills/pdf/lib/scripts/x.sh"), &roots),
+            None
+        );
+
+        let files: Vec<ScannedFile> = [
+            "skills/pdf/references/api.md",
+            "skills/pdf/assets/setup.sh",
+            "skills/pdf/scripts/run.sh",
+            "skills/pdf/SKILL.md",
+        ]
+        .into_iter()
+        .map(|path| ScannedFile {
+            path: PathBuf::from(path),
+            relative_path: PathBuf::from(path),
+            file_type: FileType::from_path(Path::new(path)),
+            content: String::new(),
+        })
+        .collect();
+        let critical = Finding::test(
+            "SL-EXEC-011",
+            Severity::Critical,
+            "skills/pdf/scripts/run.sh",
+            1,
+        );
+        let mut findings = vec![
+            Finding::test(
+                "SL-EXEC-002",
+                Severity::Warning,
+                "skills/pdf/references/api.md",
+                1,
+            ),
+            Finding::test(
+                "SL-EXEC-002",
+                Severity::Warning,
+                "skills/pdf/assets/setup.sh",
+                1,
+            ),
+            Finding::test(
+                "SL-EXEC-002",
+                Severity::Warning,
+                "skills/pdf/scripts/run.sh",
+                1,
+            ),
+            critical,
+            Finding::test(
+                "SL-NET-002",
+                Severity::Warning,
+                "skills/pdf/scripts/run.sh",
+                1,
+            ),
+            Finding::test(
+                "SL-EXEC-001",
+                Severity::Warning,
+                "skills/pdf/scripts/run.sh",
+                1,
+            ),
+            Finding::test("SL-EXEC-002", Severity::Warning, "skills/pdf/SKILL.md", 1),
+        ];
+        apply(&mut findings, &defaults(), &files, &roots, |id| {
+            id == "SL-EXEC-001"
+        });
+        let severities: Vec<Severity> = findings.iter().map(|f| f.severity).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Info,
+                Severity::Warning,
+                Severity::Error,
+                Severity::Critical,
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Warning,
+            ]
+        );
+        assert_eq!(findings[0].message, "test (info in references/)");
+    }
+}
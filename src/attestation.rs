@@ -0,0 +1,218 @@
+use crate::finding::{Finding, Severity};
+use crate::pipeline::ScanOutcome;
+use crate::scoring::{self, Grade};
+use crate::signature::{self, ManifestEntry};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path, PathBuf};
+
+const ATTESTATION_TYPE: &str = "https://skill-issue.sh/attestation/v1";
+
+/// Evidence that a skill was scanned: what content was seen, with which rules,
+/// and what came out. Each attestation records the digest of the one it
+/// replaces at the same path, so a stored series forms a hash chain.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Attestation {
+    #[serde(rename = "_type")]
+    pub attestation_type: String,
+    pub tool: ToolInfo,
+    pub ruleset_version: String,
+    pub target: String,
+    pub timestamp: String,
+    pub subjects: Vec<Subject>,
+    pub manifest_sha256: String,
+    pub findings_sha256: String,
+    pub summary: AttestationSummary,
+    pub previous_digest: Option<String>,
+    /// SHA-256 of this document serialized without the `digest` field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Subject {
+    pub path: String,
+    pub sha256: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttestationSummary {
+    pub total: usize,
+    pub critical: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    pub risk_score: u32,
+    pub grade: Grade,
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+pub fn build(
+    entries: &[ManifestEntry],
+    findings: &[Finding],
+    target: &str,
+    ruleset_version: &str,
+    previous_digest: Option<String>,
+) -> Result<Attestation, String> {
+    let mut subjects: Vec<Subject> = entries
+        .iter()
+        .map(|(path, bytes)| Subject {
+            path: path.clone(),
+            sha256: sha256_hex(bytes),
+        })
+        .collect();
+    subjects.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let findings_json = serde_json::to_vec(findings).map_err(|e| e.to_string())?;
+    let trust = scoring::score(findings);
+    let count = |s: Severity| findings.iter().filter(|f| f.severity == s).count();
+
+    let mut attestation = Attestation {
+        attestation_type: ATTESTATION_TYPE.to_string(),
+        tool: ToolInfo {
+            name: "skill-issue".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        ruleset_version: ruleset_version.to_string(),
+        target: target.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        subjects,
+        manifest_sha256: sha256_hex(signature::manifest(entries).as_bytes()),
+        findings_sha256: sha256_hex(&findings_json),
+        summary: AttestationSummary {
+            total: findings.len(),
+            critical: count(Severity::Critical),
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            info: count(Severity::Info),
+            risk_score: trust.risk_score,
+            grade: trust.grade,
+        },
+        previous_digest,
+        digest: None,
+    };
+    attestation.digest = Some(attestation.compute_digest()?);
+    Ok(attestation)
+}
+
+impl Attestation {
+    pub fn compute_digest(&self) -> Result<String, String> {
+        let mut unsigned = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        if let Some(obj) = unsigned.as_object_mut() {
+            obj.remove("digest");
+        }
+        let canonical = serde_json::to_vec(&unsigned).map_err(|e| e.to_string())?;
+        Ok(sha256_hex(&canonical))
+    }
+}
+
+/// The subjects of one scan target in a report that covers several, under
+/// the prefix its findings are named with.
+pub fn prefixed(prefix: &Path, entries: Vec<ManifestEntry>) -> Vec<ManifestEntry> {
+    entries
+        .into_iter()
+        .map(|(path, bytes)| {
+            let joined: PathBuf = prefix
+                .components()
+                .filter(|c| !matches!(c, Component::CurDir))
+                .collect::<PathBuf>()
+                .join(&path);
+            (signature::manifest_path(&joined), bytes)
+        })
+        .collect()
+}
+
+/// Build an attestation for a completed scan over the files in `entries` and
+/// write it to `path`, chaining to any attestation already there.
+pub fn write(path: &Path, entries: &[ManifestEntry], outcome: &ScanOutcome) -> Result<(), String> {
+    let previous_digest = match std::fs::read_to_string(path) {
+        Ok(existing) => {
+            let prev: Attestation = serde_json::from_str(&existing).map_err(|e| {
+                format!("existing attestation {} is not valid: {e}", path.display())
+            })?;
+            if prev.compute_digest()? != prev.digest.clone().unwrap_or_default() {
+                return Err(format!(
+                    "existing attestation {} fails its own digest check",
+                    path.display()
+                ));
+            }
+            prev.digest
+        }
+        Err(_) => None,
+    };
+
+    let attestation = build(
+        entries,
+        &outcome.findings,
+        &outcome.display_path.display().to_string(),
+        &outcome.provenance.ruleset_version,
+        previous_digest,
+    )?;
+    let json = serde_json::to_string_pretty(&attestation).map_err(|e| e.to_string())?;
+    std::fs::write(path, json + "\n").map_err(|e| format!("{}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules;
+
+    fn entries() -> Vec<ManifestEntry> {
+        vec![
+            ("scripts/a.sh".to_string(), b"echo a".to_vec()),
+            ("SKILL.md".to_string(), b"# Skill".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn test_build_records_subjects_and_digest() {
+        let a = build(&entries(), &[], "my-skill", &rules::ruleset_version(), None).unwrap();
+        assert_eq!(a.subjects.len(), 2);
+        assert_eq!(a.subjects[0].path, "SKILL.md");
+        assert_eq!(a.subjects[0].sha256, sha256_hex(b"# Skill"));
+        assert_eq!(a.summary.grade, Grade::A);
+        assert_eq!(a.digest.clone().unwrap(), a.compute_digest().unwrap());
+    }
+
+    #[test]
+    fn test_digest_detects_edits() {
+        let mut a = build(&entries(), &[], "my-skill", &rules::ruleset_version(), None).unwrap();
+        let original = a.digest.clone().unwrap();
+        a.summary.errors = 0;
+        a.target = "other".to_string();
+        assert_ne!(a.compute_digest().unwrap(), original);
+    }
+
+    #[test]
+    fn test_prefixed_subjects_name_their_target() {
+        let subjects = prefixed(Path::new("./skills/pdf"), entries());
+        assert_eq!(subjects[0].0, "skills/pdf/scripts/a.sh");
+        assert_eq!(subjects[1].0, "skills/pdf/SKILL.md");
+    }
+
+    #[test]
+    fn test_chain_links_previous() {
+        let first = build(&entries(), &[], "my-skill", &rules::ruleset_version(), None).unwrap();
+        let second = build(
+            &entries(),
+            &[],
+            "my-skill",
+            &rules::ruleset_version(),
+            first.digest.clone(),
+        )
+        .unwrap();
+        assert_eq!(second.previous_digest, first.digest);
+    }
+}
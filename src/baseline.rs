@@ -0,0 +1,93 @@
+use crate::finding::{report_path, Finding};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Findings accepted as pre-existing. Entries sit under `findings` with a
+/// `fingerprint`, the same shape as a `--format json` report, so a saved
+/// report works as a baseline too.
+#[derive(Debug, Serialize)]
+struct Baseline {
+    version: u32,
+    tool_version: &'static str,
+    findings: Vec<Entry>,
+}
+
+/// Rule and file are kept for people reading the file; matching uses only
+/// the fingerprint.
+#[derive(Debug, Serialize)]
+struct Entry {
+    fingerprint: String,
+    rule_id: String,
+    file: String,
+}
+
+#[derive(Deserialize)]
+struct Saved {
+    findings: Vec<SavedEntry>,
+}
+
+#[derive(Deserialize)]
+struct SavedEntry {
+    fingerprint: String,
+}
+
+/// Fingerprints in a baseline file or saved JSON report.
+pub fn load(path: &Path) -> Result<HashSet<String>, String> {
+    let text =
+        std::fs::read_to_string(path).map_err(|e| format!("baseline {}: {e}", path.display()))?;
+    let saved: Saved =
+        serde_json::from_str(&text).map_err(|e| format!("baseline {}: {e}", path.display()))?;
+    Ok(saved.findings.into_iter().map(|e| e.fingerprint).collect())
+}
+
+/// Write every finding to `path` as the new baseline, one entry per
+/// fingerprint in a stable order. Returns the number of entries.
+pub fn write(path: &Path, findings: &[Finding]) -> Result<usize, String> {
+    let entries: BTreeMap<(String, String), Entry> = findings
+        .iter()
+        .map(|f| {
+            let file = report_path(&f.location.file);
+            let fingerprint = f.fingerprint();
+            (
+                (file.clone(), fingerprint.clone()),
+                Entry {
+                    fingerprint,
+                    rule_id: f.rule_id.clone(),
+                    file,
+                },
+            )
+        })
+        .collect();
+    let baseline = Baseline {
+        version: 1,
+        tool_version: env!("CARGO_PKG_VERSION"),
+        findings: entries.into_values().collect(),
+    };
+    let json = serde_json::to_string_pretty(&baseline).map_err(|e| e.to_string())?;
+    std::fs::write(path, json + "\n").map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(baseline.findings.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Severity;
+
+    #[test]
+    fn test_write_then_load_round_trips_fingerprints() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        let findings = [
+            Finding::test("SL-NET-002", Severity::Error, "b.sh", 3),
+            Finding::test("SL-NET-002", Severity::Error, "a.sh", 1),
+            // Same rule, file and match on another line: one entry.
+            Finding::test("SL-NET-002", Severity::Error, "a.sh", 9),
+        ];
+        assert_eq!(write(&path, &findings).unwrap(), 2);
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.find("a.sh").unwrap() < text.find("b.sh").unwrap());
+        let loaded = load(&path).unwrap();
+        assert!(findings.iter().all(|f| loaded.contains(&f.fingerprint())));
+    }
+}
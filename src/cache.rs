@@ -0,0 +1,285 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// The cache is trimmed back under this size once a write takes it over.
+pub const DEFAULT_MAX_SIZE: u64 = 256 * 1024 * 1024;
+const LOCK_FILE: &str = ".lock";
+/// The running total of entry sizes, so writes need not walk the cache.
+const SIZE_FILE: &str = ".size";
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// `$XDG_CACHE_HOME/skill-issue`, or `~/.cache/skill-issue`.
+pub fn dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))
+        .map(|dir| dir.join("skill-issue"))
+}
+
+/// An advisory lock on a cache directory, released on drop. Readers share
+/// it; writers and garbage collection take it exclusively, so concurrent
+/// jobs never see a half-written entry or one deleted mid-read.
+pub struct Lock(File);
+
+impl Lock {
+    fn open(dir: &Path) -> Result<File, String> {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+        let path = dir.join(LOCK_FILE);
+        OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn shared(dir: &Path) -> Result<Self, String> {
+        let file = Self::open(dir)?;
+        file.lock_shared()
+            .map_err(|e| format!("lock {}: {e}", dir.display()))?;
+        Ok(Lock(file))
+    }
+
+    pub fn exclusive(dir: &Path) -> Result<Self, String> {
+        let file = Self::open(dir)?;
+        file.lock()
+            .map_err(|e| format!("lock {}: {e}", dir.display()))?;
+        Ok(Lock(file))
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = self.0.unlock();
+    }
+}
+
+/// The cached entry at `name` (a path relative to `dir`), if any.
+pub fn read(dir: &Path, name: &str) -> Option<Vec<u8>> {
+    let _lock = Lock::shared(dir).ok()?;
+    std::fs::read(dir.join(name)).ok()
+}
+
+/// Store `bytes` at `name`, replacing any previous entry in one step, and
+/// trim the cache to `DEFAULT_MAX_SIZE` if that took it over.
+pub fn write(dir: &Path, name: &str, bytes: &[u8]) -> Result<(), String> {
+    if store(dir, name, bytes)? > DEFAULT_MAX_SIZE {
+        collect_garbage(dir, DEFAULT_MAX_SIZE)?;
+    }
+    Ok(())
+}
+
+/// `write` without the trim, for callers storing many entries in a row that
+/// collect garbage once at the end. Returns the size of the cache afterwards.
+pub fn store(dir: &Path, name: &str, bytes: &[u8]) -> Result<u64, String> {
+    let _lock = Lock::exclusive(dir)?;
+    let path = dir.join(name);
+    let replaced = std::fs::metadata(&path).map_or(0, |m| m.len());
+    let before = size(dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+    let temp = PathBuf::from(format!(
+        "{}.{}{TEMP_SUFFIX}",
+        path.display(),
+        std::process::id()
+    ));
+    let written = File::create(&temp)
+        .and_then(|mut f| f.write_all(bytes))
+        .and_then(|()| std::fs::rename(&temp, &path));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&temp);
+        return Err(format!("{}: {e}", path.display()));
+    }
+    let after = before.saturating_sub(replaced) + bytes.len() as u64;
+    record_size(dir, after);
+    Ok(after)
+}
+
+/// The size of the cache, from the running total when there is one.
+fn size(dir: &Path) -> u64 {
+    std::fs::read_to_string(dir.join(SIZE_FILE))
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or_else(|| entries(dir).iter().map(|e| e.size).sum())
+}
+
+/// Best effort: a lost total is recounted on the next write.
+fn record_size(dir: &Path, size: u64) {
+    let _ = std::fs::write(dir.join(SIZE_FILE), size.to_string());
+}
+
+/// Whether the cache holds more than `max_size` bytes, without walking it.
+pub fn exceeds(dir: &Path, max_size: u64) -> bool {
+    dir.exists() && Lock::shared(dir).is_ok_and(|_lock| size(dir) > max_size)
+}
+
+/// A file in the cache, for stats and garbage collection.
+struct Entry {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+fn entries(dir: &Path) -> Vec<Entry> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| {
+            e.file_type().is_file() && e.file_name() != LOCK_FILE && e.file_name() != SIZE_FILE
+        })
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some(Entry {
+                path: e.into_path(),
+                size: meta.len(),
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Stats {
+    pub entries: usize,
+    pub bytes: u64,
+    pub oldest: Option<SystemTime>,
+    pub newest: Option<SystemTime>,
+}
+
+pub fn stats(dir: &Path) -> Result<Stats, String> {
+    if !dir.exists() {
+        return Ok(Stats::default());
+    }
+    let _lock = Lock::shared(dir)?;
+    let entries = entries(dir);
+    Ok(Stats {
+        entries: entries.len(),
+        bytes: entries.iter().map(|e| e.size).sum(),
+        oldest: entries.iter().map(|e| e.modified).min(),
+        newest: entries.iter().map(|e| e.modified).max(),
+    })
+}
+
+/// Delete the least recently written entries until the cache holds at most
+/// `max_size` bytes (0 empties it). Returns the entries and bytes removed.
+pub fn collect_garbage(dir: &Path, max_size: u64) -> Result<(usize, u64), String> {
+    if !dir.exists() {
+        return Ok((0, 0));
+    }
+    let _lock = Lock::exclusive(dir)?;
+    Ok(trim(dir, max_size))
+}
+
+/// `collect_garbage` for a caller already holding the exclusive lock.
+/// Leftover temp files from interrupted writes always go.
+fn trim(dir: &Path, max_size: u64) -> (usize, u64) {
+    let mut entries = entries(dir);
+    entries.sort_by_key(|e| e.modified);
+    let mut total: u64 = entries.iter().map(|e| e.size).sum();
+    let (mut removed, mut freed) = (0, 0);
+    for entry in entries {
+        let stale_temp = entry.path.to_string_lossy().ends_with(TEMP_SUFFIX);
+        if (total <= max_size && !stale_temp) || std::fs::remove_file(&entry.path).is_err() {
+            continue;
+        }
+        total -= entry.size;
+        removed += 1;
+        freed += entry.size;
+    }
+    record_size(dir, total);
+    (removed, freed)
+}
+
+/// `512`, `64K`, `100M` or `2G` (binary units) as bytes.
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let (digits, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => s.split_at(i),
+        None => (s, ""),
+    };
+    let invalid = || format!("invalid size {s:?} (expected e.g. 512, 64K, 100M, 2G)");
+    let shift = match unit.to_ascii_uppercase().trim_end_matches(['B', 'I']) {
+        "" => 0,
+        "K" => 10,
+        "M" => 20,
+        "G" => 30,
+        _ => return Err(invalid()),
+    };
+    let n: u64 = digits.parse().map_err(|_| invalid())?;
+    n.checked_mul(1 << shift)
+        .ok_or_else(|| format!("size {s:?} is too large"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512").unwrap(), 512);
+        assert_eq!(parse_size("64K").unwrap(), 64 * 1024);
+        assert_eq!(parse_size("100MiB").unwrap(), 100 * 1024 * 1024);
+        assert_eq!(parse_size("2g").unwrap(), 2 << 30);
+        assert!(parse_size("ten").is_err());
+        assert!(parse_size("5T").is_err());
+    }
+
+    #[test]
+    fn test_concurrent_writers_leave_whole_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::thread::scope(|scope| {
+            for i in 0..8u8 {
+                let dir = dir.path();
+                scope.spawn(move || {
+                    for _ in 0..20 {
+                        write(dir, "http/entry.json", &[i; 4096]).unwrap();
+                        let bytes = read(dir, "http/entry.json").unwrap();
+                        assert_eq!(bytes.len(), 4096);
+                        assert!(bytes.iter().all(|b| *b == bytes[0]));
+                    }
+                });
+            }
+        });
+        assert_eq!(stats(dir.path()).unwrap().entries, 1);
+    }
+
+    #[test]
+    fn test_garbage_collection_removes_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a", "b", "c"] {
+            write(dir.path(), name, &[0; 100]).unwrap();
+            // Distinct modification times on coarse-grained filesystems.
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        std::fs::write(dir.path().join("d.123.tmp"), [0; 10]).unwrap();
+
+        assert_eq!(collect_garbage(dir.path(), 250).unwrap(), (2, 110));
+        assert!(!dir.path().join("a").exists());
+        assert!(dir.path().join("c").exists());
+        assert_eq!(stats(dir.path()).unwrap().bytes, 200);
+        assert_eq!(collect_garbage(dir.path(), 0).unwrap(), (2, 200));
+        assert_eq!(stats(dir.path()).unwrap().entries, 0);
+    }
+
+    #[test]
+    fn test_store_keeps_a_running_size() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(store(dir.path(), "a", &[0; 100]).unwrap(), 100);
+        assert_eq!(store(dir.path(), "a", &[0; 50]).unwrap(), 50);
+        assert_eq!(store(dir.path(), "rules/b", &[0; 30]).unwrap(), 80);
+        assert!(exceeds(dir.path(), 60));
+
+        assert_eq!(collect_garbage(dir.path(), 0).unwrap(), (2, 80));
+        assert!(!exceeds(dir.path(), 60));
+        assert_eq!(store(dir.path(), "c", &[0; 10]).unwrap(), 10);
+        // A lost total is recounted from the entries.
+        std::fs::remove_file(dir.path().join(SIZE_FILE)).unwrap();
+        assert_eq!(store(dir.path(), "d", &[0; 5]).unwrap(), 15);
+        assert_eq!(stats(dir.path()).unwrap().bytes, 15);
+    }
+}
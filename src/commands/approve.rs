@@ -0,0 +1,187 @@
+use crate::approval::{self, Approval, Approvals, Drift, APPROVALS_FILE};
+use crate::config::{CliArgs, Config};
+use crate::finding::Finding;
+use crate::pipeline;
+use crate::signature::{self, ManifestEntry};
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ApproveArgs {
+    /// Skill directory or remote specifier (owner/repo@skill, GitHub URL)
+    #[arg(default_value = ".")]
+    pub target: String,
+
+    /// Compare against the approved snapshot instead of recording one; exits 1
+    /// if the skill changed since approval
+    #[arg(long)]
+    pub check: bool,
+
+    /// Approvals file shared by the team
+    #[arg(long, value_name = "PATH", default_value = APPROVALS_FILE)]
+    pub file: PathBuf,
+
+    /// Who approved the snapshot, recorded alongside it
+    #[arg(long, value_name = "NAME", conflicts_with = "check")]
+    pub by: Option<String>,
+}
+
+pub fn run(approve_args: &ApproveArgs, args: &CliArgs) -> i32 {
+    match approve(approve_args, args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn approve(approve_args: &ApproveArgs, args: &CliArgs) -> Result<i32, String> {
+    let target = &approve_args.target;
+    let path = &approve_args.file;
+    let mut approvals = Approvals::load(path)?;
+    let key = target_key(target, path);
+    let current = current_snapshot(target, path, args, approve_args.by.clone())?;
+
+    if !approve_args.check {
+        println!(
+            "Approved {key}: {} file(s), {} finding(s) recorded in {}",
+            current.files.len(),
+            current.findings.len(),
+            path.display()
+        );
+        approvals.approvals.insert(key, current);
+        approvals.save(path)?;
+        return Ok(0);
+    }
+
+    let Some(approved) = approvals.approvals.get(&key) else {
+        eprintln!(
+            "error: {key} has no approved snapshot in {} (run `skill-issue approve {target}` after review)",
+            path.display()
+        );
+        return Ok(1);
+    };
+    let drift = approval::drift(approved, &current);
+    if drift.is_empty() {
+        println!("{key} matches the snapshot {}", approved_when(approved));
+        return Ok(0);
+    }
+    print!("{}", format_drift(&drift));
+    eprintln!(
+        "error: {key} changed since the snapshot {} (review it, then run `skill-issue approve {target}`)",
+        approved_when(approved)
+    );
+    Ok(1)
+}
+
+/// The target's files and findings, leaving out the approvals file when it
+/// sits inside the skill, since approving rewrites it.
+fn current_snapshot(
+    target: &str,
+    approvals_file: &Path,
+    args: &CliArgs,
+    approved_by: Option<String>,
+) -> Result<Approval, String> {
+    let mut entries: Vec<ManifestEntry> = super::target_entries(target, args)?;
+    let (mut findings, ruleset_version) = scan(target, args)?;
+    if let Some(own) = path_within(approvals_file, Path::new(target)) {
+        entries.retain(|(p, _)| *p != own);
+        findings.retain(|f| signature::manifest_path(&f.location.file) != own);
+    }
+    Ok(approval::snapshot(
+        &entries,
+        &findings,
+        ruleset_version,
+        approved_by,
+    ))
+}
+
+/// The target's findings and the version of the rules that found them.
+fn scan(target: &str, args: &CliArgs) -> Result<(Vec<Finding>, String), String> {
+    let mut scan_args = args.clone();
+    scan_args.command = None;
+    scan_args.baseline = None;
+    scan_args.update_baseline = false;
+    if Path::new(target).exists() {
+        scan_args.path = PathBuf::from(target);
+    } else {
+        scan_args.remote = Some(target.to_string());
+    }
+    let config = Config::from_args_and_file(scan_args, None);
+    let outcome = pipeline::run(&config)?;
+    Ok((outcome.findings, outcome.provenance.ruleset_version))
+}
+
+/// Local targets are keyed by their path relative to the approvals file, so
+/// the file means the same thing in every checkout; remote ones by specifier.
+fn target_key(target: &str, approvals_file: &Path) -> String {
+    if !Path::new(target).exists() {
+        return target.to_string();
+    }
+    let base = match approvals_file.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    match path_within(Path::new(target), base) {
+        Some(relative) if relative.is_empty() => ".".to_string(),
+        Some(relative) => relative,
+        None => Path::new(target)
+            .canonicalize()
+            .map_or_else(|_| target.to_string(), |p| p.display().to_string()),
+    }
+}
+
+/// `path` relative to the directory `dir`, when it lies inside it.
+fn path_within(path: &Path, dir: &Path) -> Option<String> {
+    let dir = dir.canonicalize().ok()?;
+    let path = match path.canonicalize() {
+        Ok(p) => p,
+        // The approvals file may not exist yet.
+        Err(_) => path.parent()?.canonicalize().ok()?.join(path.file_name()?),
+    };
+    path.strip_prefix(&dir).ok().map(signature::manifest_path)
+}
+
+fn approved_when(approval: &Approval) -> String {
+    match approval.approved_by {
+        Some(ref by) => format!("approved on {} by {by}", approval.approved_at),
+        None => format!("approved on {}", approval.approved_at),
+    }
+}
+
+fn format_drift(drift: &Drift) -> String {
+    let mut out = String::new();
+    for (label, paths) in [
+        ("modified", &drift.modified),
+        ("added", &drift.added),
+        ("removed", &drift.removed),
+    ] {
+        for path in paths {
+            out.push_str(&format!("  {label:<9} {path}\n"));
+        }
+    }
+    for f in &drift.new_findings {
+        out.push_str(&format!("  new finding {} in {}\n", f.rule_id, f.file));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_key_is_relative_to_approvals_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("skills/a")).unwrap();
+        let file = dir.path().join(APPROVALS_FILE);
+        let target = dir.path().join("skills/a");
+        assert_eq!(target_key(&target.to_string_lossy(), &file), "skills/a");
+        assert_eq!(target_key(&dir.path().to_string_lossy(), &file), ".");
+        assert_eq!(target_key("owner/repo@skill", &file), "owner/repo@skill");
+        assert_eq!(
+            path_within(&file, dir.path()).as_deref(),
+            Some(APPROVALS_FILE)
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::cache;
+use std::time::SystemTime;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheCommand,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum CacheCommand {
+    /// Show where the cache is and how much it holds
+    Stats,
+    /// Delete cached entries, oldest first, down to --max-size (default: all)
+    Clean(CleanArgs),
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct CleanArgs {
+    /// Keep the most recent entries up to this size (e.g. 100M, 1G)
+    #[arg(long, value_name = "SIZE", value_parser = cache::parse_size)]
+    pub max_size: Option<u64>,
+}
+
+pub fn run(cache_args: &CacheArgs) -> i32 {
+    let Some(dir) = cache::dir() else {
+        eprintln!("error: cannot locate a cache directory (set XDG_CACHE_HOME or HOME)");
+        return 2;
+    };
+    let result = match &cache_args.command {
+        CacheCommand::Stats => cache::stats(&dir).map(|stats| {
+            println!("Cache: {}", dir.display());
+            println!("Entries: {}", stats.entries);
+            println!("Size: {}", format_size(stats.bytes));
+            if let (Some(oldest), Some(newest)) = (stats.oldest, stats.newest) {
+                println!("Oldest: {}", format_time(oldest));
+                println!("Newest: {}", format_time(newest));
+            }
+        }),
+        CacheCommand::Clean(clean_args) => {
+            cache::collect_garbage(&dir, clean_args.max_size.unwrap_or(0)).map(
+                |(removed, freed)| {
+                    println!(
+                        "Removed {removed} entr{} ({}) from {}",
+                        if removed == 1 { "y" } else { "ies" },
+                        format_size(freed),
+                        dir.display()
+                    );
+                },
+            )
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = "bytes";
+    for u in UNITS {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = u;
+    }
+    if unit == "bytes" {
+        format!("{bytes} bytes")
+    } else {
+        format!("{size:.1} {unit}")
+    }
+}
+
+fn format_time(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time)
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 bytes");
+        assert_eq!(format_size(1023), "1023 bytes");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(300 * 1024 * 1024), "300.0 MiB");
+    }
+}
@@ -0,0 +1,161 @@
+use crate::config::{CliArgs, Config, ConfigFile, OutputFormat};
+use crate::coverage;
+use crate::engine::Engine;
+use crate::finding::report_path;
+use crate::output;
+use crate::pipeline;
+use crate::provenance::Provenance;
+use crate::scanner;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct CheckFileArgs {
+    /// File to check
+    pub file: PathBuf,
+
+    /// Skill directory the file belongs to, for its config file and report path [default: the nearest parent holding a skill marker]
+    #[arg(long, value_name = "DIR")]
+    pub skill: Option<PathBuf>,
+
+    /// Output format
+    #[arg(short, long, default_value = "stylish")]
+    pub format: OutputFormat,
+}
+
+/// Check one file with the rules that apply to it, for editor on-save hooks.
+/// Checks that need the whole skill (duplicate names, plugin references,
+/// signatures) are left to a full scan.
+pub fn run(check_args: &CheckFileArgs, args: &CliArgs) -> i32 {
+    let started_at = chrono::Utc::now();
+    let timer = Instant::now();
+    let markers = crate::config::skill_markers(&args.skill_markers, &[]);
+    let (file, root) = match check_args.skill {
+        // Editors pass absolute paths; compare the two however they were given.
+        Some(ref skill) => match (
+            std::fs::canonicalize(&check_args.file),
+            std::fs::canonicalize(skill),
+        ) {
+            (Ok(file), Ok(root)) if file.starts_with(&root) => (file, root),
+            (Ok(_), Ok(_)) => {
+                eprintln!(
+                    "error: {} is not inside {}",
+                    check_args.file.display(),
+                    skill.display()
+                );
+                return 2;
+            }
+            (Err(e), _) => {
+                eprintln!("error: {}: {e}", check_args.file.display());
+                return 2;
+            }
+            (_, Err(e)) => {
+                eprintln!("error: {}: {e}", skill.display());
+                return 2;
+            }
+        },
+        None => (
+            check_args.file.clone(),
+            skill_dir(&check_args.file, &markers),
+        ),
+    };
+
+    let mut scan_args = args.clone();
+    scan_args.command = None;
+    scan_args.path = root.clone();
+    scan_args.format = check_args.format.clone();
+    let config_path = args
+        .config
+        .clone()
+        .unwrap_or_else(|| root.join(".skill-issue.toml"));
+    let (config_file, problem) = match pipeline::read_config_file(&config_path) {
+        Ok(file) => (file, None),
+        Err(diagnostic) => (None, Some(diagnostic)),
+    };
+    let config_file = match args.config {
+        Some(_) => config_file,
+        None => config_file.map(ConfigFile::from_target),
+    };
+    let config = Config::from_args_and_file(scan_args, config_file);
+
+    let scan = match scanner::scan_file(&file, &root) {
+        Ok(scan) => scan,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+    let registry = match pipeline::build_registry(&config) {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+    let engine = Engine::new(&config, &registry);
+    if !args.quiet
+        && scan.skipped.is_empty()
+        && !scan.files.iter().any(|f| engine.has_active_rules(f))
+    {
+        eprintln!("note: no enabled rule checks {}", check_args.file.display());
+    }
+    let findings = engine.run(&scan.files, coverage::skipped_findings(&scan.skipped));
+    let diagnostics: Vec<_> = problem
+        .into_iter()
+        .chain(registry.diagnostics().iter().cloned())
+        .collect();
+
+    let provenance = Provenance::new(
+        report_path(&root),
+        None,
+        &registry,
+        started_at,
+        timer.elapsed(),
+    );
+    let report = output::format_findings(
+        &config.format,
+        &findings,
+        &root,
+        &scanner::skill_roots(&scan.files, &config.skill_markers),
+        &provenance,
+        &diagnostics,
+        config.columns,
+        if config.redact { &[] } else { &scan.files },
+    );
+    println!("{report}");
+    Engine::exit_code(&findings, config.error_on)
+}
+
+/// The nearest directory above `file` holding a skill marker, or else the
+/// file's own directory.
+fn skill_dir(file: &Path, markers: &[String]) -> PathBuf {
+    let parent = match file.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    parent
+        .ancestors()
+        .find(|dir| markers.iter().any(|m| dir.join(m).is_file()))
+        .unwrap_or(parent)
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skill_dir_is_nearest_marker_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("skills/pdf");
+        std::fs::create_dir_all(skill.join("scripts")).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "# PDF\n").unwrap();
+        let markers = vec!["SKILL.md".to_string()];
+
+        assert_eq!(skill_dir(&skill.join("scripts/run.sh"), &markers), skill);
+        assert_eq!(skill_dir(&skill.join("SKILL.md"), &markers), skill);
+        let loose = dir.path().join("notes/todo.md");
+        assert_eq!(skill_dir(&loose, &markers), dir.path().join("notes"));
+        assert_eq!(skill_dir(Path::new("todo.md"), &markers), Path::new("."));
+    }
+}
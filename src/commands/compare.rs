@@ -0,0 +1,87 @@
+use crate::config::CliArgs;
+use crate::similarity::{self, Comparison, FileStatus};
+use colored::Colorize;
+
+/// Similarity at or above which two skills are reported as a likely clone.
+const CLONE_THRESHOLD: f64 = 0.8;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct CompareArgs {
+    /// Original skill (directory or remote specifier)
+    pub a: String,
+
+    /// Skill to compare against the original
+    pub b: String,
+
+    /// Print the comparison as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(compare_args: &CompareArgs, args: &CliArgs) -> i32 {
+    let load =
+        |target: &str| super::target_entries(target, args).map_err(|e| format!("{target}: {e}"));
+    let (a, b) = match load(&compare_args.a).and_then(|a| Ok((a, load(&compare_args.b)?))) {
+        Ok(pair) => pair,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+
+    let comparison = similarity::compare(&a, &b);
+    if compare_args.json {
+        match serde_json::to_string_pretty(&comparison) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return 2;
+            }
+        }
+    } else {
+        println!("{}", format_text(&comparison));
+    }
+    0
+}
+
+fn format_text(comparison: &Comparison) -> String {
+    let mut out = Vec::new();
+    for f in &comparison.files {
+        let line = match f.status {
+            FileStatus::Identical => format!("  identical  {}", f.path),
+            FileStatus::Moved => format!(
+                "  moved      {} (from {})",
+                f.path,
+                f.original_path.as_deref().unwrap_or("?")
+            ),
+            FileStatus::Modified => format!(
+                "  modified   {} ({:.0}% similar, +{} -{} lines)",
+                f.path,
+                f.similarity * 100.0,
+                f.lines_added,
+                f.lines_removed
+            )
+            .yellow()
+            .to_string(),
+            FileStatus::Added => format!("  added      {} (+{} lines)", f.path, f.lines_added)
+                .red()
+                .to_string(),
+            FileStatus::Removed => format!("  removed    {}", f.path),
+        };
+        out.push(line);
+    }
+
+    let percent = format!("{:.0}%", comparison.similarity * 100.0);
+    let verdict = if comparison.similarity >= 1.0 {
+        "skills are identical".green().to_string()
+    } else if comparison.similarity >= CLONE_THRESHOLD {
+        "likely a clone; review the modified and added files"
+            .red()
+            .bold()
+            .to_string()
+    } else {
+        "skills are substantially different".to_string()
+    };
+    out.push(format!("\nSimilarity: {percent} — {verdict}"));
+    out.join("\n")
+}
@@ -0,0 +1,214 @@
+use crate::config::{CliArgs, Config};
+use crate::pipeline;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Snapshot of the rule IDs each sample must trigger, at the corpus root.
+const EXPECTED_FILE: &str = "expected.toml";
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct CorpusCheckArgs {
+    /// Corpus directory: `<group>/<sample>/` skill directories plus expected.toml
+    #[arg(default_value = "tests/corpus")]
+    pub dir: PathBuf,
+
+    /// Rewrite expected.toml with the rule IDs that fire now
+    #[arg(long)]
+    pub update: bool,
+}
+
+/// `expected.toml`: sample path (relative to the corpus) to sorted rule IDs.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Expected {
+    #[serde(default)]
+    samples: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// How one sample's findings differ from its snapshot.
+#[derive(Debug, PartialEq)]
+struct Mismatch {
+    sample: String,
+    missing: Vec<String>,
+    unexpected: Vec<String>,
+}
+
+pub fn run(corpus_args: &CorpusCheckArgs, args: &CliArgs) -> i32 {
+    match check(corpus_args, args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn check(corpus_args: &CorpusCheckArgs, args: &CliArgs) -> Result<i32, String> {
+    let expected_path = corpus_args.dir.join(EXPECTED_FILE);
+    let expected: Expected = if expected_path.exists() {
+        let text = std::fs::read_to_string(&expected_path)
+            .map_err(|e| format!("{}: {e}", expected_path.display()))?;
+        toml::from_str(&text).map_err(|e| format!("{}: {e}", expected_path.display()))?
+    } else if corpus_args.update {
+        Expected::default()
+    } else {
+        return Err(format!(
+            "{} not found (run with --update to create it)",
+            expected_path.display()
+        ));
+    };
+
+    let mut actual = Expected::default();
+    for sample in samples(&corpus_args.dir)? {
+        let rules = fired_rules(&corpus_args.dir.join(&sample), args)?;
+        actual.samples.insert(sample, rules);
+    }
+
+    if corpus_args.update {
+        let text = toml::to_string(&actual).map_err(|e| e.to_string())?;
+        std::fs::write(&expected_path, text)
+            .map_err(|e| format!("{}: {e}", expected_path.display()))?;
+        println!(
+            "Wrote {} sample(s) to {}",
+            actual.samples.len(),
+            expected_path.display()
+        );
+        return Ok(0);
+    }
+
+    let mismatches = compare(&expected, &actual);
+    for m in &mismatches {
+        println!("FAIL {}", m.sample);
+        for id in &m.missing {
+            println!("  missing     {id}");
+        }
+        for id in &m.unexpected {
+            println!("  unexpected  {id}");
+        }
+    }
+    let matched = actual
+        .samples
+        .keys()
+        .filter(|name| !mismatches.iter().any(|m| &m.sample == *name))
+        .count();
+    println!(
+        "{matched} of {} sample(s) match {}",
+        actual.samples.len(),
+        expected_path.display()
+    );
+    Ok(if mismatches.is_empty() { 0 } else { 1 })
+}
+
+/// Sample directories, two levels down (`malicious/reverse-shell`), as
+/// `/`-separated paths relative to the corpus.
+fn samples(dir: &Path) -> Result<Vec<String>, String> {
+    let subdirs = |dir: &Path| -> Result<Vec<String>, String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .map_err(|e| format!("{}: {e}", dir.display()))?
+            .filter_map(Result::ok)
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        Ok(names)
+    };
+    let mut samples = Vec::new();
+    for group in subdirs(dir)? {
+        for sample in subdirs(&dir.join(&group))? {
+            samples.push(format!("{group}/{sample}"));
+        }
+    }
+    Ok(samples)
+}
+
+/// Rule IDs reported for one sample, with the global scan options.
+fn fired_rules(sample: &Path, args: &CliArgs) -> Result<BTreeSet<String>, String> {
+    let mut scan_args = args.clone();
+    scan_args.command = None;
+    scan_args.path = sample.to_path_buf();
+    let config = Config::from_args_and_file(scan_args, None);
+    let outcome = pipeline::run(&config).map_err(|e| format!("{}: {e}", sample.display()))?;
+    Ok(outcome.findings.into_iter().map(|f| f.rule_id).collect())
+}
+
+/// Samples whose rule IDs differ from the snapshot. A sample on only one side
+/// counts as a mismatch, so added and removed samples need `--update` too.
+fn compare(expected: &Expected, actual: &Expected) -> Vec<Mismatch> {
+    let names: BTreeSet<&String> = expected
+        .samples
+        .keys()
+        .chain(actual.samples.keys())
+        .collect();
+    let none = BTreeSet::new();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let want = expected.samples.get(name).unwrap_or(&none);
+            let got = actual.samples.get(name).unwrap_or(&none);
+            let missing: Vec<String> = want.difference(got).cloned().collect();
+            let unexpected: Vec<String> = got.difference(want).cloned().collect();
+            let listed = expected.samples.contains_key(name) && actual.samples.contains_key(name);
+            (!missing.is_empty() || !unexpected.is_empty() || !listed).then(|| Mismatch {
+                sample: name.clone(),
+                missing,
+                unexpected,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(entries: &[(&str, &[&str])]) -> Expected {
+        Expected {
+            samples: entries
+                .iter()
+                .map(|(name, ids)| {
+                    (
+                        name.to_string(),
+                        ids.iter().map(|id| id.to_string()).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_compare_reports_missing_and_unexpected_rules() {
+        let expected = snapshot(&[
+            ("benign/docs", &[]),
+            ("malicious/shell", &["SL-EXEC-011", "SL-NET-008"]),
+            ("malicious/removed", &["SL-INJ-001"]),
+        ]);
+        let actual = snapshot(&[
+            ("benign/docs", &[]),
+            ("malicious/shell", &["SL-EXEC-011", "SL-NET-001"]),
+        ]);
+        assert_eq!(
+            compare(&expected, &actual),
+            [
+                Mismatch {
+                    sample: "malicious/removed".to_string(),
+                    missing: vec!["SL-INJ-001".to_string()],
+                    unexpected: vec![],
+                },
+                Mismatch {
+                    sample: "malicious/shell".to_string(),
+                    missing: vec!["SL-NET-008".to_string()],
+                    unexpected: vec!["SL-NET-001".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expected_round_trips_through_toml() {
+        let expected = snapshot(&[("benign/docs", &[]), ("malicious/shell", &["SL-EXEC-011"])]);
+        let text = toml::to_string(&expected).unwrap();
+        assert!(text.contains("\"malicious/shell\" = [\"SL-EXEC-011\"]"));
+        let parsed: Expected = toml::from_str(&text).unwrap();
+        assert_eq!(parsed.samples, expected.samples);
+    }
+}
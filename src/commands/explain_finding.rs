@@ -0,0 +1,191 @@
+use crate::config::CliArgs;
+use crate::finding::{report_path, Finding};
+use crate::remote;
+use crate::rules::{self, RuleRegistry};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ExplainFindingArgs {
+    /// Fingerprint of the finding, from `--format json` or SARIF `partialFingerprints`
+    pub fingerprint: String,
+
+    /// Saved JSON report containing the finding
+    #[arg(long, value_name = "REPORT")]
+    pub from: PathBuf,
+
+    /// Lines of source to show before and after the finding
+    #[arg(long, default_value_t = 3)]
+    pub context: usize,
+}
+
+/// The parts of a saved JSON report needed to find the source again.
+#[derive(Deserialize)]
+struct SavedReport {
+    skill_path: String,
+    findings: Vec<Finding>,
+    provenance: Option<SavedProvenance>,
+}
+
+#[derive(Deserialize)]
+struct SavedProvenance {
+    commit: Option<String>,
+}
+
+pub fn run(explain_args: &ExplainFindingArgs, args: &CliArgs) -> i32 {
+    match explain(explain_args, args) {
+        Ok(text) => {
+            print!("{text}");
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn explain(explain_args: &ExplainFindingArgs, args: &CliArgs) -> Result<String, String> {
+    let path = &explain_args.from;
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let report: SavedReport = serde_json::from_str(&text)
+        .map_err(|e| format!("{}: not a JSON report: {e}", path.display()))?;
+    let commit = report.provenance.and_then(|p| p.commit);
+    let finding = report
+        .findings
+        .into_iter()
+        .find(|f| f.fingerprint() == explain_args.fingerprint)
+        .ok_or_else(|| {
+            format!(
+                "no finding with fingerprint {} in {}",
+                explain_args.fingerprint,
+                path.display()
+            )
+        })?;
+
+    let mut out = format!(
+        "{} {} [{}]\n{}:{}:{}\n  {}\n",
+        finding.rule_id,
+        finding.rule_name,
+        finding.severity,
+        report_path(&finding.location.file),
+        finding.location.line,
+        finding.location.column,
+        finding.message
+    );
+
+    if commit.is_some() && args.offline {
+        return Err(format!(
+            "{} is a report of a remote scan; showing its source needs network access, so drop --offline",
+            path.display()
+        ));
+    }
+    match source(
+        &report.skill_path,
+        commit.as_deref(),
+        &finding.location.file,
+        args,
+    ) {
+        Ok(content) => {
+            out.push('\n');
+            out.push_str(&context(
+                &content,
+                finding.location.line,
+                explain_args.context,
+            ));
+            let line = content.lines().nth(finding.location.line.saturating_sub(1));
+            let evidence = finding.matched_text.lines().next().unwrap_or_default();
+            if !line.is_some_and(|l| l.contains(evidence)) {
+                out.push_str("\nnote: the source has changed since the report was written\n");
+            }
+        }
+        Err(e) => out.push_str(&format!("\nnote: source not available: {e}\n")),
+    }
+
+    let mut registry = RuleRegistry::new();
+    registry.load_defaults();
+    if let Some(dir) = &args.rules_dir {
+        registry.load_dir(dir)?;
+    }
+    if let Some(rule) = rules::catalog(&registry)
+        .into_iter()
+        .find(|r| r.id == finding.rule_id)
+    {
+        if !rule.description.is_empty() {
+            out.push_str(&format!("\nAbout {}:\n  {}\n", rule.id, rule.description));
+        }
+        if !rule.examples.is_empty() {
+            out.push_str("\nExamples:\n");
+            for example in &rule.examples {
+                out.push_str(&format!("  {example}\n"));
+            }
+        }
+        if !rule.remediation.is_empty() {
+            out.push_str(&format!("\nRemediation:\n  {}\n", rule.remediation));
+        }
+    }
+    Ok(out)
+}
+
+/// Current contents of a reported file: read from disk for local reports, or
+/// fetched on its own at the reported commit for remote ones.
+fn source(
+    skill_path: &str,
+    commit: Option<&str>,
+    file: &Path,
+    args: &CliArgs,
+) -> Result<String, String> {
+    let Some(commit) = commit else {
+        let path = Path::new(skill_path).join(file);
+        return std::fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()));
+    };
+    let markers = crate::config::skill_markers(&args.skill_markers, &[]);
+    remote::fetch_reported_file(
+        skill_path,
+        commit,
+        file,
+        args.github_token.as_deref(),
+        &markers,
+        args.verbose,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Numbered lines around `line` (1-based), with the finding's line marked.
+fn context(content: &str, line: usize, radius: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    if line == 0 || line > lines.len() {
+        return format!("(line {line} is past the end of the file)\n");
+    }
+    let first = line.saturating_sub(radius).max(1);
+    let last = (line + radius).min(lines.len());
+    let width = last.to_string().len();
+    (first..=last)
+        .map(|n| {
+            let marker = if n == line { '>' } else { ' ' };
+            format!("{marker} {n:>width$} | {}\n", lines[n - 1])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_marks_line_and_clamps() {
+        let content = (1..=12)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            context(&content, 2, 2),
+            "  1 | line 1\n> 2 | line 2\n  3 | line 3\n  4 | line 4\n"
+        );
+        assert_eq!(
+            context(&content, 11, 1),
+            "  10 | line 10\n> 11 | line 11\n  12 | line 12\n"
+        );
+        assert!(context(&content, 13, 1).contains("past the end"));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::config::{CliArgs, Config};
+use crate::finding::{report_path, Finding, Severity};
+use crate::{history, pipeline, rules};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const ISSUES_URL: &str = "https://github.com/daviddrummond95/skill-issue-cli/issues/new";
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct FeedbackArgs {
+    /// Fingerprint of the finding, from `--format json` or SARIF `partialFingerprints`
+    pub fingerprint: String,
+
+    /// Why the finding is a false positive
+    #[arg(long)]
+    pub reason: String,
+
+    /// Look the finding up in a saved JSON report instead of rescanning
+    #[arg(long, value_name = "PATH", conflicts_with = "target")]
+    pub report: Option<PathBuf>,
+
+    /// Skill directory or remote specifier to rescan for the finding
+    #[arg(long, default_value = ".")]
+    pub target: String,
+
+    /// Append the record here instead of the default feedback file
+    #[arg(long, value_name = "PATH")]
+    pub feedback_file: Option<PathBuf>,
+
+    /// Also print (and try to open) a prefilled GitHub issue
+    #[arg(long)]
+    pub issue: bool,
+}
+
+/// One false-positive report, as a line of the feedback file.
+#[derive(Debug, Serialize)]
+struct FeedbackRecord {
+    recorded_at: String,
+    fingerprint: String,
+    rule_id: String,
+    rule_name: String,
+    severity: Severity,
+    file: String,
+    line: usize,
+    message: String,
+    matched_text: String,
+    reason: String,
+    tool_version: &'static str,
+    ruleset_version: String,
+}
+
+#[derive(Deserialize)]
+struct SavedReport {
+    findings: Vec<Finding>,
+    provenance: Option<SavedProvenance>,
+}
+
+#[derive(Deserialize)]
+struct SavedProvenance {
+    ruleset_version: String,
+}
+
+pub fn run(feedback_args: &FeedbackArgs, args: &CliArgs) -> i32 {
+    match record(feedback_args, args) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn record(feedback_args: &FeedbackArgs, args: &CliArgs) -> Result<(), String> {
+    let (findings, ruleset_version) = match feedback_args.report {
+        Some(ref path) => {
+            let text =
+                std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+            let report = serde_json::from_str::<SavedReport>(&text)
+                .map_err(|e| format!("{}: not a JSON report: {e}", path.display()))?;
+            let version = report.provenance.map(|p| p.ruleset_version);
+            (
+                report.findings,
+                version.unwrap_or_else(rules::ruleset_version),
+            )
+        }
+        None => scan(&feedback_args.target, args)?,
+    };
+    let mut finding = findings
+        .into_iter()
+        .find(|f| f.fingerprint() == feedback_args.fingerprint)
+        .ok_or_else(|| format!("no finding with fingerprint {}", feedback_args.fingerprint))?;
+    // Feedback is meant to be shared; never store a live secret in it.
+    finding.redact();
+
+    let record = FeedbackRecord {
+        recorded_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        fingerprint: feedback_args.fingerprint.clone(),
+        rule_id: finding.rule_id,
+        rule_name: finding.rule_name,
+        severity: finding.severity,
+        file: report_path(&finding.location.file),
+        line: finding.location.line,
+        message: finding.message,
+        matched_text: finding.matched_text,
+        reason: feedback_args.reason.clone(),
+        tool_version: env!("CARGO_PKG_VERSION"),
+        ruleset_version,
+    };
+
+    let path = match feedback_args.feedback_file {
+        Some(ref p) => p.clone(),
+        None => history::data_dir()
+            .map(|dir| dir.join("feedback.jsonl"))
+            .ok_or("cannot locate a data directory; pass --feedback-file")?,
+    };
+    append(&path, &record)?;
+    println!(
+        "Recorded false positive for {} in {}",
+        record.rule_id,
+        path.display()
+    );
+
+    if feedback_args.issue {
+        let url = issue_url(&record);
+        println!("Open an issue to share it: {url}");
+        open_browser(&url);
+    }
+    Ok(())
+}
+
+/// Findings for a skill directory or remote specifier, with the global scan
+/// options, and the version of the rules that found them.
+fn scan(target: &str, args: &CliArgs) -> Result<(Vec<Finding>, String), String> {
+    let mut scan_args = args.clone();
+    scan_args.command = None;
+    if Path::new(target).exists() {
+        scan_args.path = PathBuf::from(target);
+    } else {
+        scan_args.remote = Some(target.to_string());
+    }
+    let config = Config::from_args_and_file(scan_args, None);
+    let outcome = pipeline::run(&config)?;
+    Ok((outcome.findings, outcome.provenance.ruleset_version))
+}
+
+fn append(path: &Path, record: &FeedbackRecord) -> Result<(), String> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+    let line = serde_json::to_string(record).map_err(|e| e.to_string())?;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut f| writeln!(f, "{line}"))
+        .map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// A new-issue link with the title, body and label filled in.
+fn issue_url(record: &FeedbackRecord) -> String {
+    let title = format!("False positive: {} {}", record.rule_id, record.rule_name);
+    let body = format!(
+        "**Rule:** {} ({})\n**Message:** {}\n**Matched text:**\n```\n{}\n```\n**Why it is a false positive:** {}\n\nskill-issue {}, rule set {}, fingerprint `{}`\n",
+        record.rule_id,
+        record.severity,
+        record.message,
+        record.matched_text,
+        record.reason,
+        record.tool_version,
+        record.ruleset_version,
+        record.fingerprint
+    );
+    format!(
+        "{ISSUES_URL}?labels=false-positive&title={}&body={}",
+        percent_encode(&title),
+        percent_encode(&body)
+    )
+}
+
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Best effort: the URL has already been printed.
+fn open_browser(url: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut c = std::process::Command::new("cmd");
+        c.args(["/C", "start", ""]);
+        c
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    let _ = command
+        .arg(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_url_is_prefilled_and_encoded() {
+        let record = FeedbackRecord {
+            recorded_at: "2026-05-01T00:00:00Z".to_string(),
+            fingerprint: "0123456789abcdef".to_string(),
+            rule_id: "SL-NET-001".to_string(),
+            rule_name: "Outbound HTTP".to_string(),
+            severity: Severity::Warning,
+            file: "SKILL.md".to_string(),
+            line: 3,
+            message: "curl call".to_string(),
+            matched_text: "curl https://example.com".to_string(),
+            reason: "documented install step & pinned".to_string(),
+            tool_version: "0.1.0",
+            ruleset_version: "0.1.0+abc".to_string(),
+        };
+        let url = issue_url(&record);
+        assert!(url.starts_with(&format!(
+            "{ISSUES_URL}?labels=false-positive&title=False%20positive%3A%20SL-NET-001"
+        )));
+        assert!(url.contains("pinned"));
+        assert!(url.contains("%26"));
+        assert!(!url[ISSUES_URL.len()..].contains(' '));
+    }
+}
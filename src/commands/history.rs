@@ -0,0 +1,102 @@
+use crate::config::CliArgs;
+use crate::history::{self, History};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct HistoryArgs {
+    /// Skill directory or remote specifier whose history to show
+    #[arg(default_value = ".")]
+    pub target: String,
+
+    /// Number of most recent scans to show
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+}
+
+pub fn run(history_args: &HistoryArgs, args: &CliArgs) -> i32 {
+    let result = history::db_path(args.history_db.as_deref()).and_then(|p| History::open(&p));
+    let db = match result {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("error: failed to open scan history: {e}");
+            return 2;
+        }
+    };
+
+    match report(
+        &db,
+        &history::target_key(&history_args.target),
+        history_args.limit,
+    ) {
+        Ok(out) => {
+            println!("{out}");
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn report(db: &History, target: &str, limit: usize) -> Result<String, String> {
+    let scans = db.scans(target, limit)?;
+    if scans.is_empty() {
+        return Ok(format!("No scan history for {target}"));
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            "Scanned", "Commit", "Grade", "Risk", "Critical", "Errors", "Warnings", "Info",
+            "Change",
+        ]);
+
+    let mut regressions = Vec::new();
+    let mut previous: Option<(u32, _)> = None;
+    for scan in &scans {
+        let rules = db.rule_ids(scan.id)?;
+        let change = match &previous {
+            Some((prev_score, prev_rules)) => {
+                let added = history::new_rules(prev_rules, &rules);
+                if !added.is_empty() {
+                    regressions.push(format!("{}: new {}", scan.scanned_at, added.join(", ")));
+                }
+                format!("{:+}", scan.risk_score as i64 - *prev_score as i64)
+            }
+            None => String::new(),
+        };
+        table.add_row(vec![
+            scan.scanned_at.clone(),
+            scan.commit
+                .as_deref()
+                .map(|c| c.chars().take(12).collect())
+                .unwrap_or_default(),
+            scan.grade.clone(),
+            scan.risk_score.to_string(),
+            scan.critical.to_string(),
+            scan.errors.to_string(),
+            scan.warnings.to_string(),
+            scan.info.to_string(),
+            change,
+        ]);
+        previous = Some((scan.risk_score, rules));
+    }
+
+    let scores: Vec<u32> = scans.iter().map(|s| s.risk_score).collect();
+    let mut out = format!(
+        "History for {target}\n{table}\nRisk trend: {}",
+        history::sparkline(&scores)
+    );
+    if regressions.is_empty() {
+        out.push_str("\nNo regressions.");
+    } else {
+        out.push_str("\nRegressions:");
+        for r in regressions {
+            out.push_str(&format!("\n  {r}"));
+        }
+    }
+    Ok(out)
+}
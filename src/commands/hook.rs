@@ -0,0 +1,365 @@
+use crate::config::{self, CliArgs, Config};
+use crate::pipeline;
+use crate::remote::RemoteTarget;
+use crate::scanner;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Claude Code PreToolUse hook integration.
+///
+/// Reads the hook payload from stdin, finds any skill the tool call refers to
+/// (a skill directory, a SKILL.md path, a GitHub URL in an install command, or
+/// a `Skill` tool invocation), scans it, and prints the hook response JSON.
+#[derive(clap::Args, Debug, Clone)]
+pub struct HookArgs {
+    /// Emit an explicit "allow" decision when every referenced skill scans clean.
+    /// By default clean scans defer to Claude Code's normal permission prompt.
+    #[arg(long)]
+    pub allow_clean: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct HookPayload {
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    #[serde(default)]
+    hook_event_name: Option<String>,
+    #[serde(default)]
+    tool_name: Option<String>,
+    #[serde(default)]
+    tool_input: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct HookResponse {
+    #[serde(rename = "hookSpecificOutput")]
+    hook_specific_output: HookSpecificOutput,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HookSpecificOutput {
+    hook_event_name: String,
+    permission_decision: &'static str,
+    permission_decision_reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SkillTarget {
+    Local(PathBuf),
+    Remote(String),
+}
+
+impl std::fmt::Display for SkillTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkillTarget::Local(p) => write!(f, "{}", p.display()),
+            SkillTarget::Remote(spec) => write!(f, "{spec}"),
+        }
+    }
+}
+
+/// Tool input keys that may hold a path to a skill directory or SKILL.md.
+const PATH_KEYS: &[&str] = &["skill_path", "path", "file_path"];
+
+pub fn run(hook_args: &HookArgs, args: &CliArgs) -> i32 {
+    let mut input = String::new();
+    if let Err(e) = std::io::stdin().read_to_string(&mut input) {
+        eprintln!("error: failed to read hook payload: {e}");
+        return 1;
+    }
+
+    let payload: HookPayload = match serde_json::from_str(&input) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("error: invalid hook payload: {e}");
+            return 1;
+        }
+    };
+
+    // Only an explicitly passed config is honored: a skill under review must
+    // not be able to ship its own `.skill-issue.toml` that silences rules.
+    let config_file = args.config.as_deref().and_then(pipeline::load_config_file);
+    let markers = config::skill_markers(
+        &args.skill_markers,
+        config_file
+            .as_ref()
+            .map_or(&[], |f| &f.settings.skill_markers),
+    );
+
+    let targets = resolve_targets(&payload, &markers);
+    if targets.is_empty() {
+        // Nothing skill-related in this tool call — stay out of the way.
+        return 0;
+    }
+
+    let mut blocked = Vec::new();
+    for target in &targets {
+        let mut scan_args = args.clone();
+        scan_args.command = None;
+        match target {
+            SkillTarget::Local(path) => {
+                scan_args.path = path.clone();
+                scan_args.remote = None;
+            }
+            SkillTarget::Remote(spec) => scan_args.remote = Some(spec.clone()),
+        }
+
+        let config = Config::from_args_and_file(scan_args, config_file.clone());
+        match pipeline::run(&config) {
+            Ok(outcome) => {
+                let mut rule_ids: Vec<&str> = outcome
+                    .findings
+                    .iter()
+                    .filter(|f| f.severity >= config.error_on)
+                    .map(|f| f.rule_id.as_str())
+                    .collect();
+                if !rule_ids.is_empty() {
+                    let count = rule_ids.len();
+                    rule_ids.sort_unstable();
+                    rule_ids.dedup();
+                    blocked.push(format!(
+                        "{target}: {count} finding(s) at or above {} ({})",
+                        config.error_on,
+                        rule_ids.join(", ")
+                    ));
+                }
+            }
+            // Fail closed: a skill we could not scan is not a skill we can vouch for.
+            Err(e) => blocked.push(format!("{target}: could not be scanned ({e})")),
+        }
+    }
+
+    let decision = if !blocked.is_empty() {
+        Some((
+            "deny",
+            format!("skill-issue blocked {}", blocked.join("; ")),
+        ))
+    } else if hook_args.allow_clean {
+        let names: Vec<String> = targets.iter().map(|t| t.to_string()).collect();
+        Some((
+            "allow",
+            format!(
+                "skill-issue found no blocking issues in {}",
+                names.join(", ")
+            ),
+        ))
+    } else {
+        None
+    };
+
+    if let Some((permission_decision, reason)) = decision {
+        let response = HookResponse {
+            hook_specific_output: HookSpecificOutput {
+                hook_event_name: payload
+                    .hook_event_name
+                    .clone()
+                    .unwrap_or_else(|| "PreToolUse".to_string()),
+                permission_decision,
+                permission_decision_reason: reason,
+            },
+        };
+        match serde_json::to_string(&response) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("error: failed to serialize hook response: {e}");
+                return 1;
+            }
+        }
+    }
+
+    0
+}
+
+/// Work out which skills a tool call refers to. A skill is a directory with
+/// one of `markers`.
+fn resolve_targets(payload: &HookPayload, markers: &[String]) -> Vec<SkillTarget> {
+    let cwd = payload
+        .cwd
+        .clone()
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let input = &payload.tool_input;
+    let mut targets = Vec::new();
+
+    if payload.tool_name.as_deref() == Some("Skill") {
+        if let Some(name) = input["skill"].as_str() {
+            let mut candidates = vec![cwd.join(".claude/skills").join(name)];
+            if let Some(home) = home_dir() {
+                candidates.push(home.join(".claude/skills").join(name));
+            }
+            if let Some(dir) = candidates.into_iter().find(|d| is_skill_root(d, markers)) {
+                targets.push(SkillTarget::Local(dir));
+            }
+        }
+    }
+
+    for key in PATH_KEYS {
+        if let Some(value) = input[*key].as_str() {
+            if let Some(dir) = local_skill_root(value, &cwd, markers) {
+                targets.push(SkillTarget::Local(dir));
+            }
+        }
+    }
+
+    if let Some(command) = input["command"].as_str() {
+        for token in command.split_whitespace() {
+            let token = token.trim_matches(|c| matches!(c, '"' | '\'' | ';' | '(' | ')'));
+            if let Some(spec) = github_spec(token) {
+                targets.push(SkillTarget::Remote(spec));
+            } else if let Some(dir) = local_skill_root(token, &cwd, markers) {
+                targets.push(SkillTarget::Local(dir));
+            }
+        }
+    }
+
+    let mut unique = Vec::new();
+    for t in targets {
+        if !unique.contains(&t) {
+            unique.push(t);
+        }
+    }
+    unique
+}
+
+/// Resolve a path-like string to the skill directory it belongs to, if any.
+fn local_skill_root(raw: &str, cwd: &Path, markers: &[String]) -> Option<PathBuf> {
+    if raw.is_empty() || raw.starts_with('-') {
+        return None;
+    }
+    let expanded = match raw.strip_prefix("~/") {
+        Some(rest) => home_dir()?.join(rest),
+        None => PathBuf::from(raw),
+    };
+    let path = if expanded.is_absolute() {
+        expanded
+    } else {
+        cwd.join(expanded)
+    };
+
+    if scanner::is_skill_marker(&path, markers) && path.is_file() {
+        return path.parent().map(Path::to_path_buf);
+    }
+    if is_skill_root(&path, markers) {
+        return Some(path);
+    }
+    None
+}
+
+/// Convert a GitHub clone/browse URL into a remote specifier.
+fn github_spec(token: &str) -> Option<String> {
+    let spec = if let Some(rest) = token.strip_prefix("git@github.com:") {
+        rest.trim_end_matches(".git").to_string()
+    } else if token.starts_with("https://github.com/") || token.starts_with("http://github.com/") {
+        token.to_string()
+    } else {
+        return None;
+    };
+    RemoteTarget::parse(&spec).ok().map(|_| spec)
+}
+
+fn is_skill_root(dir: &Path, markers: &[String]) -> bool {
+    markers.iter().any(|m| dir.join(m).is_file())
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn payload(json: serde_json::Value) -> HookPayload {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn markers() -> Vec<String> {
+        config::skill_markers(&[], &[])
+    }
+
+    #[test]
+    fn test_resolve_skill_dir_in_command() {
+        let dir = TempDir::new().unwrap();
+        let skill = dir.path().join("my-skill");
+        fs::create_dir(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# Skill").unwrap();
+
+        let p = payload(serde_json::json!({
+            "cwd": dir.path(),
+            "tool_name": "Bash",
+            "tool_input": { "command": "cp -r ./my-skill ~/.claude/skills/" }
+        }));
+        let targets = resolve_targets(&p, &markers());
+        assert_eq!(
+            targets,
+            vec![SkillTarget::Local(dir.path().join("./my-skill"))]
+        );
+    }
+
+    #[test]
+    fn test_resolve_skill_md_file_path() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("SKILL.md"), "# Skill").unwrap();
+
+        let p = payload(serde_json::json!({
+            "tool_name": "Read",
+            "tool_input": { "file_path": dir.path().join("SKILL.md") }
+        }));
+        assert_eq!(
+            resolve_targets(&p, &markers()),
+            vec![SkillTarget::Local(dir.path().to_path_buf())]
+        );
+    }
+
+    #[test]
+    fn test_resolve_custom_marker() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("AGENT.md"), "# Agent").unwrap();
+        let p = payload(serde_json::json!({
+            "tool_name": "Read",
+            "tool_input": { "file_path": dir.path().join("AGENT.md") }
+        }));
+        assert!(resolve_targets(&p, &markers()).is_empty());
+        assert_eq!(
+            resolve_targets(&p, &["AGENT.md".to_string()]),
+            vec![SkillTarget::Local(dir.path().to_path_buf())]
+        );
+    }
+
+    #[test]
+    fn test_resolve_github_clone() {
+        let p = payload(serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "git clone https://github.com/owner/repo.git skills/repo" }
+        }));
+        assert_eq!(
+            resolve_targets(&p, &markers()),
+            vec![SkillTarget::Remote(
+                "https://github.com/owner/repo.git".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_resolve_ssh_clone() {
+        assert_eq!(
+            github_spec("git@github.com:owner/repo.git"),
+            Some("owner/repo".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_unrelated_command() {
+        let p = payload(serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "ls -la" }
+        }));
+        assert!(resolve_targets(&p, &markers()).is_empty());
+    }
+}
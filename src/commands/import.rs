@@ -0,0 +1,116 @@
+use crate::config::{CliArgs, Config, OutputFormat};
+use crate::engine::Engine;
+use crate::finding::Finding;
+use crate::import::{self, ImportFormat};
+use crate::output;
+use crate::pipeline;
+use crate::provenance::Provenance;
+use crate::scanner;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ImportArgs {
+    /// Reports written by the other scanner
+    #[arg(required = true)]
+    pub reports: Vec<PathBuf>,
+
+    /// Scanner that wrote the reports
+    #[arg(long, value_enum)]
+    pub format: ImportFormat,
+
+    /// Also scan this skill and merge its findings; imported paths inside it
+    /// are made relative to it
+    #[arg(long, value_name = "PATH")]
+    pub scan: Option<PathBuf>,
+
+    /// Output format of the merged report
+    #[arg(short = 'o', long, default_value = "table")]
+    pub output_format: OutputFormat,
+}
+
+pub fn run(import_args: &ImportArgs, args: &CliArgs) -> i32 {
+    match merge(import_args, args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn merge(import_args: &ImportArgs, args: &CliArgs) -> Result<i32, String> {
+    let started_at = chrono::Utc::now();
+    let timer = Instant::now();
+    let mut scan_args = args.clone();
+    scan_args.command = None;
+    if let Some(ref path) = import_args.scan {
+        scan_args.path = path.clone();
+    }
+    // Only an explicit --config: the policy must not come from a scanned skill.
+    let file = args.config.as_deref().and_then(pipeline::load_config_file);
+    let config = Config::from_args_and_file(scan_args, file);
+
+    let mut imported = Vec::new();
+    for report in &import_args.reports {
+        let text =
+            std::fs::read_to_string(report).map_err(|e| format!("{}: {e}", report.display()))?;
+        let findings = import::parse(import_args.format, &text, import_args.scan.as_deref())
+            .map_err(|e| format!("{}: {e}", report.display()))?;
+        imported.extend(findings);
+    }
+    // Imported findings get the same ignores, allowlist, overrides and
+    // thresholds as our own.
+    let registry = pipeline::build_registry(&config)?;
+    let mut findings = Engine::new(&config, &registry).run(&[], imported);
+    if config.redact {
+        findings.iter_mut().for_each(Finding::redact);
+    }
+
+    let (display_path, files, provenance, diagnostics) = match import_args.scan {
+        Some(_) => {
+            let outcome = pipeline::run(&config)?;
+            findings.extend(outcome.findings);
+            (
+                outcome.display_path,
+                outcome.files,
+                outcome.provenance,
+                outcome.diagnostics,
+            )
+        }
+        None => (
+            PathBuf::from("."),
+            Vec::new(),
+            Provenance::new(
+                ".".to_string(),
+                None,
+                &registry,
+                started_at,
+                timer.elapsed(),
+            ),
+            registry.diagnostics().to_vec(),
+        ),
+    };
+    findings.sort_by_key(|f| f.sort_key());
+
+    let provenance = if config.deterministic {
+        provenance.reproducible()
+    } else {
+        provenance
+    };
+    println!(
+        "{}",
+        output::format_findings(
+            &import_args.output_format,
+            &findings,
+            &display_path,
+            &scanner::skill_roots(&files, &config.skill_markers),
+            &provenance,
+            &diagnostics,
+            config.columns,
+            // Snippets would show redacted secrets in full.
+            if config.redact { &[] } else { &files },
+        )
+    );
+    Ok(Engine::exit_code(&findings, config.error_on))
+}
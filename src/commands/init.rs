@@ -0,0 +1,244 @@
+use crate::config::{CliArgs, Config};
+use crate::finding::{report_path, Finding};
+use crate::pipeline;
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE: &str = ".skill-issue.toml";
+
+/// Every setting commented out, so the file changes nothing until edited.
+const TEMPLATE: &str = r#"# skill-issue configuration. Scans of this directory pick it up
+# automatically; `--config PATH` uses another file.
+
+[settings]
+# Rule IDs to skip entirely.
+# ignore = ["SL-NET-001"]
+# Also match injection phrases after undoing leetspeak and spaced letters.
+# normalize = true
+# Collapse rules that flood a file; downgrade vendored and minified files.
+# suppress_noise = true
+# Mask detected secrets in every report.
+# redact = true
+# Also run the SKILL.md quality checks (SL-QUAL-*).
+# quality = true
+# Reuse rule results for files scanned before (see `skill-issue cache`).
+# rule_cache = true
+# Load extra pattern files from this directory.
+# rules_dir = "rules"
+
+# Per-rule overrides: change a severity, turn a rule off, or tune the
+# parameters it declares (see `skill-issue rules show <ID>`).
+# [rules.SL-NET-002]
+# severity = "error"    # or: enabled = false
+#
+# [rules.SL-NET-003.params]
+# allowed_domains = ["github.com"]
+#
+# [rules.SL-NET-012.params]
+# deny = ["evil.example"]
+# allow = ["github.com"]    # set to report every other domain
+
+# Severity per category or rule ID in a skill's references/, assets/ and
+# scripts/ directories. Listing an area replaces its defaults (execution is
+# info in references/ and assets/, error in scripts/).
+# [policy.areas.references]
+# execution = "warning"
+
+# Reviewed exceptions. `file` limits an entry to paths containing it;
+# `reason` is shown by `skill-issue suppressions`.
+# [[allowlist]]
+# rule = "SL-NET-002"
+# file = "scripts/install.sh"
+# reason = "Pinned installer, verified by checksum"
+"#;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct InitArgs {
+    /// Skill directory to write .skill-issue.toml into
+    #[arg(default_value = ".")]
+    pub target: PathBuf,
+
+    /// Scan the target first and offer to allowlist each rule's current findings
+    #[arg(long)]
+    pub scan: bool,
+
+    /// With --scan, allowlist every current finding without asking
+    #[arg(long, requires = "scan")]
+    pub yes: bool,
+
+    /// Replace an existing .skill-issue.toml
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Findings of one rule in one file: a single allowlist entry covers them.
+#[derive(Debug, PartialEq)]
+struct Candidate {
+    rule_id: String,
+    rule_name: String,
+    file: String,
+    count: usize,
+}
+
+pub fn run(init_args: &InitArgs, args: &CliArgs) -> i32 {
+    match init(init_args, args) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn init(init_args: &InitArgs, args: &CliArgs) -> Result<(), String> {
+    let target = &init_args.target;
+    if !target.is_dir() {
+        return Err(format!("{} is not a directory", target.display()));
+    }
+    let path = target.join(CONFIG_FILE);
+    if path.exists() && !init_args.force {
+        return Err(format!(
+            "{} already exists (use --force to replace it)",
+            path.display()
+        ));
+    }
+
+    let mut accepted = Vec::new();
+    if init_args.scan {
+        let candidates = candidates(&scan(target, args)?);
+        if candidates.is_empty() {
+            eprintln!("note: no findings to allowlist");
+        }
+        let stdin = std::io::stdin();
+        let mut answers = stdin.lock().lines();
+        for candidate in candidates {
+            if init_args.yes || confirm(&candidate, &mut answers) {
+                accepted.push(candidate);
+            }
+        }
+    }
+
+    std::fs::write(&path, render(&accepted, &today()))
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    println!(
+        "Wrote {} with {} allowlist entr{}",
+        path.display(),
+        accepted.len(),
+        if accepted.len() == 1 { "y" } else { "ies" }
+    );
+    Ok(())
+}
+
+/// Findings for the target as a scan with no config file would report them.
+fn scan(target: &Path, args: &CliArgs) -> Result<Vec<Finding>, String> {
+    let mut scan_args = args.clone();
+    scan_args.command = None;
+    scan_args.path = target.to_path_buf();
+    scan_args.baseline = None;
+    scan_args.update_baseline = false;
+    let config = Config::from_args_and_file(scan_args, None);
+    Ok(pipeline::run(&config)?.findings)
+}
+
+/// One candidate per rule and file, in rule then file order.
+fn candidates(findings: &[Finding]) -> Vec<Candidate> {
+    let mut grouped: BTreeMap<(&str, String), Candidate> = BTreeMap::new();
+    for f in findings {
+        let file = report_path(&f.location.file);
+        grouped
+            .entry((f.rule_id.as_str(), file.clone()))
+            .or_insert_with(|| Candidate {
+                rule_id: f.rule_id.clone(),
+                rule_name: f.rule_name.clone(),
+                file,
+                count: 0,
+            })
+            .count += 1;
+    }
+    grouped.into_values().collect()
+}
+
+/// Ask on stderr; anything but `y`/`yes`, including end of input, is no.
+fn confirm(
+    candidate: &Candidate,
+    answers: &mut impl Iterator<Item = std::io::Result<String>>,
+) -> bool {
+    eprint!(
+        "Allowlist {} ({}) in {}, {} finding(s)? [y/N] ",
+        candidate.rule_id, candidate.rule_name, candidate.file, candidate.count
+    );
+    let _ = std::io::stderr().flush();
+    let answer = answers.next().and_then(Result::ok).unwrap_or_default();
+    if answer.is_empty() {
+        eprintln!();
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+fn render(accepted: &[Candidate], date: &str) -> String {
+    let mut out = TEMPLATE.to_string();
+    for c in accepted {
+        out.push_str(&format!(
+            "\n[[allowlist]]\nrule = {}\nfile = {}\nreason = {}\n",
+            quoted(&c.rule_id),
+            quoted(&c.file),
+            quoted(&format!(
+                "Accepted by skill-issue init on {date} ({} finding(s))",
+                c.count
+            ))
+        ));
+    }
+    out
+}
+
+fn quoted(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
+
+fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFile;
+    use crate::finding::Severity;
+
+    #[test]
+    fn test_rendered_config_parses_with_allowlist() {
+        let template: ConfigFile = toml::from_str(&render(&[], "2026-01-01")).unwrap();
+        assert!(template.allowlist.is_empty() && template.rules.is_empty());
+
+        let accepted = candidates(&[
+            Finding::test("SL-NET-002", Severity::Warning, "scripts/a \"b\".sh", 1),
+            Finding::test("SL-NET-001", Severity::Warning, "SKILL.md", 1),
+            Finding::test("SL-NET-001", Severity::Warning, "SKILL.md", 1),
+        ]);
+        let config: ConfigFile = toml::from_str(&render(&accepted, "2026-01-01")).unwrap();
+        assert_eq!(config.allowlist.len(), 2);
+        assert_eq!(config.allowlist[0].rule, "SL-NET-001");
+        assert_eq!(
+            config.allowlist[1].file.as_deref(),
+            Some("scripts/a \"b\".sh")
+        );
+        assert_eq!(
+            config.allowlist[0].reason.as_deref(),
+            Some("Accepted by skill-issue init on 2026-01-01 (2 finding(s))")
+        );
+    }
+
+    #[test]
+    fn test_confirm_defaults_to_no() {
+        let c = &candidates(&[Finding::test(
+            "SL-NET-001",
+            Severity::Warning,
+            "SKILL.md",
+            1,
+        )])[0];
+        let mut answers = ["y", "", "Yes", "n"].map(|a| Ok(a.to_string())).into_iter();
+        let got: Vec<bool> = (0..5).map(|_| confirm(c, &mut answers)).collect();
+        assert_eq!(got, [true, false, true, false, false]);
+    }
+}
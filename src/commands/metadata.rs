@@ -0,0 +1,170 @@
+use crate::config::CliArgs;
+use crate::rules::metadata_rule::extract_frontmatter;
+use crate::scanner;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::path::Path;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct MetadataArgs {
+    /// Skill directory or remote specifier (owner/repo@skill, GitHub URL)
+    #[arg(default_value = ".")]
+    pub target: String,
+}
+
+/// Everything `metadata` prints: one entry per skill marker found.
+#[derive(Debug, Serialize)]
+struct MetadataDocument {
+    target: String,
+    skills: Vec<SkillMetadata>,
+}
+
+/// A skill's frontmatter, normalized: `allowed-tools` is always a list, and
+/// `version` is read from `metadata.version` when not set at the top level.
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct SkillMetadata {
+    path: String,
+    name: Option<String>,
+    description: Option<String>,
+    allowed_tools: Vec<String>,
+    version: Option<String>,
+    dependencies: Vec<String>,
+    /// Why the frontmatter could not be read; the other fields are empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+pub fn run(metadata_args: &MetadataArgs, args: &CliArgs) -> i32 {
+    let entries = match super::target_entries(&metadata_args.target, args) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+    let markers = crate::config::skill_markers(&args.skill_markers, &[]);
+    let mut skills: Vec<SkillMetadata> = entries
+        .iter()
+        .filter(|(path, _)| scanner::is_skill_marker(Path::new(path), &markers))
+        .map(|(path, bytes)| parse(path, &String::from_utf8_lossy(bytes)))
+        .collect();
+    skills.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let document = MetadataDocument {
+        target: metadata_args.target.clone(),
+        skills,
+    };
+    match serde_json::to_string_pretty(&document) {
+        Ok(json) => {
+            println!("{json}");
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+/// Metadata for the marker file at `path`, using the same frontmatter parser
+/// as the metadata validation rule.
+fn parse(path: &str, content: &str) -> SkillMetadata {
+    let mut skill = SkillMetadata {
+        path: path.to_string(),
+        ..Default::default()
+    };
+    let Some(frontmatter) = extract_frontmatter(content) else {
+        skill.error = Some("no frontmatter".to_string());
+        return skill;
+    };
+    let yaml: Value = match serde_yaml::from_str(&frontmatter) {
+        Ok(v) => v,
+        Err(e) => {
+            skill.error = Some(format!("invalid frontmatter: {e}"));
+            return skill;
+        }
+    };
+    if !yaml.is_mapping() {
+        skill.error = Some("frontmatter is not a mapping".to_string());
+        return skill;
+    }
+
+    skill.name = yaml.get("name").and_then(scalar);
+    skill.description = yaml.get("description").and_then(scalar);
+    skill.allowed_tools = yaml.get("allowed-tools").map(list).unwrap_or_default();
+    skill.version = yaml
+        .get("version")
+        .or_else(|| yaml.get("metadata").and_then(|m| m.get("version")))
+        .and_then(scalar);
+    skill.dependencies = yaml.get("dependencies").map(list).unwrap_or_default();
+    skill
+}
+
+/// A string, number or boolean as text.
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// A YAML sequence, a comma-separated string, or a `name: version` mapping,
+/// as a list of strings (`requests >=2.31` for a mapping entry).
+fn list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Sequence(items) => items.iter().filter_map(scalar).collect(),
+        Value::Mapping(map) => map
+            .iter()
+            .filter_map(|(k, v)| {
+                let name = scalar(k)?;
+                Some(match scalar(v) {
+                    Some(spec) if !spec.is_empty() => format!("{name} {spec}"),
+                    _ => name,
+                })
+            })
+            .collect(),
+        other => scalar(other)
+            .map(|s| {
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_normalizes_fields() {
+        let skill = parse(
+            "pdf/SKILL.md",
+            "---\nname: pdf\ndescription: Fill PDF forms\nallowed-tools: Read, Bash\nmetadata:\n  version: 1.2\ndependencies:\n  pypdf: \">=4\"\n  jq:\n---\n# PDF\n",
+        );
+        assert_eq!(
+            skill,
+            SkillMetadata {
+                path: "pdf/SKILL.md".to_string(),
+                name: Some("pdf".to_string()),
+                description: Some("Fill PDF forms".to_string()),
+                allowed_tools: vec!["Read".to_string(), "Bash".to_string()],
+                version: Some("1.2".to_string()),
+                dependencies: vec!["pypdf >=4".to_string(), "jq".to_string()],
+                error: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_reports_missing_frontmatter() {
+        let skill = parse("SKILL.md", "# No frontmatter\n");
+        assert_eq!(skill.error.as_deref(), Some("no frontmatter"));
+        assert!(skill.name.is_none());
+    }
+}
@@ -0,0 +1,109 @@
+pub mod approve;
+pub mod cache;
+pub mod check_file;
+pub mod compare;
+pub mod corpus_check;
+pub mod explain_finding;
+pub mod feedback;
+pub mod history;
+pub mod hook;
+pub mod import;
+pub mod init;
+pub mod metadata;
+pub mod queue;
+pub mod report;
+pub mod rules;
+pub mod suppressions;
+pub mod verify_signature;
+
+use crate::config::CliArgs;
+use crate::remote;
+use crate::signature::{self, ManifestEntry};
+use std::path::Path;
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Record a skill's files and findings as an approved snapshot, or check it against one
+    Approve(approve::ApproveArgs),
+    /// Inspect or trim the on-disk cache
+    Cache(cache::CacheArgs),
+    /// Check one file with the rules that apply to it, for editor on-save hooks
+    CheckFile(check_file::CheckFileArgs),
+    /// Compare two skills to spot clones republished with changes
+    Compare(compare::CompareArgs),
+    /// Check that each sample in a fixture corpus triggers exactly its expected rules
+    CorpusCheck(corpus_check::CorpusCheckArgs),
+    /// Show a saved report's finding with its source context and rule documentation
+    ExplainFinding(explain_finding::ExplainFindingArgs),
+    /// Record a finding as a false positive, to help tune the patterns
+    Feedback(feedback::FeedbackArgs),
+    /// Run as a Claude Code PreToolUse hook (reads the hook payload from stdin)
+    Hook(hook::HookArgs),
+    /// Show recorded scan results for a target over time (see --history)
+    History(history::HistoryArgs),
+    /// Merge gitleaks or semgrep JSON reports into one report, optionally with a scan
+    Import(import::ImportArgs),
+    /// Write a commented .skill-issue.toml, optionally allowlisting current findings
+    Init(init::InitArgs),
+    /// Print each skill's frontmatter (name, description, tools, version, dependencies) as JSON
+    Metadata(metadata::MetadataArgs),
+    /// Scan an organization's repositories and pull requests updated since a time, as an intake gate
+    Queue(queue::QueueArgs),
+    /// Work with saved JSON reports
+    Report(report::ReportArgs),
+    /// List the built-in rules or generate their Markdown docs
+    Rules(rules::RulesArgs),
+    /// List every active suppression (ignores, disabled rules, allowlist, baseline) and what it hides
+    Suppressions(suppressions::SuppressionsArgs),
+    /// Verify a skill's minisign signature against trusted keys
+    VerifySignature(verify_signature::VerifySignatureArgs),
+}
+
+/// Dispatch a subcommand, returning the process exit code.
+pub fn run(command: &Command, args: &CliArgs) -> i32 {
+    match command {
+        Command::Approve(approve_args) => approve::run(approve_args, args),
+        Command::Cache(cache_args) => cache::run(cache_args),
+        Command::CheckFile(check_args) => check_file::run(check_args, args),
+        Command::Compare(compare_args) => compare::run(compare_args, args),
+        Command::CorpusCheck(corpus_args) => corpus_check::run(corpus_args, args),
+        Command::ExplainFinding(explain_args) => explain_finding::run(explain_args, args),
+        Command::Feedback(feedback_args) => feedback::run(feedback_args, args),
+        Command::Hook(hook_args) => hook::run(hook_args, args),
+        Command::History(history_args) => history::run(history_args, args),
+        Command::Import(import_args) => import::run(import_args, args),
+        Command::Init(init_args) => init::run(init_args, args),
+        Command::Metadata(metadata_args) => metadata::run(metadata_args, args),
+        Command::Queue(queue_args) => queue::run(queue_args, args),
+        Command::Report(report_args) => report::run(report_args, args),
+        Command::Rules(rules_args) => rules::run(rules_args, args),
+        Command::Suppressions(suppressions_args) => suppressions::run(suppressions_args, args),
+        Command::VerifySignature(sig_args) => verify_signature::run(sig_args, args),
+    }
+}
+
+/// Raw file contents for a local skill directory or, failing that, a remote
+/// specifier.
+fn target_entries(target: &str, args: &CliArgs) -> Result<Vec<ManifestEntry>, String> {
+    if Path::new(target).exists() {
+        signature::local_entries(Path::new(target))
+    } else {
+        let markers = crate::config::skill_markers(&args.skill_markers, &[]);
+        let skill = remote::fetch_remote_skill(
+            target,
+            args.github_token.as_deref(),
+            &markers,
+            args.verbose,
+        )
+        .map_err(|e| e.to_string())?;
+        // A manifest missing a skill's files would compare or verify wrongly.
+        if let Some(failed) = skill.failed.first() {
+            return Err(format!(
+                "could not fetch {}: {}",
+                failed.root.display(),
+                failed.error
+            ));
+        }
+        Ok(signature::scanned_entries(&skill.files))
+    }
+}
@@ -0,0 +1,221 @@
+use crate::config::{CliArgs, Config};
+use crate::engine::Engine;
+use crate::pipeline;
+use crate::remote::{self, QueueItem, RemoteError};
+use crate::scoring::{self, Grade};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use serde::Serialize;
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct QueueArgs {
+    /// GitHub organization (or user) whose repositories to watch
+    #[arg(long = "remote-org", value_name = "ORG")]
+    pub remote_org: String,
+
+    /// Only scan repositories pushed, and pull requests updated, at or after
+    /// this time (RFC 3339 or YYYY-MM-DD)
+    #[arg(long, value_name = "TIMESTAMP")]
+    pub since: String,
+
+    /// Skip open pull requests
+    #[arg(long)]
+    pub no_prs: bool,
+
+    /// Print the digest as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Everything a scheduled intake run found, and when to start the next one.
+#[derive(Serialize)]
+struct Digest {
+    org: String,
+    since: String,
+    /// Pass as `--since` on the next run so nothing is missed or scanned twice.
+    checked_at: String,
+    entries: Vec<DigestEntry>,
+}
+
+#[derive(Serialize)]
+struct DigestEntry {
+    target: String,
+    spec: String,
+    updated_at: String,
+    /// `scanned`, `no skills` or `error`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grade: Option<Grade>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    risk_score: Option<u32>,
+    findings: usize,
+    /// Findings at or above `--error-on`.
+    blocking: usize,
+}
+
+pub fn run(queue_args: &QueueArgs, args: &CliArgs) -> i32 {
+    let checked_at = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let since = match parse_since(&queue_args.since) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+
+    let items = match remote::github::updated_since(
+        &queue_args.remote_org,
+        &since,
+        !queue_args.no_prs,
+        args.github_token.as_deref(),
+        args.verbose,
+    ) {
+        Ok(items) => items,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+
+    let mut exit_code = 0;
+    let entries: Vec<DigestEntry> = items
+        .into_iter()
+        .map(|item| {
+            let (entry, code) = scan(item, args);
+            exit_code = exit_code.max(code);
+            entry
+        })
+        .collect();
+
+    let digest = Digest {
+        org: queue_args.remote_org.clone(),
+        since,
+        checked_at,
+        entries,
+    };
+    if queue_args.json {
+        match serde_json::to_string_pretty(&digest) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return 2;
+            }
+        }
+    } else {
+        println!("{}", format_table(&digest));
+    }
+    exit_code
+}
+
+/// Normalize `--since` to GitHub's timestamp format, so it compares directly
+/// with the timestamps the API returns.
+fn parse_since(s: &str) -> Result<String, String> {
+    let parsed = chrono::DateTime::parse_from_rfc3339(s)
+        .map(|d| d.to_utc())
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        })
+        .map_err(|_| format!("invalid --since '{s}': use RFC 3339 or YYYY-MM-DD"))?;
+    Ok(parsed.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+/// Scan one queue item with the global scan options, returning its digest
+/// entry and exit code. Repositories without skills are not a failure.
+fn scan(item: QueueItem, args: &CliArgs) -> (DigestEntry, i32) {
+    let mut scan_args = args.clone();
+    scan_args.command = None;
+    scan_args.remote = Some(item.spec.clone());
+    let config = Config::from_args_and_file(scan_args, None);
+
+    let mut entry = DigestEntry {
+        target: item.label,
+        spec: item.spec,
+        updated_at: item.updated_at,
+        status: "scanned",
+        error: None,
+        grade: None,
+        risk_score: None,
+        findings: 0,
+        blocking: 0,
+    };
+    match pipeline::run(&config) {
+        Ok(outcome) if outcome.empty.is_some() => {
+            entry.status = "no skills";
+            (entry, 0)
+        }
+        Ok(outcome) => {
+            let trust = scoring::score(&outcome.findings);
+            entry.grade = Some(trust.grade);
+            entry.risk_score = Some(trust.risk_score);
+            entry.findings = outcome.findings.len();
+            entry.blocking = outcome
+                .findings
+                .iter()
+                .filter(|f| f.severity >= config.error_on)
+                .count();
+            (entry, Engine::exit_code(&outcome.findings, config.error_on))
+        }
+        Err(e) if e == RemoteError::NoSkillsFound.to_string() => {
+            entry.status = "no skills";
+            (entry, 0)
+        }
+        Err(e) => {
+            entry.status = "error";
+            entry.error = Some(e);
+            (entry, 2)
+        }
+    }
+}
+
+fn format_table(digest: &Digest) -> String {
+    let footer = format!(
+        "{} updated item(s) in {} since {}. Next run: --since {}",
+        digest.entries.len(),
+        digest.org,
+        digest.since,
+        digest.checked_at
+    );
+    if digest.entries.is_empty() {
+        return footer;
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            "Target", "Updated", "Grade", "Risk", "Findings", "Blocking", "Status",
+        ]);
+    for e in &digest.entries {
+        let status = match &e.error {
+            Some(err) => format!("{}: {err}", e.status),
+            None => e.status.to_string(),
+        };
+        table.add_row(vec![
+            e.target.clone(),
+            e.updated_at.clone(),
+            e.grade.map(|g| g.to_string()).unwrap_or_default(),
+            e.risk_score.map(|r| r.to_string()).unwrap_or_default(),
+            e.findings.to_string(),
+            e.blocking.to_string(),
+            status,
+        ]);
+    }
+    format!("{table}\n{footer}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since_normalizes_to_github_format() {
+        assert_eq!(parse_since("2026-03-01").unwrap(), "2026-03-01T00:00:00Z");
+        assert_eq!(
+            parse_since("2026-03-01T10:30:00+02:00").unwrap(),
+            "2026-03-01T08:30:00Z"
+        );
+        assert!(parse_since("yesterday").is_err());
+    }
+}
@@ -0,0 +1,497 @@
+use crate::config::{CliArgs, OutputFormat};
+use crate::engine::Engine;
+use crate::finding::{report_path, Finding};
+use crate::output::{html, markdown, sarif, stylish, vscode};
+use crate::report_signing;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Cell, Color, Table};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ReportArgs {
+    #[command(subcommand)]
+    pub command: ReportCommand,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum ReportCommand {
+    /// Compare two saved JSON reports; exits non-zero when new findings reach --error-on
+    Diff(DiffArgs),
+    /// Check a report against the detached signature written by --sign-report
+    Verify(VerifyArgs),
+    /// Count which rules fire most across many saved JSON reports, per repository and over time
+    Stats(StatsArgs),
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct DiffArgs {
+    /// Earlier report (from `--format json`)
+    pub old: PathBuf,
+
+    /// Later report to compare against it
+    pub new: PathBuf,
+
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub format: OutputFormat,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifyArgs {
+    /// Report file, exactly as the scan printed it
+    pub report: PathBuf,
+
+    /// Detached signature written by --signature-out
+    #[arg(long, value_name = "PATH")]
+    pub signature: PathBuf,
+
+    /// Ed25519 public key (SubjectPublicKeyInfo PEM or DER)
+    #[arg(long, value_name = "KEY")]
+    pub key: PathBuf,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct StatsArgs {
+    /// Saved reports (from `--format json`)
+    #[arg(required = true)]
+    pub reports: Vec<PathBuf>,
+
+    /// Show only the N rules with the most findings
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    pub top: usize,
+
+    /// Print JSON instead of tables
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// The parts of a saved JSON report the diff needs.
+#[derive(Deserialize)]
+struct SavedReport {
+    skill_path: PathBuf,
+    findings: Vec<Finding>,
+}
+
+/// The parts of a saved JSON report the stats need.
+#[derive(Deserialize)]
+struct StatsReport {
+    skill_path: PathBuf,
+    findings: Vec<Finding>,
+    #[serde(default)]
+    provenance: Option<StatsProvenance>,
+}
+
+#[derive(Deserialize)]
+struct StatsProvenance {
+    target: Option<String>,
+    repository: Option<String>,
+    started_at: Option<String>,
+}
+
+/// How often one rule fired across the reports.
+#[derive(Debug, Default, Serialize)]
+struct RuleStats {
+    rule_id: String,
+    rule_name: String,
+    findings: usize,
+    /// Findings per repository.
+    repos: BTreeMap<String, usize>,
+    /// Findings per month the reports were made in.
+    periods: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct Stats {
+    reports: usize,
+    /// Every month with a report, oldest first; `undated` for reports
+    /// without a start time, such as `--deterministic` ones.
+    periods: Vec<String>,
+    rules: Vec<RuleStats>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ReportDiff {
+    added: Vec<Finding>,
+    removed: Vec<Finding>,
+    unchanged: Vec<Finding>,
+}
+
+#[derive(Serialize)]
+struct DiffSummary {
+    added: usize,
+    removed: usize,
+    unchanged: usize,
+}
+
+pub fn run(report_args: &ReportArgs, args: &CliArgs) -> i32 {
+    match &report_args.command {
+        ReportCommand::Diff(diff_args) => run_diff(diff_args, args),
+        ReportCommand::Verify(verify_args) => run_verify(verify_args),
+        ReportCommand::Stats(stats_args) => run_stats(stats_args),
+    }
+}
+
+fn run_stats(stats_args: &StatsArgs) -> i32 {
+    let mut reports = Vec::new();
+    for path in &stats_args.reports {
+        let loaded = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: {e}", path.display()))
+            .and_then(|text| {
+                serde_json::from_str::<StatsReport>(&text)
+                    .map_err(|e| format!("{}: not a skill-issue JSON report: {e}", path.display()))
+            });
+        match loaded {
+            Ok(report) => reports.push(report),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return 2;
+            }
+        }
+    }
+
+    let mut stats = stats(&reports);
+    stats.rules.truncate(stats_args.top);
+    if stats_args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&stats)
+                .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
+        );
+    } else {
+        println!("{}", format_stats(&stats));
+    }
+    0
+}
+
+/// Rules by how often they fired, most first. A report's repository is the
+/// one it was fetched from, else the target it scanned.
+fn stats(reports: &[StatsReport]) -> Stats {
+    let mut rules: BTreeMap<&str, RuleStats> = BTreeMap::new();
+    let mut periods = BTreeSet::new();
+    for report in reports {
+        let provenance = report.provenance.as_ref();
+        let repo = provenance
+            .and_then(|p| p.repository.clone().or_else(|| p.target.clone()))
+            .unwrap_or_else(|| report_path(&report.skill_path));
+        let period = provenance
+            .and_then(|p| p.started_at.as_deref())
+            .and_then(|t| t.get(..7))
+            .unwrap_or("undated")
+            .to_string();
+        periods.insert(period.clone());
+        for f in &report.findings {
+            let rule = rules.entry(&f.rule_id).or_insert_with(|| RuleStats {
+                rule_id: f.rule_id.clone(),
+                rule_name: f.rule_name.clone(),
+                ..RuleStats::default()
+            });
+            rule.findings += 1;
+            *rule.repos.entry(repo.clone()).or_default() += 1;
+            *rule.periods.entry(period.clone()).or_default() += 1;
+        }
+    }
+    let mut rules: Vec<RuleStats> = rules.into_values().collect();
+    rules.sort_by(|a, b| b.findings.cmp(&a.findings).then(a.rule_id.cmp(&b.rule_id)));
+    Stats {
+        reports: reports.len(),
+        periods: periods.into_iter().collect(),
+        rules,
+    }
+}
+
+/// A table of the busiest rules and their repositories, then a heatmap of
+/// findings per rule and month.
+fn format_stats(stats: &Stats) -> String {
+    if stats.rules.is_empty() {
+        return format!("No findings in {} report(s).", stats.reports);
+    }
+    let mut rules = Table::new();
+    rules
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec!["Rule", "Name", "Findings", "Repos", "Most affected"]);
+    for rule in &stats.rules {
+        let mut repos: Vec<(&String, &usize)> = rule.repos.iter().collect();
+        repos.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let top = repos
+            .iter()
+            .take(3)
+            .map(|(repo, n)| format!("{repo} ({n})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        rules.add_row(vec![
+            Cell::new(&rule.rule_id),
+            Cell::new(&rule.rule_name),
+            Cell::new(rule.findings),
+            Cell::new(rule.repos.len()),
+            Cell::new(top),
+        ]);
+    }
+
+    let mut heatmap = Table::new();
+    heatmap
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(std::iter::once("Rule").chain(stats.periods.iter().map(String::as_str)));
+    let busiest = stats
+        .rules
+        .iter()
+        .flat_map(|r| r.periods.values())
+        .max()
+        .copied()
+        .unwrap_or(0);
+    for rule in &stats.rules {
+        let cells = stats.periods.iter().map(|period| {
+            let n = rule.periods.get(period).copied().unwrap_or(0);
+            // The busiest cells stand out; empty ones fade.
+            match n {
+                0 => Cell::new("·").fg(Color::DarkGrey),
+                n if n * 2 >= busiest => Cell::new(n).fg(Color::Red),
+                n => Cell::new(n).fg(Color::Yellow),
+            }
+        });
+        heatmap.add_row(std::iter::once(Cell::new(&rule.rule_id)).chain(cells));
+    }
+    format!(
+        "{rules}\n\n{heatmap}\n{} report(s), {} period(s)",
+        stats.reports,
+        stats.periods.len()
+    )
+}
+
+fn run_verify(verify_args: &VerifyArgs) -> i32 {
+    let read = |path: &Path| std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()));
+    let verified = read(&verify_args.report).and_then(|report| {
+        let jws = read(&verify_args.signature)?;
+        report_signing::verify(&report, &String::from_utf8_lossy(&jws), &verify_args.key)
+    });
+    match verified {
+        Ok(kid) => {
+            println!(
+                "Signature valid: {} signed by key {kid}",
+                verify_args.report.display()
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn run_diff(diff_args: &DiffArgs, args: &CliArgs) -> i32 {
+    let (old, new) = match load(&diff_args.old).and_then(|o| Ok((o, load(&diff_args.new)?))) {
+        Ok(pair) => pair,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+
+    let result = diff(old.findings, new.findings);
+    println!(
+        "{}",
+        format_diff(&result, &diff_args.format, &new.skill_path)
+    );
+    Engine::exit_code(&result.added, args.error_on)
+}
+
+fn load(path: &Path) -> Result<SavedReport, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("{}: not a skill-issue JSON report: {e}", path.display()))
+}
+
+/// Match findings by fingerprint. Identical fingerprints are paired in order,
+/// so a second copy of the same match still counts as added.
+fn diff(old: Vec<Finding>, new: Vec<Finding>) -> ReportDiff {
+    let mut remaining: HashMap<String, VecDeque<Finding>> = HashMap::new();
+    for f in old {
+        remaining.entry(f.fingerprint()).or_default().push_back(f);
+    }
+
+    let mut result = ReportDiff::default();
+    for f in new {
+        match remaining
+            .get_mut(&f.fingerprint())
+            .and_then(|q| q.pop_front())
+        {
+            Some(_) => result.unchanged.push(f),
+            None => result.added.push(f),
+        }
+    }
+    result.removed = remaining.into_values().flatten().collect();
+    for list in [
+        &mut result.added,
+        &mut result.removed,
+        &mut result.unchanged,
+    ] {
+        list.sort_by_key(|f| f.sort_key());
+    }
+    result
+}
+
+/// Every finding in the diff, labelled with its change and SARIF baseline state.
+fn labelled(diff: &ReportDiff) -> Vec<(&'static str, &'static str, &Finding)> {
+    diff.added
+        .iter()
+        .map(|f| ("added", "new", f))
+        .chain(diff.removed.iter().map(|f| ("removed", "absent", f)))
+        .chain(diff.unchanged.iter().map(|f| ("unchanged", "unchanged", f)))
+        .collect()
+}
+
+/// Findings with their change prefixed to the message, for formats with no
+/// field of their own for it.
+fn with_labels(entries: &[(&str, &str, &Finding)]) -> Vec<Finding> {
+    entries
+        .iter()
+        .map(|(label, _, f)| Finding {
+            message: format!("[{label}] {}", f.message),
+            ..(*f).clone()
+        })
+        .collect()
+}
+
+fn format_diff(diff: &ReportDiff, format: &OutputFormat, skill_path: &Path) -> String {
+    let entries = labelled(diff);
+    match format {
+        OutputFormat::Json => {
+            #[derive(Serialize)]
+            struct JsonDiff<'a> {
+                summary: DiffSummary,
+                #[serde(flatten)]
+                diff: &'a ReportDiff,
+            }
+            let out = JsonDiff {
+                summary: summary(diff),
+                diff,
+            };
+            serde_json::to_string_pretty(&out).unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
+        }
+        OutputFormat::Sarif => {
+            let findings: Vec<Finding> = entries.iter().map(|(_, _, f)| (*f).clone()).collect();
+            let states: Vec<&'static str> = entries.iter().map(|(_, state, _)| *state).collect();
+            sarif::format_sarif_baseline(&findings, &states)
+        }
+        OutputFormat::Vscode => vscode::format_vscode(&with_labels(&entries), skill_path),
+        OutputFormat::Html => html::format_html(&with_labels(&entries), skill_path, &[], None),
+        OutputFormat::Markdown => markdown::format_markdown(&with_labels(&entries), skill_path),
+        OutputFormat::Stylish => stylish::format_stylish(&with_labels(&entries), &[]),
+        OutputFormat::Table => {
+            let s = summary(diff);
+            let totals = format!(
+                "{} added, {} removed, {} unchanged",
+                s.added, s.removed, s.unchanged
+            );
+            if entries.is_empty() {
+                return format!("No findings in either report.\n{totals}");
+            }
+            let mut table = Table::new();
+            table
+                .load_preset(UTF8_FULL)
+                .apply_modifier(UTF8_ROUND_CORNERS)
+                .set_header(vec![
+                    "Change", "Severity", "Rule", "File", "Line", "Message",
+                ]);
+            for (label, _, f) in &entries {
+                let change = match *label {
+                    "added" => Cell::new(label).fg(Color::Red),
+                    "removed" => Cell::new(label).fg(Color::Green),
+                    _ => Cell::new(label),
+                };
+                table.add_row(vec![
+                    change,
+                    Cell::new(f.severity),
+                    Cell::new(&f.rule_id),
+                    Cell::new(report_path(&f.location.file)),
+                    Cell::new(format!("{}:{}", f.location.line, f.location.column)),
+                    Cell::new(&f.message),
+                ]);
+            }
+            format!("{table}\n{totals}")
+        }
+    }
+}
+
+fn summary(diff: &ReportDiff) -> DiffSummary {
+    DiffSummary {
+        added: diff.added.len(),
+        removed: diff.removed.len(),
+        unchanged: diff.unchanged.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Severity;
+
+    #[test]
+    fn test_diff_ignores_moved_lines() {
+        let old = vec![
+            Finding {
+                matched_text: "eval(".into(),
+                ..Finding::test("SL-EXEC-002", Severity::Error, "SKILL.md", 3)
+            },
+            Finding {
+                matched_text: "curl".into(),
+                ..Finding::test("SL-NET-001", Severity::Error, "SKILL.md", 5)
+            },
+        ];
+        let new = vec![
+            Finding {
+                matched_text: "eval(".into(),
+                ..Finding::test("SL-EXEC-002", Severity::Error, "SKILL.md", 9)
+            },
+            Finding {
+                matched_text: "eval(".into(),
+                ..Finding::test("SL-EXEC-002", Severity::Error, "SKILL.md", 12)
+            },
+        ];
+        let d = diff(old, new);
+        assert_eq!(d.unchanged.len(), 1);
+        assert_eq!(d.unchanged[0].location.line, 9);
+        assert_eq!(d.added.len(), 1);
+        assert_eq!(d.added[0].location.line, 12);
+        assert_eq!(d.removed.len(), 1);
+        assert_eq!(d.removed[0].rule_id, "SL-NET-001");
+    }
+
+    #[test]
+    fn test_stats_count_rules_per_repo_and_month() {
+        let report = |repo: &str, started_at: Option<&str>, rules: &[&str]| StatsReport {
+            skill_path: PathBuf::from("."),
+            findings: rules
+                .iter()
+                .map(|r| Finding {
+                    matched_text: "x".into(),
+                    ..Finding::test(r, Severity::Error, "SKILL.md", 1)
+                })
+                .collect(),
+            provenance: Some(StatsProvenance {
+                target: Some(repo.to_string()),
+                repository: None,
+                started_at: started_at.map(str::to_string),
+            }),
+        };
+        let stats = stats(&[
+            report(
+                "acme/a",
+                Some("2026-08-03T10:00:00.000Z"),
+                &["SL-NET-001", "SL-NET-001", "SL-EXEC-002"],
+            ),
+            report("acme/b", Some("2026-09-14T10:00:00.000Z"), &["SL-NET-001"]),
+            report("acme/b", None, &["SL-EXEC-002"]),
+        ]);
+        assert_eq!(stats.reports, 3);
+        assert_eq!(stats.periods, ["2026-08", "2026-09", "undated"]);
+        let net = &stats.rules[0];
+        assert_eq!((net.rule_id.as_str(), net.findings), ("SL-NET-001", 3));
+        assert_eq!(net.repos["acme/a"], 2);
+        assert_eq!(net.periods["2026-09"], 1);
+        assert_eq!(stats.rules[1].repos.len(), 2);
+    }
+}
@@ -0,0 +1,334 @@
+use crate::config::CliArgs;
+use crate::finding::Category;
+use crate::rules::{self, RuleInfo, RuleRegistry};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RulesFormat {
+    Table,
+    Json,
+    /// One Markdown page per rule
+    Markdown,
+}
+
+/// `skill-issue rules` on its own lists the rules, like `rules list`.
+#[derive(clap::Args, Debug, Clone)]
+pub struct RulesArgs {
+    #[command(subcommand)]
+    pub command: Option<RulesCommand>,
+
+    #[command(flatten)]
+    pub list: ListArgs,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ListArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    pub format: RulesFormat,
+
+    /// Write one `<RULE-ID>.md` page per rule plus a `README.md` index to this
+    /// directory (requires --format markdown)
+    #[arg(long, value_name = "DIR")]
+    pub out: Option<std::path::PathBuf>,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum RulesCommand {
+    /// List every rule (the default)
+    List(ListArgs),
+    /// Describe one rule, including the pattern it matches
+    Show(ShowArgs),
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ShowArgs {
+    /// Rule ID, e.g. SL-INJ-001
+    pub id: String,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    pub format: RulesFormat,
+}
+
+pub fn run(rules_args: &RulesArgs, args: &CliArgs) -> i32 {
+    let mut registry = RuleRegistry::new();
+    registry.load_defaults();
+    if let Some(dir) = &args.rules_dir {
+        if let Err(e) = registry.load_dir(dir) {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    }
+    let catalog = rules::catalog(&registry);
+
+    if let Some(RulesCommand::Show(show)) = &rules_args.command {
+        let Some(rule) = catalog.iter().find(|r| r.id.eq_ignore_ascii_case(&show.id)) else {
+            eprintln!(
+                "error: unknown rule {} (see `skill-issue rules list`)",
+                show.id
+            );
+            return 2;
+        };
+        match show.format {
+            RulesFormat::Table => print!("{}", describe(rule)),
+            RulesFormat::Markdown => print!("{}", markdown_page(rule)),
+            RulesFormat::Json => match serde_json::to_string_pretty(rule) {
+                Ok(json) => println!("{json}"),
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    return 2;
+                }
+            },
+        }
+        return 0;
+    }
+
+    let list = match &rules_args.command {
+        Some(RulesCommand::List(list)) => list,
+        _ => &rules_args.list,
+    };
+    let result = match (list.format, &list.out) {
+        (RulesFormat::Markdown, Some(dir)) => write_pages(&catalog, dir).map(|count| {
+            println!("Wrote {count} rule pages to {}", dir.display());
+        }),
+        (_, Some(_)) => Err("--out requires --format markdown".to_string()),
+        (RulesFormat::Markdown, None) => {
+            let pages: Vec<String> = catalog.iter().map(markdown_page).collect();
+            print!("{}", pages.join("\n"));
+            Ok(())
+        }
+        (RulesFormat::Json, None) => serde_json::to_string_pretty(&catalog)
+            .map(|json| println!("{json}"))
+            .map_err(|e| e.to_string()),
+        (RulesFormat::Table, None) => {
+            println!("{}", table(&catalog));
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn applies_to(rule: &RuleInfo) -> String {
+    if rule.applies_to.is_empty() {
+        "all files".to_string()
+    } else {
+        rule.applies_to.join(", ")
+    }
+}
+
+fn table(catalog: &[RuleInfo]) -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec!["Rule", "Severity", "Category", "Name", "Applies to"]);
+    for rule in catalog {
+        table.add_row(vec![
+            rule.id.clone(),
+            rule.severity.to_string(),
+            rule.category.to_string(),
+            rule.name.clone(),
+            applies_to(rule),
+        ]);
+    }
+    table
+}
+
+/// Everything known about one rule, for `rules show`.
+fn describe(rule: &RuleInfo) -> String {
+    let mut out = format!("{}: {}\n\n", rule.id, rule.name);
+    out.push_str(&format!("Severity:   {}\n", rule.severity));
+    out.push_str(&format!("Category:   {}\n", rule.category));
+    out.push_str(&format!("Applies to: {}\n", applies_to(rule)));
+    if !rule.tags.is_empty() {
+        out.push_str(&format!("Tags:       {}\n", rule.tags.join(", ")));
+    }
+    match &rule.pattern {
+        Some(pattern) => out.push_str(&format!("Pattern:    {pattern}\n")),
+        None => out.push_str("Pattern:    none (checked in code)\n"),
+    }
+    for (i, (name, value)) in rule.params.iter().enumerate() {
+        let label = if i == 0 { "Params:" } else { "" };
+        out.push_str(&format!("{label:<12}{name} = {value}\n"));
+    }
+    if !rule.description.is_empty() {
+        out.push_str(&format!("\n{}\n", rule.description));
+    }
+    if !rule.examples.is_empty() {
+        out.push_str("\nExamples:\n");
+        for example in &rule.examples {
+            out.push_str(&format!("  {}\n", example.replace('\n', "\n  ")));
+        }
+    }
+    if !rule.remediation.is_empty() {
+        out.push_str(&format!("\nRemediation: {}\n", rule.remediation));
+    }
+    out
+}
+
+/// A code fence longer than any backtick run in `text`, so examples that
+/// themselves contain fences render intact.
+fn fence_for(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn markdown_page(rule: &RuleInfo) -> String {
+    let mut page = format!("# {}: {}\n\n", rule.id, rule.name);
+    page.push_str("| Severity | Category | Applies to |\n|---|---|---|\n");
+    page.push_str(&format!(
+        "| {} | {} | {} |\n\n",
+        rule.severity,
+        rule.category,
+        applies_to(rule)
+    ));
+    if !rule.tags.is_empty() {
+        let tags: Vec<String> = rule.tags.iter().map(|t| format!("`{t}`")).collect();
+        page.push_str(&format!(
+            "Tags: {}
+
+",
+            tags.join(", ")
+        ));
+    }
+
+    if !rule.description.is_empty() {
+        page.push_str(&format!("## Description\n\n{}\n\n", rule.description));
+    }
+    if !rule.examples.is_empty() {
+        page.push_str("## Examples\n\n");
+        for example in &rule.examples {
+            let fence = fence_for(example);
+            page.push_str(&format!("{fence}text\n{example}\n{fence}\n\n"));
+        }
+    }
+    if let Some(pattern) = &rule.pattern {
+        let fence = fence_for(pattern);
+        page.push_str(&format!(
+            "## Pattern\n\n{fence}regex\n{pattern}\n{fence}\n\n"
+        ));
+    }
+    if !rule.remediation.is_empty() {
+        page.push_str(&format!("## Remediation\n\n{}\n\n", rule.remediation));
+    }
+
+    page.push_str(&format!(
+        "## Configuration\n\nChange the severity or turn the rule off in `.skill-issue.toml`:\n\n```toml\n[rules.{}]\nseverity = \"warning\"  # or: enabled = false\n```\n\n",
+        rule.id
+    ));
+    if !rule.params.is_empty() {
+        let params: Vec<String> = rule
+            .params
+            .iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect();
+        page.push_str(&format!(
+            "Tune its parameters (defaults shown):\n\n```toml\n[rules.{}.params]\n{}\n```\n\n",
+            rule.id,
+            params.join("\n")
+        ));
+    }
+    page.push_str("_Generated by `skill-issue rules --format markdown`._\n");
+    page
+}
+
+fn index_page(catalog: &[RuleInfo]) -> String {
+    let mut by_category: BTreeMap<Category, Vec<&RuleInfo>> = BTreeMap::new();
+    for rule in catalog {
+        by_category.entry(rule.category).or_default().push(rule);
+    }
+
+    let mut page = String::from("# skill-issue rules\n");
+    for (category, rules) in by_category {
+        let name = category.to_string();
+        let mut chars = name.chars();
+        let title: String = chars
+            .next()
+            .map(|c| c.to_uppercase().chain(chars).collect())
+            .unwrap_or_default();
+        page.push_str(&format!(
+            "\n## {title}\n\n| Rule | Name | Severity |\n|---|---|---|\n"
+        ));
+        for rule in rules {
+            page.push_str(&format!(
+                "| [{id}]({id}.md) | {} | {} |\n",
+                rule.name,
+                rule.severity,
+                id = rule.id
+            ));
+        }
+    }
+    page.push_str("\n_Generated by `skill-issue rules --format markdown`._\n");
+    page
+}
+
+fn write_pages(catalog: &[RuleInfo], dir: &Path) -> Result<usize, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    let write = |name: String, contents: String| {
+        let path = dir.join(name);
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))
+    };
+    for rule in catalog {
+        write(format!("{}.md", rule.id), markdown_page(rule))?;
+    }
+    write("README.md".to_string(), index_page(catalog))?;
+    Ok(catalog.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fence_outgrows_backticks_in_example() {
+        assert_eq!(fence_for("curl example.com"), "```");
+        assert_eq!(fence_for("```\nrm -rf\n```"), "````");
+    }
+
+    #[test]
+    fn test_markdown_page_sections() {
+        let mut registry = RuleRegistry::new();
+        registry.load_defaults();
+        let catalog = rules::catalog(&registry);
+        let rule = catalog.iter().find(|r| r.id == "SL-INJ-001").unwrap();
+        let page = markdown_page(rule);
+        assert!(page.starts_with("# SL-INJ-001: Prompt Injection Pattern\n"));
+        for section in [
+            "## Description",
+            "## Examples",
+            "## Pattern",
+            "## Remediation",
+        ] {
+            assert!(page.contains(section), "missing {section}");
+        }
+        assert!(page.contains("[rules.SL-INJ-001]"));
+    }
+
+    #[test]
+    fn test_describe_shows_pattern() {
+        let mut registry = RuleRegistry::new();
+        registry.load_defaults();
+        let catalog = rules::catalog(&registry);
+        let find = |id: &str| catalog.iter().find(|r| r.id == id).unwrap();
+
+        let text = describe(find("SL-INJ-011"));
+        assert!(text.starts_with("SL-INJ-011: Active HTML in Markdown\n"));
+        assert!(text.contains("Category:   injection\n"));
+        assert!(text.contains("Applies to: markdown\n"));
+        assert!(text.contains("Pattern:    (?i)<(?:iframe|"));
+
+        let text = describe(find("SL-INJ-013"));
+        assert!(text.contains("Pattern:    none (checked in code)"));
+    }
+}
@@ -0,0 +1,368 @@
+use crate::baseline;
+use crate::config::{AllowlistEntry, CliArgs, Config, ConfigFile};
+use crate::finding::{report_path, Finding};
+use crate::pipeline;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct SuppressionsArgs {
+    /// Skill directory or remote specifier (owner/repo@skill, GitHub URL)
+    #[arg(default_value = ".")]
+    pub target: String,
+
+    /// Also review the entries of this baseline file (or saved JSON report)
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// Print JSON instead of text
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Every suppression in effect for a target, with what each one hides.
+#[derive(Debug, Serialize)]
+struct Review {
+    target: String,
+    suppressions: Vec<Suppression>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    /// `--ignore` or `ignore` under `[settings]`.
+    Ignore,
+    /// `enabled = false` under `[rules.<ID>]`.
+    Disabled,
+    /// An `[[allowlist]]` entry.
+    Allowlist,
+    /// A fingerprint in `--baseline`.
+    Baseline,
+}
+
+#[derive(Debug, Serialize)]
+struct Suppression {
+    kind: Kind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule: Option<String>,
+    /// Path fragment an allowlist entry is limited to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    /// Where the suppression is declared: a config file, baseline or flag.
+    source: String,
+    suppresses: Vec<Suppressed>,
+}
+
+/// A finding a suppression hides from the report.
+#[derive(Debug, Serialize)]
+struct Suppressed {
+    rule_id: String,
+    file: String,
+    line: usize,
+    message: String,
+    fingerprint: String,
+}
+
+impl Suppression {
+    fn new(kind: Kind, source: &str) -> Self {
+        Self {
+            kind,
+            rule: None,
+            file: None,
+            fingerprint: None,
+            reason: None,
+            source: source.to_string(),
+            suppresses: Vec::new(),
+        }
+    }
+
+    fn covers(&self, finding: &Finding, fingerprint: &str) -> bool {
+        match self.kind {
+            Kind::Ignore | Kind::Disabled => self.rule.as_deref() == Some(&finding.rule_id),
+            Kind::Allowlist => AllowlistEntry {
+                rule: self.rule.clone().unwrap_or_default(),
+                file: self.file.clone(),
+                reason: None,
+            }
+            .matches(&finding.rule_id, &finding.location.file.to_string_lossy()),
+            Kind::Baseline => self.fingerprint.as_deref() == Some(fingerprint),
+        }
+    }
+}
+
+pub fn run(suppressions_args: &SuppressionsArgs, args: &CliArgs) -> i32 {
+    match review(suppressions_args, args) {
+        Ok(review) if suppressions_args.json => match serde_json::to_string_pretty(&review) {
+            Ok(json) => {
+                println!("{json}");
+                0
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                2
+            }
+        },
+        Ok(review) => {
+            println!("{}", format_text(&review));
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            2
+        }
+    }
+}
+
+fn review(suppressions_args: &SuppressionsArgs, args: &CliArgs) -> Result<Review, String> {
+    let target = &suppressions_args.target;
+    let local = Path::new(target).exists();
+    // The same config file a scan of the target would use.
+    let config_path = args.config.clone().or_else(|| {
+        let dir = Path::new(target);
+        let dir = if dir.is_file() {
+            dir.parent().unwrap_or(dir)
+        } else {
+            dir
+        };
+        local.then(|| dir.join(".skill-issue.toml"))
+    });
+    let mut file = config_path
+        .as_deref()
+        .and_then(pipeline::load_config_file)
+        .unwrap_or_default();
+    if args.config.is_none() {
+        file.signatures = Default::default();
+    }
+    let source = config_path
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+
+    let mut suppressions = declared(&file, &args.ignore, &source);
+    if let Some(ref path) = suppressions_args.baseline {
+        let mut fingerprints: Vec<String> = baseline::load(path)?.into_iter().collect();
+        fingerprints.sort();
+        suppressions.extend(fingerprints.into_iter().map(|fingerprint| Suppression {
+            fingerprint: Some(fingerprint),
+            ..Suppression::new(Kind::Baseline, &path.display().to_string())
+        }));
+    }
+
+    let redact = args.redact || file.settings.redact;
+    let findings = scan_unsuppressed(target, local, args, file)?;
+    attach(&mut suppressions, findings, redact);
+    Ok(Review {
+        target: target.clone(),
+        suppressions,
+    })
+}
+
+/// Suppressions declared in the config file and on the command line, where
+/// `--ignore` replaces the file's list as it does for a scan.
+fn declared(file: &ConfigFile, cli_ignore: &[String], source: &str) -> Vec<Suppression> {
+    let (ignore, ignore_source) = if cli_ignore.is_empty() {
+        (file.settings.ignore.as_slice(), source)
+    } else {
+        (cli_ignore, "--ignore")
+    };
+    let mut suppressions: Vec<Suppression> = ignore
+        .iter()
+        .map(|rule| Suppression {
+            rule: Some(rule.clone()),
+            ..Suppression::new(Kind::Ignore, ignore_source)
+        })
+        .collect();
+
+    let mut disabled: Vec<&String> = file
+        .rules
+        .iter()
+        .filter(|(_, o)| o.enabled == Some(false))
+        .map(|(id, _)| id)
+        .collect();
+    disabled.sort();
+    suppressions.extend(disabled.into_iter().map(|rule| Suppression {
+        rule: Some(rule.clone()),
+        ..Suppression::new(Kind::Disabled, source)
+    }));
+
+    suppressions.extend(file.allowlist.iter().map(|entry| Suppression {
+        rule: Some(entry.rule.clone()),
+        file: entry.file.clone(),
+        reason: entry.reason.clone(),
+        ..Suppression::new(Kind::Allowlist, source)
+    }));
+    suppressions
+}
+
+/// Findings for the target with every suppression lifted and nothing
+/// redacted, so baseline fingerprints still match.
+fn scan_unsuppressed(
+    target: &str,
+    local: bool,
+    args: &CliArgs,
+    mut file: ConfigFile,
+) -> Result<Vec<Finding>, String> {
+    file.settings.ignore.clear();
+    file.settings.redact = false;
+    file.allowlist.clear();
+    for rule in file.rules.values_mut() {
+        rule.enabled = None;
+    }
+
+    let mut scan_args = args.clone();
+    scan_args.command = None;
+    scan_args.ignore.clear();
+    scan_args.redact = false;
+    scan_args.baseline = None;
+    scan_args.update_baseline = false;
+    if local {
+        scan_args.path = PathBuf::from(target);
+    } else {
+        scan_args.remote = Some(target.to_string());
+    }
+    let config = Config::from_args_and_file(scan_args, Some(file));
+    Ok(pipeline::run(&config)?.findings)
+}
+
+/// Record each finding under every suppression that covers it. A finding
+/// can be hidden for more than one reason; each is listed.
+fn attach(suppressions: &mut [Suppression], findings: Vec<Finding>, redact: bool) {
+    for mut finding in findings {
+        let fingerprint = finding.fingerprint();
+        if redact {
+            finding.redact();
+        }
+        for s in suppressions.iter_mut() {
+            if s.covers(&finding, &fingerprint) {
+                s.suppresses.push(Suppressed {
+                    rule_id: finding.rule_id.clone(),
+                    file: report_path(&finding.location.file),
+                    line: finding.location.line,
+                    message: finding.message.clone(),
+                    fingerprint: fingerprint.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn format_text(review: &Review) -> String {
+    if review.suppressions.is_empty() {
+        return format!("No suppressions in effect for {}", review.target);
+    }
+    let mut out = vec![format!("Suppressions in effect for {}:", review.target)];
+    for s in &review.suppressions {
+        let mut what = match s.kind {
+            Kind::Baseline => s.fingerprint.clone().unwrap_or_default(),
+            _ => s.rule.clone().unwrap_or_default(),
+        };
+        if let Some(ref file) = s.file {
+            what.push_str(&format!(" in {file}"));
+        }
+        let kind = format!("{:?}", s.kind).to_lowercase();
+        out.push(format!("\n  {kind:<10} {what}  ({})", s.source));
+        if let Some(ref reason) = s.reason {
+            out.push(format!("             reason: {reason}"));
+        }
+        if s.suppresses.is_empty() {
+            out.push("             suppresses nothing (stale)".to_string());
+        }
+        for f in &s.suppresses {
+            out.push(format!(
+                "             {}:{}  {}  {}",
+                f.file, f.line, f.rule_id, f.message
+            ));
+        }
+    }
+    let hidden: usize = review.suppressions.iter().map(|s| s.suppresses.len()).sum();
+    let stale = review
+        .suppressions
+        .iter()
+        .filter(|s| s.suppresses.is_empty())
+        .count();
+    out.push(format!(
+        "\n{} suppression(s) hiding {hidden} finding(s); {stale} suppress nothing",
+        review.suppressions.len()
+    ));
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Severity;
+
+    #[test]
+    fn test_declared_lists_ignores_disabled_rules_and_allowlist() {
+        let file: ConfigFile = toml::from_str(
+            r#"
+            [settings]
+            ignore = ["SL-NET-002"]
+
+            [rules.SL-EXE-001]
+            enabled = false
+
+            [rules.SL-SEC-001]
+            severity = "warning"
+
+            [[allowlist]]
+            rule = "SL-SEC-004"
+            file = "tests/"
+            reason = "fixture"
+            "#,
+        )
+        .unwrap();
+        let s = declared(&file, &[], ".skill-issue.toml");
+        let kinds: Vec<Kind> = s.iter().map(|s| s.kind).collect();
+        assert_eq!(kinds, [Kind::Ignore, Kind::Disabled, Kind::Allowlist]);
+        assert_eq!(s[1].rule.as_deref(), Some("SL-EXE-001"));
+        assert_eq!(s[2].reason.as_deref(), Some("fixture"));
+
+        let cli = declared(&file, &["SL-INJ-001".to_string()], ".skill-issue.toml");
+        assert_eq!(cli[0].rule.as_deref(), Some("SL-INJ-001"));
+        assert_eq!(cli[0].source, "--ignore");
+        assert_eq!(cli.len(), 3);
+    }
+
+    #[test]
+    fn test_attach_records_what_each_suppression_hides() {
+        let hidden = Finding::test("SL-SEC-004", Severity::Error, "tests/conf.py", 2);
+        let fingerprint = hidden.fingerprint();
+        let mut suppressions = vec![
+            Suppression {
+                rule: Some("SL-SEC-004".to_string()),
+                file: Some("tests/".to_string()),
+                ..Suppression::new(Kind::Allowlist, "c")
+            },
+            Suppression {
+                fingerprint: Some(fingerprint.clone()),
+                ..Suppression::new(Kind::Baseline, "b")
+            },
+            Suppression {
+                rule: Some("SL-NET-002".to_string()),
+                ..Suppression::new(Kind::Ignore, "c")
+            },
+        ];
+        attach(
+            &mut suppressions,
+            vec![
+                hidden,
+                Finding::test("SL-SEC-004", Severity::Error, "src/conf.py", 2),
+            ],
+            false,
+        );
+        assert_eq!(suppressions[0].suppresses.len(), 1);
+        assert_eq!(suppressions[0].suppresses[0].file, "tests/conf.py");
+        assert_eq!(suppressions[1].suppresses[0].fingerprint, fingerprint);
+        assert!(suppressions[2].suppresses.is_empty());
+        assert!(format_text(&Review {
+            target: ".".to_string(),
+            suppressions,
+        })
+        .ends_with("3 suppression(s) hiding 2 finding(s); 1 suppress nothing"));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::config::CliArgs;
+use crate::pipeline;
+use crate::signature::{self, Verification};
+
+/// Verify a skill's detached minisign signature against trusted keys.
+///
+/// Signatures cover the canonical manifest printed by `--manifest`; sign it
+/// with `minisign -S -m manifest.txt -x <skill>/skill.minisig`.
+#[derive(clap::Args, Debug, Clone)]
+pub struct VerifySignatureArgs {
+    /// Skill directory or remote specifier (owner/repo@skill, GitHub URL)
+    #[arg(default_value = ".")]
+    pub target: String,
+
+    /// Print the canonical manifest a signature must cover, then exit
+    #[arg(long)]
+    pub manifest: bool,
+}
+
+pub fn run(sig_args: &VerifySignatureArgs, args: &CliArgs) -> i32 {
+    let entries = match super::target_entries(&sig_args.target, args) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+
+    if sig_args.manifest {
+        print!("{}", signature::manifest(&entries));
+        return 0;
+    }
+
+    let mut key_specs = args.trusted_keys.clone();
+    if let Some(cf) = args.config.as_deref().and_then(pipeline::load_config_file) {
+        key_specs.extend(cf.signatures.trusted_keys);
+    }
+    if key_specs.is_empty() {
+        eprintln!("error: no trusted keys configured (use --trusted-key or [signatures] trusted_keys in --config)");
+        return 2;
+    }
+    let keys = match signature::load_trusted_keys(&key_specs) {
+        Ok(k) => k,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+
+    match signature::verify(&entries, &keys) {
+        Verification::Valid { key } => {
+            println!("Signature valid: {} signed by {key}", sig_args.target);
+            0
+        }
+        other => {
+            for f in signature::findings(&other, true) {
+                eprintln!("error: {} [{}]", f.message, f.rule_id);
+            }
+            2
+        }
+    }
+}
@@ -1,13 +1,27 @@
-use crate::finding::Severity;
+use crate::areas::{self, AreaPolicy};
+use crate::commands::Command;
+use crate::diagnostics::Diagnostic;
+use crate::email::EmailSettings;
+use crate::escalation::{self, Escalation};
+use crate::finding::{report_path, Confidence, Severity};
+use crate::rules::combining_rule::DEFAULT_MAX_COMBINING_MARKS;
+use crate::rules::quality_rule;
+use crate::rules::regex_rule::ParamValue;
+use crate::rules::schema_rule::SkillSpec;
+use crate::scanner::DEFAULT_SKILL_MARKERS;
+use crate::signature;
+use crate::simulate::Profile;
 use clap::Parser;
 use serde::Deserialize;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(
     name = "skill-issue",
     version,
+    disable_version_flag = true,
     about = "Static security analyzer for Claude skill directories — skill-issue.sh"
 )]
 pub struct CliArgs {
@@ -15,24 +29,52 @@ pub struct CliArgs {
     #[arg(default_value = ".")]
     pub path: PathBuf,
 
+    /// More skill directories, scanned into the same report
+    #[arg(value_name = "MORE_PATHS")]
+    pub more_paths: Vec<PathBuf>,
+
+    /// Find every skill directory under PATH, scan each on its own and report pass/fail per skill
+    #[arg(long, conflicts_with_all = ["remote", "more_paths"])]
+    pub batch: bool,
+
+    /// What kind of target to scan
+    #[arg(long, value_enum, default_value = "skill")]
+    pub mode: ScanMode,
+
     /// Output format
     #[arg(short, long, default_value = "table")]
     pub format: OutputFormat,
 
+    /// Column convention for locations [default: utf16 for SARIF, char otherwise]
+    #[arg(long, value_enum, value_name = "UNIT")]
+    pub columns: Option<ColumnUnit>,
+
     /// Path to configuration file
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub config: Option<PathBuf>,
 
     /// Minimum severity to report
     #[arg(short, long, default_value = "info")]
     pub severity: Severity,
 
+    /// Skip running rules whose default (or configured) severity is below this
+    #[arg(long, value_name = "SEVERITY")]
+    pub min_rule_severity: Option<Severity>,
+
+    /// Minimum confidence to report (low, medium or high)
+    #[arg(long, default_value = "low", value_name = "CONFIDENCE")]
+    pub min_confidence: Confidence,
+
     /// Rule IDs to ignore (can be repeated)
     #[arg(long, num_args = 1..)]
     pub ignore: Vec<String>,
 
+    /// Only report findings carrying one of these tags (e.g. exfiltration, persistence)
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+
     /// Minimum severity that causes a non-zero exit code
-    #[arg(long, default_value = "error")]
+    #[arg(long, default_value = "error", global = true)]
     pub error_on: Severity,
 
     /// Suppress all output except findings
@@ -40,20 +82,200 @@ pub struct CliArgs {
     pub quiet: bool,
 
     /// Show verbose output including rule details
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub verbose: bool,
 
     /// Disable colored output
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub no_color: bool,
 
-    /// Remote GitHub skill specifier (e.g. owner/repo, owner/repo@skill-name, GitHub URL)
+    /// Remote GitHub skill specifier (e.g. owner/repo, owner/repo@skill-name, GitHub URL, or `.` for this checkout's origin)
     #[arg(long)]
     pub remote: Option<String>,
 
     /// GitHub API token for authenticated requests (or set GITHUB_TOKEN env var)
-    #[arg(long, env = "GITHUB_TOKEN")]
+    #[arg(long, env = "GITHUB_TOKEN", global = true)]
     pub github_token: Option<String>,
+
+    /// Seconds to wait for a connection to GitHub
+    #[arg(long, value_name = "SECS", default_value_t = 10, global = true)]
+    pub connect_timeout: u64,
+
+    /// Seconds to wait for a GitHub response, and again for its body
+    #[arg(long, value_name = "SECS", default_value_t = 30, global = true)]
+    pub read_timeout: u64,
+
+    /// Times to retry a GitHub request after a timeout, connection failure or 5xx
+    #[arg(long, value_name = "N", default_value_t = 2, global = true)]
+    pub retries: u32,
+
+    /// Seconds to pause, per request, for a GitHub rate limit to lift before giving up
+    #[arg(long, value_name = "SECS", default_value_t = 120, global = true)]
+    pub max_rate_limit_wait: u64,
+
+    /// Flag remote skills changed more recently than this age (e.g. 24h, 7d)
+    #[arg(long, value_name = "AGE", value_parser = crate::freshness::parse_age)]
+    pub min_age: Option<chrono::TimeDelta>,
+
+    /// Flag remote skills not changed for longer than this age (e.g. 2y)
+    #[arg(long, value_name = "AGE", value_parser = crate::freshness::parse_age)]
+    pub max_age: Option<chrono::TimeDelta>,
+
+    /// Report publisher signals for remote scans: stars, owner account age, fork divergence
+    #[arg(long)]
+    pub reputation: bool,
+
+    /// Report a missing or untrusted skill signature as an error
+    #[arg(long)]
+    pub require_signature: bool,
+
+    /// Check detected GitHub and Slack tokens with their issuer; live tokens become critical
+    #[arg(long)]
+    pub verify_secrets: bool,
+
+    /// Fetch every URL in the scanned files; report links that redirect to another domain, serve executables or are dead
+    #[arg(long)]
+    pub check_links: bool,
+
+    /// Make no network requests: skip --check-links, --verify-secrets, --reputation and update checks, and refuse --remote
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// File name that marks a skill's root directory (repeatable) [default: SKILL.md]
+    #[arg(long = "skill-marker", value_name = "FILE", global = true)]
+    pub skill_markers: Vec<String>,
+
+    /// Also load every `*.toml` pattern file in this directory
+    #[arg(long, value_name = "DIR", global = true)]
+    pub rules_dir: Option<PathBuf>,
+
+    /// Mask the middle of detected secrets in findings, so reports can be shared
+    #[arg(long)]
+    pub redact: bool,
+
+    /// Report a clean pass for a target with no SKILL.md or no scannable files
+    #[arg(long)]
+    pub allow_empty: bool,
+
+    /// Write run metadata (exit code and reason, thresholds, counts, target) as JSON to this path
+    #[arg(long, value_name = "PATH")]
+    pub meta_out: Option<PathBuf>,
+
+    /// Write a JSON Lines trace of files scanned, rules run, suppressions applied and HTTP requests made to this path
+    #[arg(long, value_name = "PATH", global = true)]
+    pub trace_log: Option<PathBuf>,
+
+    /// Write a hash-chained scan attestation (content hashes, rule set, summary) to this path
+    #[arg(long, value_name = "PATH")]
+    pub attest: Option<PathBuf>,
+
+    /// Sign the JSON or SARIF report with this Ed25519 private key (PKCS#8 PEM or DER)
+    #[arg(long, value_name = "KEY", requires = "signature_out")]
+    pub sign_report: Option<PathBuf>,
+
+    /// Where to write the detached report signature (a JWS) for --sign-report
+    #[arg(long, value_name = "PATH", requires = "sign_report")]
+    pub signature_out: Option<PathBuf>,
+
+    /// Stop checking files at the first finding at or above --error-on (the report is then partial)
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Hide findings recorded in this baseline file (or saved JSON report)
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// Write this scan's findings to --baseline instead of hiding them
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// Re-check only the files and rules behind the findings of this saved JSON report
+    #[arg(long, value_name = "REPORT", conflicts_with = "update_baseline")]
+    pub rerun_from: Option<PathBuf>,
+
+    /// Scan once and compare findings and exit code with those under a stricter or proposed policy: strict, lenient, or a config file
+    #[arg(
+        long,
+        value_name = "PROFILE",
+        conflicts_with_all = ["batch", "more_paths", "update_baseline", "history"]
+    )]
+    pub simulate_profile: Option<Profile>,
+
+    /// Check up to N files at once [default: number of CPUs]
+    #[arg(short, long, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Omit timestamps, durations and host names, and break ordering ties, so identical content gives byte-identical reports
+    #[arg(long)]
+    pub deterministic: bool,
+
+    /// De-obfuscate text (leetspeak, spaced letters) and re-check injection phrases
+    #[arg(long)]
+    pub normalize: bool,
+
+    /// Print version (add --verbose for rule-set versions and update status)
+    #[arg(short = 'V', long)]
+    pub version: bool,
+
+    /// Check at most daily for a newer release (also enabled by SKILL_ISSUE_UPDATE_CHECK=1)
+    #[arg(long, global = true)]
+    pub check_updates: bool,
+
+    /// Collapse rules that flood a file and downgrade findings in vendored/minified assets
+    #[arg(long)]
+    pub suppress_noise: bool,
+
+    /// Also run the opt-in SKILL.md quality checks (SL-QUAL-*)
+    #[arg(long)]
+    pub quality: bool,
+
+    /// Reuse rule results for files already scanned with the same rules (kept in the cache directory)
+    #[arg(long)]
+    pub rule_cache: bool,
+
+    /// Record this scan's findings and score in the local history database
+    #[arg(long)]
+    pub history: bool,
+
+    /// History database path (default: ~/.local/share/skill-issue/history.db)
+    #[arg(
+        long,
+        value_name = "PATH",
+        env = "SKILL_ISSUE_HISTORY_DB",
+        global = true
+    )]
+    pub history_db: Option<PathBuf>,
+
+    /// Trusted minisign public key (base64 or path to a .pub file; can be repeated)
+    #[arg(long = "trusted-key", global = true)]
+    pub trusted_keys: Vec<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ScanMode {
+    /// A skill directory tree
+    Skill,
+    /// Agent instruction files (CLAUDE.md, AGENTS.md, .cursorrules, GEMINI.md)
+    AgentFiles,
+}
+
+/// Rule families that apply to agent instruction files. These files are prose
+/// read straight into the agent's context, so only the injection, hidden-content,
+/// social-engineering and safety-bypass rules are meaningful.
+const AGENT_FILE_RULE_PREFIXES: &[&str] = &["SL-INJ-", "SL-HID-", "SL-SOC-", "SL-BYP-"];
+
+impl ScanMode {
+    pub fn includes_rule(self, rule_id: &str) -> bool {
+        match self {
+            ScanMode::Skill => true,
+            ScanMode::AgentFiles => AGENT_FILE_RULE_PREFIXES
+                .iter()
+                .any(|prefix| rule_id.starts_with(prefix)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -61,9 +283,28 @@ pub enum OutputFormat {
     Table,
     Json,
     Sarif,
+    /// One `file:line:col: severity RULE message` line per finding
+    Vscode,
+    /// Standalone HTML page with filters and source snippets
+    Html,
+    /// GitHub-flavored Markdown summary for pull request comments
+    Markdown,
+    /// Findings grouped by file, each under the source lines it points at
+    Stylish,
 }
 
-#[derive(Debug, Deserialize, Default)]
+/// How columns are counted in reported locations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColumnUnit {
+    /// Unicode characters (code points)
+    Char,
+    /// UTF-16 code units, as editors and SARIF viewers count them
+    Utf16,
+    /// Bytes of the UTF-8 text
+    Byte,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
 pub struct ConfigFile {
     #[serde(default)]
     pub settings: ConfigSettings,
@@ -71,9 +312,49 @@ pub struct ConfigFile {
     pub rules: HashMap<String, RuleOverride>,
     #[serde(default)]
     pub allowlist: Vec<AllowlistEntry>,
+    #[serde(default)]
+    pub signatures: SignatureSettings,
+    /// Replaces the default escalations; `escalations = []` turns them off.
+    pub escalations: Option<Vec<Escalation>>,
+    #[serde(default)]
+    pub policy: PolicySettings,
+    pub email: Option<EmailSettings>,
+}
+
+impl ConfigFile {
+    /// A config that lives in the scanned target, without what only the user
+    /// may set: trust roots, whether the signature checks report, noise
+    /// suppression, and where the report is emailed with whose credentials.
+    /// A skill must not vouch for itself.
+    pub fn from_target(mut self) -> Self {
+        let signature_rule = |id: &str| id.starts_with(signature::RULE_PREFIX);
+        self.signatures = Default::default();
+        self.settings.suppress_noise = false;
+        self.settings.ignore.retain(|id| !signature_rule(id));
+        self.rules.retain(|id, _| !signature_rule(id));
+        self.allowlist.retain(|entry| !signature_rule(&entry.rule));
+        self.email = None;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct PolicySettings {
+    /// Severity per category or rule ID in `references/`, `assets/` and
+    /// `scripts/`. An area listed here replaces that area's defaults.
+    #[serde(default)]
+    pub areas: AreaPolicy,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct SignatureSettings {
+    #[serde(default)]
+    pub require: bool,
+    #[serde(default)]
+    pub trusted_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
 #[allow(dead_code)]
 pub struct ConfigSettings {
     pub severity: Option<String>,
@@ -81,15 +362,34 @@ pub struct ConfigSettings {
     pub error_on: Option<String>,
     #[serde(default)]
     pub ignore: Vec<String>,
+    #[serde(default)]
+    pub normalize: bool,
+    #[serde(default)]
+    pub suppress_noise: bool,
+    #[serde(default)]
+    pub quality: bool,
+    #[serde(default)]
+    pub rule_cache: bool,
+    #[serde(default)]
+    pub redact: bool,
+    #[serde(default)]
+    pub skill_markers: Vec<String>,
+    pub rules_dir: Option<PathBuf>,
+    pub max_combining_marks: Option<usize>,
+    /// Frontmatter schema revision for SL-META-013..015 (`2025-10`, `2025-12`).
+    pub skill_spec: Option<SkillSpec>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct RuleOverride {
     pub severity: Option<String>,
     pub enabled: Option<bool>,
+    /// Values for the parameters a pattern rule declares.
+    #[serde(default)]
+    pub params: BTreeMap<String, ParamValue>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub struct AllowlistEntry {
     pub rule: String,
@@ -97,21 +397,92 @@ pub struct AllowlistEntry {
     pub reason: Option<String>,
 }
 
+impl AllowlistEntry {
+    /// The entry covers `rule_id` in every file, or in paths containing `file`.
+    /// Either may separate with `/` or `\`.
+    pub fn matches(&self, rule_id: &str, file_path: &str) -> bool {
+        self.rule == rule_id
+            && self
+                .file
+                .as_ref()
+                .is_none_or(|f| report_path(Path::new(file_path)).contains(&f.replace('\\', "/")))
+    }
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct Config {
     pub path: PathBuf,
+    pub mode: ScanMode,
     pub format: OutputFormat,
+    pub columns: ColumnUnit,
     pub min_severity: Severity,
+    pub min_rule_severity: Severity,
+    pub min_confidence: Confidence,
     pub ignore: Vec<String>,
+    pub tags: Vec<String>,
     pub error_on: Severity,
     pub quiet: bool,
     pub verbose: bool,
     pub no_color: bool,
     pub rule_overrides: HashMap<String, RuleOverride>,
     pub allowlist: Vec<AllowlistEntry>,
+    pub escalations: Vec<Escalation>,
+    pub areas: AreaPolicy,
+    pub email: Option<EmailSettings>,
     pub remote: Option<String>,
     pub github_token: Option<String>,
+    pub min_age: Option<chrono::TimeDelta>,
+    pub max_age: Option<chrono::TimeDelta>,
+    pub reputation: bool,
+    pub require_signature: bool,
+    pub trusted_keys: Vec<String>,
+    pub verify_secrets: bool,
+    pub check_links: bool,
+    /// No network access; the flags that need it are already off.
+    pub offline: bool,
+    pub redact: bool,
+    pub skill_markers: Vec<String>,
+    pub rules_dir: Option<PathBuf>,
+    pub allow_empty: bool,
+    pub meta_out: Option<PathBuf>,
+    pub attest: Option<PathBuf>,
+    pub sign_report: Option<PathBuf>,
+    pub signature_out: Option<PathBuf>,
+    pub deterministic: bool,
+    pub baseline: Option<PathBuf>,
+    pub update_baseline: bool,
+    pub rerun_from: Option<PathBuf>,
+    pub fail_fast: bool,
+    /// Worker threads for file rules; always 1 with `fail_fast`.
+    pub jobs: usize,
+    pub history: bool,
+    pub history_db: Option<PathBuf>,
+    pub normalize: bool,
+    pub suppress_noise: bool,
+    /// Run the opt-in `quality` rules.
+    pub quality: bool,
+    /// Cache rule results by file content in the on-disk cache.
+    pub rule_cache: bool,
+    pub max_combining_marks: usize,
+    pub skill_spec: SkillSpec,
+    pub check_updates: bool,
+    /// Problems reading the config file, reported with the scan.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Markers from the command line, else the config file, else the defaults.
+pub fn skill_markers(cli: &[String], file: &[String]) -> Vec<String> {
+    if !cli.is_empty() {
+        cli.to_vec()
+    } else if !file.is_empty() {
+        file.to_vec()
+    } else {
+        DEFAULT_SKILL_MARKERS
+            .iter()
+            .map(|m| m.to_string())
+            .collect()
+    }
 }
 
 impl Config {
@@ -126,17 +497,74 @@ impl Config {
 
         Config {
             path: args.path,
+            mode: args.mode,
+            columns: args.columns.unwrap_or(match args.format {
+                OutputFormat::Sarif => ColumnUnit::Utf16,
+                _ => ColumnUnit::Char,
+            }),
             format: args.format,
             min_severity: args.severity,
+            min_rule_severity: args.min_rule_severity.unwrap_or(Severity::Info),
+            min_confidence: args.min_confidence,
             ignore,
+            tags: args.tags,
             error_on: args.error_on,
             quiet: args.quiet,
             verbose: args.verbose,
             no_color: args.no_color,
             rule_overrides: file.rules,
             allowlist: file.allowlist,
+            escalations: file.escalations.unwrap_or_else(escalation::defaults),
+            areas: areas::defaults()
+                .into_iter()
+                .chain(file.policy.areas)
+                .collect(),
+            email: file.email,
             remote: args.remote,
             github_token: args.github_token,
+            min_age: args.min_age,
+            max_age: args.max_age,
+            reputation: args.reputation && !args.offline,
+            require_signature: args.require_signature || file.signatures.require,
+            trusted_keys: args
+                .trusted_keys
+                .into_iter()
+                .chain(file.signatures.trusted_keys)
+                .collect(),
+            verify_secrets: args.verify_secrets && !args.offline,
+            check_links: args.check_links && !args.offline,
+            offline: args.offline,
+            redact: args.redact || file.settings.redact,
+            skill_markers: skill_markers(&args.skill_markers, &file.settings.skill_markers),
+            rules_dir: args.rules_dir.or(file.settings.rules_dir),
+            allow_empty: args.allow_empty,
+            meta_out: args.meta_out,
+            attest: args.attest,
+            sign_report: args.sign_report,
+            signature_out: args.signature_out,
+            deterministic: args.deterministic,
+            baseline: args.baseline,
+            update_baseline: args.update_baseline,
+            rerun_from: args.rerun_from,
+            fail_fast: args.fail_fast,
+            jobs: match args.jobs {
+                _ if args.fail_fast => 1,
+                Some(n) => n.get(),
+                None => std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            },
+            history: args.history,
+            history_db: args.history_db,
+            normalize: args.normalize || file.settings.normalize,
+            suppress_noise: args.suppress_noise || file.settings.suppress_noise,
+            quality: args.quality || file.settings.quality,
+            rule_cache: args.rule_cache || file.settings.rule_cache,
+            max_combining_marks: file
+                .settings
+                .max_combining_marks
+                .unwrap_or(DEFAULT_MAX_COMBINING_MARKS),
+            skill_spec: file.settings.skill_spec.unwrap_or_default(),
+            check_updates: args.check_updates && !args.offline,
+            diagnostics: Vec::new(),
         }
     }
 
@@ -145,13 +573,9 @@ impl Config {
     }
 
     pub fn is_allowlisted(&self, rule_id: &str, file_path: &str) -> bool {
-        self.allowlist.iter().any(|entry| {
-            entry.rule == rule_id
-                && entry
-                    .file
-                    .as_ref()
-                    .is_none_or(|f| file_path.contains(f.as_str()))
-        })
+        self.allowlist
+            .iter()
+            .any(|entry| entry.matches(rule_id, file_path))
     }
 
     pub fn effective_severity(&self, rule_id: &str, default: Severity) -> Severity {
@@ -162,10 +586,35 @@ impl Config {
             .unwrap_or(default)
     }
 
+    /// Whether the config file sets the rule's severity under `[rules.ID]`.
+    pub fn has_severity_override(&self, rule_id: &str) -> bool {
+        self.rule_overrides
+            .get(rule_id)
+            .is_some_and(|o| o.severity.is_some())
+    }
+
+    /// Whether a rule at `severity` (after overrides) is worth running at all.
+    pub fn meets_min_rule_severity(&self, rule_id: &str, severity: Severity) -> bool {
+        self.effective_severity(rule_id, severity) >= self.min_rule_severity
+    }
+
+    /// Parameter overrides, per rule ID, in ID order.
+    pub fn rule_params(&self) -> Vec<(&str, &BTreeMap<String, ParamValue>)> {
+        let mut params: Vec<_> = self
+            .rule_overrides
+            .iter()
+            .filter(|(_, o)| !o.params.is_empty())
+            .map(|(id, o)| (id.as_str(), &o.params))
+            .collect();
+        params.sort_by_key(|(id, _)| *id);
+        params
+    }
+
+    /// An explicit `enabled` wins; otherwise quality rules need `--quality`.
     pub fn is_rule_enabled(&self, rule_id: &str) -> bool {
         self.rule_overrides
             .get(rule_id)
             .and_then(|o| o.enabled)
-            .unwrap_or(true)
+            .unwrap_or(self.quality || !rule_id.starts_with(quality_rule::RULE_PREFIX))
     }
 }
@@ -0,0 +1,141 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::remote::FailedSkill;
+use crate::rules::RuleDoc;
+use crate::scanner::{ScannedFile, SkipReason, SkippedFile};
+
+const RULE_ID: &str = "SL-COV-001";
+const RULE_NAME: &str = "File Not Scanned";
+
+pub const RULE_DOC: RuleDoc = RuleDoc {
+    id: RULE_ID,
+    name: RULE_NAME,
+    severity: Severity::Info,
+    applies_to: &[],
+    description: "A file in the target that no rule looked at: binary or non-UTF-8 content, a file over the 10 MiB size limit, a read error, or a file type no enabled rule checks. Reporting these means a clean result never hides content that was not analyzed.",
+    remediation: "Check the listed files by hand, or convert them to a scannable form. A binary a skill depends on deserves the same review as its scripts.",
+};
+
+const FAILED_SKILL_RULE_ID: &str = "SL-COV-002";
+const FAILED_SKILL_RULE_NAME: &str = "Skill Not Fetched";
+
+pub const FAILED_SKILL_RULE_DOC: RuleDoc = RuleDoc {
+    id: FAILED_SKILL_RULE_ID,
+    name: FAILED_SKILL_RULE_NAME,
+    severity: Severity::Error,
+    applies_to: &[],
+    description: "A skill in a remote repository whose files could not all be fetched: a file missing at the scanned commit, a rate limit that outlasted `--max-rate-limit-wait`, or a network failure. The other skills were still scanned, but this one was not, so the result says nothing about it.",
+    remediation: "Rescan once the cause is fixed, for example with a `--github-token` for a higher rate limit, or scan the skill on its own with `--remote owner/repo@skill`.",
+};
+
+/// One error finding per remote skill that could not be fetched, reported on
+/// the skill's directory.
+pub fn failed_skill_findings(failed: &[FailedSkill]) -> Vec<Finding> {
+    failed
+        .iter()
+        .map(|s| Finding {
+            rule_id: FAILED_SKILL_RULE_ID.to_string(),
+            rule_name: FAILED_SKILL_RULE_NAME.to_string(),
+            severity: Severity::Error,
+            message: format!("Skill was not scanned: {}", s.error),
+            location: Location {
+                file: s.root.clone(),
+                line: 1,
+                column: 1,
+            },
+            matched_text: String::new(),
+            confidence: Confidence::High,
+            tags: Vec::new(),
+            domain: None,
+        })
+        .collect()
+}
+
+/// One info finding per file that was found but not analyzed.
+pub fn skipped_findings(skipped: &[SkippedFile]) -> Vec<Finding> {
+    skipped
+        .iter()
+        .map(|s| Finding {
+            rule_id: RULE_ID.to_string(),
+            rule_name: RULE_NAME.to_string(),
+            severity: Severity::Info,
+            message: format!("File was not scanned ({})", s.reason),
+            location: Location {
+                file: s.relative_path.clone(),
+                line: 1,
+                column: 1,
+            },
+            matched_text: String::new(),
+            confidence: Confidence::High,
+            tags: Vec::new(),
+            domain: None,
+        })
+        .collect()
+}
+
+/// Files no enabled rule applies to, judged by `has_rules`.
+pub fn unchecked(
+    files: &[ScannedFile],
+    has_rules: impl Fn(&ScannedFile) -> bool,
+) -> Vec<SkippedFile> {
+    files
+        .iter()
+        .filter(|f| !has_rules(f))
+        .map(|f| SkippedFile {
+            relative_path: f.relative_path.clone(),
+            reason: SkipReason::NoApplicableRules,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::FileType;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_skipped_files_become_info_findings() {
+        let findings = skipped_findings(&[SkippedFile {
+            relative_path: PathBuf::from("assets/model.bin"),
+            reason: SkipReason::TooLarge(20_000_000),
+        }]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Info);
+        assert_eq!(
+            findings[0].message,
+            "File was not scanned (20000000 bytes, over the 10485760-byte limit)"
+        );
+    }
+
+    #[test]
+    fn test_failed_skills_become_error_findings() {
+        let findings = failed_skill_findings(&[FailedSkill {
+            root: PathBuf::from("skills/pdf"),
+            error: "GitHub API rate limit exceeded".to_string(),
+        }]);
+        assert_eq!(findings[0].rule_id, "SL-COV-002");
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].location.file, PathBuf::from("skills/pdf"));
+        assert_eq!(
+            findings[0].message,
+            "Skill was not scanned: GitHub API rate limit exceeded"
+        );
+    }
+
+    #[test]
+    fn test_unchecked_lists_files_without_rules() {
+        let file = |name: &str, file_type| ScannedFile {
+            path: PathBuf::from(name),
+            relative_path: PathBuf::from(name),
+            file_type,
+            content: String::new(),
+        };
+        let files = [
+            file("SKILL.md", FileType::Markdown),
+            file("data.csv", FileType::Unknown),
+        ];
+        let unchecked = unchecked(&files, |f| f.file_type != FileType::Unknown);
+        assert_eq!(unchecked.len(), 1);
+        assert_eq!(unchecked[0].relative_path, PathBuf::from("data.csv"));
+    }
+}
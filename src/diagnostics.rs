@@ -0,0 +1,44 @@
+use serde::Serialize;
+
+/// What went wrong. A scan with any of these is degraded: some input was not
+/// read or some rule did not run as configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Code {
+    ConfigUnreadable,
+    ConfigInvalid,
+    /// A flag that has no effect on this kind of scan.
+    OptionIgnored,
+    PatternFileInvalid,
+    RuleInvalid,
+    SkillIndexInvalid,
+    SkillFetchFailed,
+    /// Too large or unreadable; binary files are expected and not included.
+    FileSkipped,
+    LinksTruncated,
+}
+
+/// A runtime warning, kept for the report as well as printed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub code: Code,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(code: Code, message: impl Into<String>) -> Self {
+        Diagnostic {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Print `warning: {message}` and return it, for the caller to pass on to
+/// the scan's report.
+#[must_use]
+pub fn warn(code: Code, message: impl Into<String>) -> Diagnostic {
+    let diagnostic = Diagnostic::new(code, message);
+    eprintln!("warning: {}", diagnostic.message);
+    diagnostic
+}
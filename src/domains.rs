@@ -0,0 +1,296 @@
+use crate::finding::{Category, Finding, Severity};
+use crate::rules::url_rule;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+/// Services that hand anyone a public endpoint or an anonymous upload, and so
+/// turn up in exfiltration and payload staging far more than in honest skills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceKind {
+    /// Exposes a local port on a public hostname.
+    Tunnel,
+    /// Anonymous text paste.
+    Paste,
+    /// Anonymous file upload.
+    FileDrop,
+    /// Records every request it receives.
+    Webhook,
+    /// Redirects to a destination the link does not show.
+    Shortener,
+}
+
+impl fmt::Display for ServiceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceKind::Tunnel => write!(f, "tunnel"),
+            ServiceKind::Paste => write!(f, "paste"),
+            ServiceKind::FileDrop => write!(f, "file drop"),
+            ServiceKind::Webhook => write!(f, "request capture"),
+            ServiceKind::Shortener => write!(f, "URL shortener"),
+        }
+    }
+}
+
+/// Registered domains of known services. Subdomains match too.
+const KNOWN_SERVICES: &[(&str, ServiceKind)] = &[
+    ("ngrok.io", ServiceKind::Tunnel),
+    ("ngrok.app", ServiceKind::Tunnel),
+    ("ngrok-free.app", ServiceKind::Tunnel),
+    ("trycloudflare.com", ServiceKind::Tunnel),
+    ("loca.lt", ServiceKind::Tunnel),
+    ("localtunnel.me", ServiceKind::Tunnel),
+    ("serveo.net", ServiceKind::Tunnel),
+    ("localhost.run", ServiceKind::Tunnel),
+    ("pagekite.me", ServiceKind::Tunnel),
+    ("pastebin.com", ServiceKind::Paste),
+    ("paste.ee", ServiceKind::Paste),
+    ("hastebin.com", ServiceKind::Paste),
+    ("ghostbin.co", ServiceKind::Paste),
+    ("rentry.co", ServiceKind::Paste),
+    ("dpaste.org", ServiceKind::Paste),
+    ("termbin.com", ServiceKind::Paste),
+    ("transfer.sh", ServiceKind::FileDrop),
+    ("file.io", ServiceKind::FileDrop),
+    ("0x0.st", ServiceKind::FileDrop),
+    ("temp.sh", ServiceKind::FileDrop),
+    ("bashupload.com", ServiceKind::FileDrop),
+    ("webhook.site", ServiceKind::Webhook),
+    ("requestbin.com", ServiceKind::Webhook),
+    ("pipedream.net", ServiceKind::Webhook),
+    ("beeceptor.com", ServiceKind::Webhook),
+    ("interact.sh", ServiceKind::Webhook),
+    ("oast.fun", ServiceKind::Webhook),
+    ("burpcollaborator.net", ServiceKind::Webhook),
+    ("bit.ly", ServiceKind::Shortener),
+    ("tinyurl.com", ServiceKind::Shortener),
+    ("t.co", ServiceKind::Shortener),
+    ("goo.gl", ServiceKind::Shortener),
+    ("is.gd", ServiceKind::Shortener),
+    ("ow.ly", ServiceKind::Shortener),
+    ("buff.ly", ServiceKind::Shortener),
+    ("rebrand.ly", ServiceKind::Shortener),
+    ("cutt.ly", ServiceKind::Shortener),
+    ("shorturl.at", ServiceKind::Shortener),
+    ("tiny.cc", ServiceKind::Shortener),
+    ("rb.gy", ServiceKind::Shortener),
+];
+
+/// Public suffixes with two labels, so `example.co.uk` is the registered
+/// domain rather than `co.uk`. Not the full Public Suffix List: enough for
+/// the hosts skills link to.
+const TWO_LABEL_SUFFIXES: &[&str] = &[
+    "co.uk",
+    "org.uk",
+    "ac.uk",
+    "gov.uk",
+    "com.au",
+    "net.au",
+    "org.au",
+    "co.jp",
+    "ne.jp",
+    "co.nz",
+    "co.in",
+    "com.br",
+    "com.cn",
+    "com.mx",
+    "co.za",
+    "github.io",
+    "pages.dev",
+    "vercel.app",
+    "netlify.app",
+    "herokuapp.com",
+];
+
+static URL_HOST: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\b(?:https?|wss?|ftp)://(?:[^/\s@"'`]*@)?([a-z0-9.-]+\.[a-z0-9-]+)"#).unwrap()
+});
+
+/// A whole URL, up to the whitespace, quote or bracket that ends it.
+static URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\b(?:https?|wss?|ftp)://[^\s"'`<>()\[\]]+"#).unwrap());
+
+/// What is known about the host a network finding refers to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainInfo {
+    pub host: String,
+    pub registered_domain: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<ServiceKind>,
+}
+
+/// Look up the first URL host in `text`.
+pub fn lookup(text: &str) -> Option<DomainInfo> {
+    let host = URL_HOST
+        .captures(text)?
+        .get(1)?
+        .as_str()
+        .trim_end_matches('.')
+        .to_lowercase();
+    let registered_domain = registered_domain(&host);
+    let service = KNOWN_SERVICES
+        .iter()
+        .find(|(domain, _)| host_matches(&host, domain))
+        .map(|(_, kind)| *kind);
+    Some(DomainInfo {
+        host,
+        registered_domain,
+        service,
+    })
+}
+
+/// Every URL in `text` with a host: its byte offset, the URL, and its host.
+pub fn find_urls(text: &str) -> Vec<(usize, &str, DomainInfo)> {
+    URL.find_iter(text)
+        .filter_map(|m| {
+            let url = m.as_str().trim_end_matches(['.', ',', ';', ':']);
+            Some((m.start(), url, lookup(url)?))
+        })
+        .collect()
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+pub fn host_matches(host: &str, domain: &str) -> bool {
+    let domain = domain.trim_start_matches("*.").to_lowercase();
+    host == domain || host.ends_with(&format!(".{domain}"))
+}
+
+/// The host's last two labels, or three under a two-label public suffix.
+/// IP addresses are their own registered domain.
+fn registered_domain(host: &str) -> String {
+    if host.parse::<std::net::Ipv4Addr>().is_ok() {
+        return host.to_string();
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    let suffix_labels = if labels.len() >= 3
+        && TWO_LABEL_SUFFIXES.contains(&labels[labels.len() - 2..].join(".").as_str())
+    {
+        2
+    } else {
+        1
+    };
+    labels[labels.len().saturating_sub(suffix_labels + 1)..].join(".")
+}
+
+/// Attach domain metadata to network findings that name a URL, and raise a
+/// finding one severity level when its host is a known tunnel, paste, file
+/// drop or request capture service. A host the URL policy rule already
+/// reported on the same line is not raised again.
+pub fn enrich(findings: &mut [Finding]) {
+    let reported: HashSet<(PathBuf, usize, String)> = findings
+        .iter()
+        .filter(|f| f.rule_id == url_rule::RULE_ID)
+        .filter_map(|f| {
+            let host = lookup(&f.matched_text)?.host;
+            Some((f.location.file.clone(), f.location.line, host))
+        })
+        .collect();
+    for f in findings
+        .iter_mut()
+        .filter(|f| f.category() == Category::Network)
+    {
+        let Some(info) = lookup(&f.matched_text).or_else(|| lookup(&f.message)) else {
+            continue;
+        };
+        // The URL policy rule already rates a host by its service.
+        let rated = f.rule_id == url_rule::RULE_ID
+            || reported.contains(&(f.location.file.clone(), f.location.line, info.host.clone()));
+        if let (Some(kind), false) = (info.service, rated) {
+            let raised = match f.severity {
+                Severity::Info => Severity::Warning,
+                Severity::Warning => Severity::Error,
+                Severity::Error | Severity::Critical => Severity::Critical,
+            };
+            if raised > f.severity {
+                f.severity = raised;
+                f.message = format!(
+                    "{} (escalated to {raised}: {} is a known {kind} service)",
+                    f.message, info.registered_domain
+                );
+            }
+        }
+        f.domain = Some(info);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_finds_registered_domain_and_service() {
+        let info = lookup("curl -fsSL https://user@Abc-12.eu.ngrok.io:8443/x.sh").unwrap();
+        assert_eq!(info.host, "abc-12.eu.ngrok.io");
+        assert_eq!(info.registered_domain, "ngrok.io");
+        assert_eq!(info.service, Some(ServiceKind::Tunnel));
+
+        let info = lookup("see https://docs.example.co.uk/guide").unwrap();
+        assert_eq!(info.registered_domain, "example.co.uk");
+        assert_eq!(info.service, None);
+        assert_eq!(
+            lookup("http://10.0.0.1/a").unwrap().registered_domain,
+            "10.0.0.1"
+        );
+        // Lookalikes are not the service.
+        assert_eq!(lookup("https://notpastebin.com/x").unwrap().service, None);
+        assert!(lookup("no url here").is_none());
+    }
+
+    #[test]
+    fn test_enrich_escalates_known_services_only() {
+        let mut findings = vec![
+            Finding {
+                matched_text: "curl https://transfer.sh/abc".into(),
+                ..Finding::test("SL-NET-002", Severity::Warning, "run.sh", 1)
+            },
+            Finding {
+                matched_text: "https://github.com/acme/tool".into(),
+                ..Finding::test("SL-NET-001", Severity::Info, "run.sh", 1)
+            },
+            Finding {
+                matched_text: "eval https://pastebin.com/x".into(),
+                ..Finding::test("SL-EXEC-001", Severity::Error, "run.sh", 1)
+            },
+        ];
+        enrich(&mut findings);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert!(findings[0]
+            .message
+            .ends_with("(escalated to error: transfer.sh is a known file drop service)"));
+        assert_eq!(findings[1].severity, Severity::Info);
+        assert_eq!(
+            findings[1].domain.as_ref().unwrap().registered_domain,
+            "github.com"
+        );
+        // Only network findings are looked at.
+        assert!(findings[2].domain.is_none());
+    }
+
+    #[test]
+    fn test_enrich_leaves_hosts_the_url_policy_reported() {
+        let mut findings = vec![
+            Finding {
+                matched_text: "curl https://pastebin.com/raw/x".into(),
+                ..Finding::test("SL-NET-002", Severity::Warning, "run.sh", 1)
+            },
+            Finding {
+                matched_text: "https://pastebin.com/raw/x".into(),
+                ..Finding::test(url_rule::RULE_ID, Severity::Error, "run.sh", 1)
+            },
+            Finding {
+                matched_text: "curl https://pastebin.com/raw/y".into(),
+                ..Finding::test("SL-NET-002", Severity::Warning, "run.sh", 2)
+            },
+        ];
+        enrich(&mut findings);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[1].severity, Severity::Error);
+        // No policy finding on that line, so the service still counts.
+        assert_eq!(findings[2].severity, Severity::Error);
+        assert!(findings[0].domain.is_some());
+    }
+}
@@ -0,0 +1,194 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::metadata_rule::extract_frontmatter;
+use crate::rules::RuleDoc;
+use crate::scanner::{self, FileType, ScannedFile};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+const NAME_RULE_ID: &str = "SL-META-009";
+const NAME_RULE_NAME: &str = "Duplicate Skill Name";
+const DESCRIPTION_RULE_ID: &str = "SL-META-010";
+const DESCRIPTION_RULE_NAME: &str = "Near-Duplicate Skill Description";
+
+/// Word overlap at or above which two descriptions count as near-identical.
+const DESCRIPTION_SIMILARITY: f64 = 0.85;
+/// Shorter descriptions overlap by chance too easily to compare.
+const MIN_DESCRIPTION_WORDS: usize = 5;
+
+pub const RULE_DOCS: &[RuleDoc] = &[
+    RuleDoc {
+        id: NAME_RULE_ID,
+        name: NAME_RULE_NAME,
+        severity: Severity::Error,
+        applies_to: &[FileType::Markdown],
+        description: "Two skills in the scanned tree declare the same `name`. The agent picks skills by name, so one can shadow the other, and a malicious copy can take over a trusted skill's invocations.",
+        remediation: "Give every skill a unique name, and remove copies you did not write.",
+    },
+    RuleDoc {
+        id: DESCRIPTION_RULE_ID,
+        name: DESCRIPTION_RULE_NAME,
+        severity: Severity::Warning,
+        applies_to: &[FileType::Markdown],
+        description: "Two skills have near-identical descriptions. The agent chooses a skill by matching the request against descriptions, so either one may be loaded for the same task.",
+        remediation: "Make each description say what is specific to that skill, or remove the duplicate.",
+    },
+];
+
+struct SkillMeta {
+    path: PathBuf,
+    name: Option<String>,
+    description_words: BTreeSet<String>,
+}
+
+fn skill_meta(file: &ScannedFile, markers: &[String]) -> Option<SkillMeta> {
+    if !scanner::is_skill_marker(&file.relative_path, markers) {
+        return None;
+    }
+    // Markdown markers carry metadata in frontmatter; YAML markers (such as
+    // `skill.yaml`) are the metadata.
+    let yaml: serde_yaml::Value = match file.file_type {
+        FileType::Markdown => serde_yaml::from_str(&extract_frontmatter(&file.content)?).ok()?,
+        FileType::Yaml => serde_yaml::from_str(&file.content).ok()?,
+        _ => return None,
+    };
+    let field = |key: &str| yaml.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    Some(SkillMeta {
+        path: file.relative_path.clone(),
+        name: field("name").map(|n| n.trim().to_lowercase()),
+        description_words: field("description")
+            .unwrap_or_default()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect(),
+    })
+}
+
+fn jaccard(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Compare every pair of skill marker files in the target, reporting each
+/// duplicate against the first skill (by path) that it collides with.
+pub fn check(files: &[ScannedFile], markers: &[String]) -> Vec<Finding> {
+    let mut skills: Vec<SkillMeta> = files
+        .iter()
+        .filter_map(|f| skill_meta(f, markers))
+        .collect();
+    skills.sort_by(|a, b| a.path.cmp(&b.path));
+    skills.dedup_by(|a, b| a.path == b.path);
+
+    let mut findings = Vec::new();
+    for (i, skill) in skills.iter().enumerate() {
+        for earlier in &skills[..i] {
+            let finding = if skill.name.is_some() && skill.name == earlier.name {
+                Some((
+                    NAME_RULE_ID,
+                    NAME_RULE_NAME,
+                    Severity::Error,
+                    format!(
+                        "Skill name {:?} is also declared by {}; the agent may load either",
+                        skill.name.as_deref().unwrap_or_default(),
+                        earlier.path.display()
+                    ),
+                ))
+            } else if skill.description_words.len() >= MIN_DESCRIPTION_WORDS
+                && earlier.description_words.len() >= MIN_DESCRIPTION_WORDS
+                && jaccard(&skill.description_words, &earlier.description_words)
+                    >= DESCRIPTION_SIMILARITY
+            {
+                Some((
+                    DESCRIPTION_RULE_ID,
+                    DESCRIPTION_RULE_NAME,
+                    Severity::Warning,
+                    format!(
+                        "Skill description is nearly identical to {}; the agent may load either",
+                        earlier.path.display()
+                    ),
+                ))
+            } else {
+                None
+            };
+            if let Some((rule_id, rule_name, severity, message)) = finding {
+                findings.push(Finding {
+                    rule_id: rule_id.to_string(),
+                    rule_name: rule_name.to_string(),
+                    severity,
+                    message,
+                    location: Location {
+                        file: skill.path.clone(),
+                        line: 1,
+                        column: 1,
+                    },
+                    matched_text: String::new(),
+                    confidence: Confidence::High,
+                    tags: Vec::new(),
+                    domain: None,
+                });
+                break;
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(path: &str, name: &str, description: &str) -> ScannedFile {
+        ScannedFile {
+            path: PathBuf::from(path),
+            relative_path: PathBuf::from(path),
+            file_type: FileType::Markdown,
+            content: format!("---\nname: {name}\ndescription: {description}\n---\n# Skill\n"),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_name_reported_on_later_skill() {
+        let findings = check(
+            &[
+                skill("b/SKILL.md", "Deploy", "Ship it"),
+                skill("a/SKILL.md", "deploy", "Deploy the app"),
+                skill("c/SKILL.md", "lint", "Run the linter"),
+            ],
+            &["SKILL.md".to_string()],
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule_id, NAME_RULE_ID);
+        assert_eq!(findings[0].location.file, PathBuf::from("b/SKILL.md"));
+        assert!(findings[0].message.contains("a/SKILL.md"));
+    }
+
+    #[test]
+    fn test_near_identical_descriptions() {
+        let findings = check(
+            &[
+                skill(
+                    "a/SKILL.md",
+                    "pdf",
+                    "Extract text and tables from PDF files for analysis",
+                ),
+                skill(
+                    "b/SKILL.md",
+                    "pdf-tools",
+                    "Extract text and tables from PDF files, for analysis",
+                ),
+                skill(
+                    "c/SKILL.md",
+                    "xlsx",
+                    "Create and edit spreadsheets with formulas and charts",
+                ),
+            ],
+            &["SKILL.md".to_string()],
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule_id, DESCRIPTION_RULE_ID);
+        assert_eq!(findings[0].location.file, PathBuf::from("b/SKILL.md"));
+    }
+}
@@ -0,0 +1,416 @@
+use crate::engine::Engine;
+use crate::finding::{report_path, Finding, Severity};
+use crate::scoring;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_PASSWORD_ENV: &str = "SKILL_ISSUE_SMTP_PASSWORD";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `[email]` in the config file: where to send the report when a scan
+/// reaches `threshold`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailSettings {
+    pub smtp_host: String,
+    /// Defaults to 465 for `tls`, 587 for `starttls` and 25 for `none`.
+    pub smtp_port: Option<u16>,
+    #[serde(default)]
+    pub tls: TlsMode,
+    pub username: Option<String>,
+    /// Environment variable holding the SMTP password, so it stays out of the
+    /// config file.
+    pub password_env: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    #[serde(default = "default_threshold")]
+    pub threshold: Severity,
+    #[serde(default)]
+    pub format: EmailFormat,
+}
+
+fn default_threshold() -> Severity {
+    Severity::Error
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TlsMode {
+    /// TLS from the first byte (SMTPS)
+    Tls,
+    /// Plain connection upgraded with STARTTLS
+    #[default]
+    Starttls,
+    /// No encryption; only for relays on localhost or a trusted network
+    None,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+/// Email the report if any finding reaches the threshold. Returns whether a
+/// message was sent.
+pub fn send_if_needed(
+    settings: &EmailSettings,
+    findings: &[Finding],
+    target: &str,
+) -> Result<bool, String> {
+    let over: Vec<&Finding> = findings
+        .iter()
+        .filter(|f| f.severity >= settings.threshold)
+        .collect();
+    if over.is_empty() {
+        return Ok(false);
+    }
+    let subject = format!(
+        "skill-issue: {} finding(s) at or above {} in {target}",
+        over.len(),
+        settings.threshold
+    );
+    let (content_type, body) = match settings.format {
+        EmailFormat::Markdown => ("text/plain", markdown_body(findings, target)),
+        EmailFormat::Html => ("text/html", html_body(findings, target)),
+    };
+    let message = message(settings, &subject, content_type, &body);
+    send(settings, &message)?;
+    Ok(true)
+}
+
+fn summary(findings: &[Finding]) -> String {
+    let trust = scoring::score(findings);
+    format!(
+        "{} finding(s), highest severity {}. Trust grade {} (risk score {}).",
+        findings.len(),
+        Engine::max_severity(findings)
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "none".to_string()),
+        trust.grade,
+        trust.risk_score
+    )
+}
+
+fn markdown_body(findings: &[Finding], target: &str) -> String {
+    let mut body = format!(
+        "# skill-issue report: {target}\n\n{}\n\n| Severity | Rule | Location | Message |\n|---|---|---|---|\n",
+        summary(findings)
+    );
+    for f in findings {
+        body.push_str(&format!(
+            "| {} | {} | {}:{} | {} |\n",
+            f.severity,
+            f.rule_id,
+            report_path(&f.location.file),
+            f.location.line,
+            f.message.replace('|', "\\|")
+        ));
+    }
+    body
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_body(findings: &[Finding], target: &str) -> String {
+    let mut body = format!(
+        "<html><body>\n<h1>skill-issue report: {}</h1>\n<p>{}</p>\n<table border=\"1\" cellpadding=\"4\">\n<tr><th>Severity</th><th>Rule</th><th>Location</th><th>Message</th></tr>\n",
+        html_escape(target),
+        html_escape(&summary(findings))
+    );
+    for f in findings {
+        body.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}:{}</td><td>{}</td></tr>\n",
+            f.severity,
+            html_escape(&f.rule_id),
+            html_escape(&report_path(&f.location.file)),
+            f.location.line,
+            html_escape(&f.message)
+        ));
+    }
+    body.push_str("</table>\n</body></html>\n");
+    body
+}
+
+/// RFC 2047-encode a header value that is not plain ASCII.
+fn header_value(s: &str) -> String {
+    if s.is_ascii() {
+        s.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", STANDARD.encode(s))
+    }
+}
+
+fn message(settings: &EmailSettings, subject: &str, content_type: &str, body: &str) -> String {
+    let headers = [
+        format!("From: {}", settings.from),
+        format!("To: {}", settings.to.join(", ")),
+        format!("Subject: {}", header_value(subject)),
+        format!("Date: {}", chrono::Utc::now().to_rfc2822()),
+        "MIME-Version: 1.0".to_string(),
+        format!("Content-Type: {content_type}; charset=utf-8"),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+    ];
+    // CRLF line endings, and a leading dot doubled so it cannot end DATA.
+    let body: Vec<String> = body
+        .lines()
+        .map(|l| match l.strip_prefix('.') {
+            Some(_) => format!(".{l}"),
+            None => l.to_string(),
+        })
+        .collect();
+    format!("{}\r\n\r\n{}\r\n", headers.join("\r\n"), body.join("\r\n"))
+}
+
+enum Connection {
+    Plain(TcpStream),
+    Tls(Box<rustls::StreamOwned<rustls::ClientConnection, TcpStream>>),
+}
+
+impl Connection {
+    fn stream(&mut self) -> &mut dyn ReadWrite {
+        match self {
+            Connection::Plain(s) => s,
+            Connection::Tls(s) => s.as_mut(),
+        }
+    }
+
+    /// Read one (possibly multi-line) reply and check its code.
+    fn expect(&mut self, codes: &[u16]) -> Result<String, String> {
+        let mut reply = String::new();
+        loop {
+            let line = self.read_line()?;
+            reply.push_str(&line);
+            reply.push('\n');
+            // "250-..." continues the reply; "250 ..." ends it.
+            if line.as_bytes().get(3) != Some(&b'-') {
+                let code: u16 = line.get(..3).and_then(|c| c.parse().ok()).unwrap_or(0);
+                if codes.contains(&code) {
+                    return Ok(reply);
+                }
+                return Err(format!("SMTP server replied: {}", reply.trim_end()));
+            }
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String, String> {
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            match self.stream().read(&mut byte) {
+                Ok(0) => return Err("SMTP server closed the connection".to_string()),
+                Ok(_) if byte[0] == b'\n' => break,
+                Ok(_) => line.push(byte[0]),
+                Err(e) => return Err(format!("SMTP read failed: {e}")),
+            }
+        }
+        Ok(String::from_utf8_lossy(&line)
+            .trim_end_matches('\r')
+            .to_string())
+    }
+
+    fn command(&mut self, command: &str, codes: &[u16]) -> Result<String, String> {
+        self.stream()
+            .write_all(format!("{command}\r\n").as_bytes())
+            .and_then(|_| self.stream().flush())
+            .map_err(|e| format!("SMTP write failed: {e}"))?;
+        self.expect(codes)
+    }
+}
+
+trait ReadWrite: Read + Write {}
+impl<T: Read + Write> ReadWrite for T {}
+
+fn tls(host: &str, stream: TcpStream) -> Result<Connection, String> {
+    let roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| e.to_string())?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    let name = rustls::pki_types::ServerName::try_from(host.to_string())
+        .map_err(|e| format!("invalid SMTP host '{host}': {e}"))?;
+    let client = rustls::ClientConnection::new(Arc::new(config), name)
+        .map_err(|e| format!("TLS setup failed: {e}"))?;
+    Ok(Connection::Tls(Box::new(rustls::StreamOwned::new(
+        client, stream,
+    ))))
+}
+
+/// Connect to the first address of `host` that answers within `TIMEOUT`.
+fn connect(host: &str, port: u16) -> Result<TcpStream, String> {
+    let addrs = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("cannot resolve {host}: {e}"))?;
+    let mut last = format!("{host} has no addresses");
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last = e.to_string(),
+        }
+    }
+    Err(format!("cannot connect to {host}:{port}: {last}"))
+}
+
+fn send(settings: &EmailSettings, message: &str) -> Result<(), String> {
+    let host = settings.smtp_host.as_str();
+    let port = settings.smtp_port.unwrap_or(match settings.tls {
+        TlsMode::Tls => 465,
+        TlsMode::Starttls => 587,
+        TlsMode::None => 25,
+    });
+    let stream = connect(host, port)?;
+    stream.set_read_timeout(Some(TIMEOUT)).ok();
+    stream.set_write_timeout(Some(TIMEOUT)).ok();
+
+    let mut conn = match settings.tls {
+        TlsMode::Tls => tls(host, stream)?,
+        _ => Connection::Plain(stream),
+    };
+    conn.expect(&[220])?;
+    conn.command("EHLO skill-issue", &[250])?;
+    if settings.tls == TlsMode::Starttls {
+        conn.command("STARTTLS", &[220])?;
+        let Connection::Plain(stream) = conn else {
+            unreachable!("STARTTLS is only sent on a plain connection")
+        };
+        conn = tls(host, stream)?;
+        conn.command("EHLO skill-issue", &[250])?;
+    }
+
+    if let Some(ref user) = settings.username {
+        let var = settings
+            .password_env
+            .as_deref()
+            .unwrap_or(DEFAULT_PASSWORD_ENV);
+        let password =
+            std::env::var(var).map_err(|_| format!("SMTP username is set but ${var} is not"))?;
+        let token = STANDARD.encode(format!("\0{user}\0{password}"));
+        conn.command(&format!("AUTH PLAIN {token}"), &[235])?;
+    }
+
+    conn.command(&format!("MAIL FROM:<{}>", settings.from), &[250])?;
+    for to in &settings.to {
+        conn.command(&format!("RCPT TO:<{to}>"), &[250, 251])?;
+    }
+    conn.command("DATA", &[354])?;
+    conn.command(&format!("{message}."), &[250])?;
+    // The message is accepted; a failed goodbye does not matter.
+    let _ = conn.command("QUIT", &[221]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    fn settings(port: u16) -> EmailSettings {
+        EmailSettings {
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: Some(port),
+            tls: TlsMode::None,
+            username: None,
+            password_env: None,
+            from: "scanner@example.com".to_string(),
+            to: vec!["security@example.com".to_string()],
+            threshold: Severity::Error,
+            format: EmailFormat::Html,
+        }
+    }
+
+    /// Accept one connection, reply OK to everything, and return the dialogue.
+    fn fake_server() -> (u16, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut lines = Vec::new();
+            writer.write_all(b"220 fake ESMTP\r\n").unwrap();
+            let mut in_data = false;
+            for line in BufReader::new(stream).lines() {
+                let line = line.unwrap();
+                lines.push(line.clone());
+                let reply: &[u8] = if in_data {
+                    if line != "." {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else if line == "DATA" {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line == "QUIT" {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else if line.starts_with("EHLO") {
+                    b"250-fake\r\n250 8BITMIME\r\n"
+                } else {
+                    b"250 ok\r\n"
+                };
+                writer.write_all(reply).unwrap();
+            }
+            lines
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn test_sends_report_over_smtp_when_threshold_reached() {
+        let (port, server) = fake_server();
+        let findings = [
+            Finding {
+                message: "curl posts <token>".into(),
+                ..Finding::test("SL-NET-010", Severity::Critical, "SKILL.md", 4)
+            },
+            Finding::test("SL-NET-010", Severity::Info, "SKILL.md", 4),
+        ];
+        assert!(send_if_needed(&settings(port), &findings, "./skill").unwrap());
+        let lines = server.join().unwrap();
+        assert_eq!(lines[0], "EHLO skill-issue");
+        assert!(lines.contains(&"MAIL FROM:<scanner@example.com>".to_string()));
+        assert!(lines.contains(&"RCPT TO:<security@example.com>".to_string()));
+        assert!(lines.contains(
+            &"Subject: skill-issue: 1 finding(s) at or above error in ./skill".to_string()
+        ));
+        assert!(lines.contains(&"Content-Type: text/html; charset=utf-8".to_string()));
+        assert!(lines.iter().any(|l| l.contains("curl posts &lt;token&gt;")));
+    }
+
+    #[test]
+    fn test_below_threshold_sends_nothing() {
+        let findings = [Finding::test(
+            "SL-NET-010",
+            Severity::Warning,
+            "SKILL.md",
+            4,
+        )];
+        // Port 9 is never contacted: nothing reaches the threshold.
+        assert!(!send_if_needed(&settings(9), &findings, "./skill").unwrap());
+    }
+
+    #[test]
+    fn test_message_dot_stuffs_body() {
+        let text = message(&settings(25), "é", "text/plain", "a\n.b\n");
+        assert!(text.contains("Subject: =?UTF-8?B?w6k=?=\r\n"));
+        assert!(text.ends_with("\r\n\r\na\r\n..b\r\n"));
+    }
+}
@@ -1,56 +1,494 @@
-use crate::config::Config;
-use crate::finding::{Finding, Severity};
-use crate::rules::RuleRegistry;
-use crate::scanner::ScannedFile;
+use crate::areas;
+use crate::config::{ColumnUnit, Config};
+use crate::decode;
+use crate::domains;
+use crate::escalation;
+use crate::finding::{report_path, Confidence, Finding, Location, Severity};
+use crate::markdown;
+use crate::noise;
+use crate::placeholder;
+use crate::rule_cache::RuleCache;
+use crate::rules::normalize;
+use crate::rules::{Rule, RuleRegistry};
+use crate::scanner::{self, FileType, ScannedFile};
+use crate::trace;
+use crate::transform;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Rules re-run over de-obfuscated text when normalization is enabled.
+const NORMALIZED_RULE_PREFIXES: &[&str] = &["SL-INJ-"];
 
 pub struct Engine<'a> {
     config: &'a Config,
     registry: &'a RuleRegistry,
+    /// Fingerprints of findings accepted in `--baseline`.
+    baseline: HashSet<String>,
+    /// Set when `--fail-fast` skipped the remaining files.
+    stopped_early: AtomicBool,
+    /// Findings hidden because they are in the baseline.
+    baselined: AtomicUsize,
+    /// Set with `--rule-cache`.
+    rule_cache: Option<RuleCache>,
 }
 
 impl<'a> Engine<'a> {
     pub fn new(config: &'a Config, registry: &'a RuleRegistry) -> Self {
-        Self { config, registry }
+        Self {
+            config,
+            registry,
+            baseline: HashSet::new(),
+            stopped_early: AtomicBool::new(false),
+            baselined: AtomicUsize::new(0),
+            rule_cache: config.rule_cache.then(RuleCache::open).flatten(),
+        }
+    }
+
+    /// Hide findings whose fingerprint is in `baseline`.
+    pub fn with_baseline(mut self, baseline: HashSet<String>) -> Self {
+        self.baseline = baseline;
+        self
     }
 
-    pub fn run(&self, files: &[ScannedFile]) -> Vec<Finding> {
+    /// How many findings the last run hid because of the baseline.
+    pub fn baselined(&self) -> usize {
+        self.baselined.load(Ordering::Relaxed)
+    }
+
+    /// Whether the last run stopped at the first blocking finding.
+    pub fn stopped_early(&self) -> bool {
+        self.stopped_early.load(Ordering::Relaxed)
+    }
+
+    /// A finding that will fail the scan: reported, and at or above `--error-on`.
+    fn is_blocking(&self, f: &Finding) -> bool {
+        f.severity >= self.config.error_on
+            && f.severity >= self.config.min_severity
+            && f.confidence >= self.config.min_confidence
+            && !self.is_baselined(f)
+    }
+
+    fn is_baselined(&self, f: &Finding) -> bool {
+        !self.baseline.is_empty() && self.baseline.contains(&f.fingerprint())
+    }
+
+    /// Run the file rules and merge in target-level findings (e.g. signature
+    /// checks), applying the same ignore/allowlist/severity policy to both.
+    pub fn run(&self, files: &[ScannedFile], target_findings: Vec<Finding>) -> Vec<Finding> {
         let mut findings = Vec::new();
+        // Derived views (e.g. package.json scripts) share a path with their
+        // source file, so a rule that applies to both would report twice.
+        let mut seen = HashSet::new();
+        let key = |f: &Finding| {
+            (
+                f.rule_id.clone(),
+                f.location.file.clone(),
+                f.location.line,
+                f.location.column,
+            )
+        };
+        let mut target_findings: Vec<Finding> = target_findings
+            .into_iter()
+            .filter_map(|f| self.admit_target_finding(f))
+            .collect();
+        // A blocking target finding (e.g. a bad signature) settles it already.
+        let mut blocked =
+            self.config.fail_fast && target_findings.iter().any(|f| self.is_blocking(f));
 
-        for file in files {
-            let rules = self.registry.rules_for_file(file.file_type);
-            for rule in rules {
-                if !self.config.is_rule_enabled(rule.id()) {
-                    continue;
-                }
-                if self.config.is_rule_ignored(rule.id()) {
-                    continue;
+        if self.config.jobs > 1 && files.len() > 1 {
+            for file_findings in self.check_files_parallel(files) {
+                findings.extend(file_findings.into_iter().filter(|f| seen.insert(key(f))));
+            }
+        } else {
+            for file in files {
+                if blocked {
+                    self.stopped_early.store(true, Ordering::Relaxed);
+                    break;
                 }
+                let file_findings = self.check_file(file);
+                findings.extend(file_findings.into_iter().filter(|f| seen.insert(key(f))));
+                blocked = self.config.fail_fast && findings.iter().any(|f| self.is_blocking(f));
+            }
+        }
 
-                let file_path_str = file.relative_path.to_string_lossy();
-                if self.config.is_allowlisted(rule.id(), &file_path_str) {
-                    continue;
+        if let Some(ref cache) = self.rule_cache {
+            cache.finish();
+        }
+
+        if self.config.normalize {
+            let normalized = self.normalized_findings(files, &findings);
+            findings.extend(normalized);
+        }
+        let decoded = self.decoded_findings(files);
+        findings.extend(decoded.into_iter().filter(|f| seen.insert(key(f))));
+        let hidden = self.hidden_findings(files, &mut findings);
+        findings.extend(hidden.into_iter().filter(|f| seen.insert(key(f))));
+
+        if self.config.suppress_noise {
+            let before = trace::enabled().then(|| findings.clone());
+            let (kept, meta) = noise::suppress(findings, files);
+            if let Some(before) = before {
+                let kept: HashSet<String> = kept.iter().map(Finding::fingerprint).collect();
+                for f in before.iter().filter(|f| !kept.contains(&f.fingerprint())) {
+                    trace_suppression("noise", f);
                 }
+            }
+            findings = kept;
+            // Explanations go through the same policy as other target findings.
+            target_findings.extend(
+                meta.into_iter()
+                    .filter_map(|f| self.admit_target_finding(f)),
+            );
+        }
+        findings.extend(target_findings);
 
-                let mut rule_findings = rule.check(file);
+        let roots = scanner::skill_roots(files, &self.config.skill_markers);
+        areas::apply(&mut findings, &self.config.areas, files, &roots, |id| {
+            self.config.has_severity_override(id)
+        });
+        domains::enrich(&mut findings);
+        escalation::apply(&mut findings, &self.config.escalations, &roots);
 
-                // Apply severity overrides
-                for f in &mut rule_findings {
-                    f.severity = self.config.effective_severity(&f.rule_id, f.severity);
+        // Filter by minimum severity and confidence
+        findings.retain(|f| {
+            let kind = if f.severity < self.config.min_severity {
+                "min_severity"
+            } else if f.confidence < self.config.min_confidence {
+                "min_confidence"
+            } else if !self.config.tags.is_empty()
+                && !f.tags.iter().any(|t| self.config.tags.contains(t))
+            {
+                "tags"
+            } else {
+                return true;
+            };
+            trace_suppression(kind, f);
+            false
+        });
+        if !self.baseline.is_empty() {
+            let before = findings.len();
+            findings.retain(|f| {
+                let baselined = self.is_baselined(f);
+                if baselined {
+                    trace_suppression("baseline", f);
                 }
+                !baselined
+            });
+            self.baselined
+                .store(before - findings.len(), Ordering::Relaxed);
+        }
 
-                findings.extend(rule_findings);
+        if self.config.columns != ColumnUnit::Byte {
+            let contents: HashMap<&PathBuf, &str> = files
+                .iter()
+                .rev()
+                .map(|f| (&f.relative_path, f.content.as_str()))
+                .collect();
+            for f in &mut findings {
+                if let Some(content) = contents.get(&f.location.file) {
+                    f.location.column = convert_column(content, &f.location, self.config.columns);
+                }
             }
         }
 
-        // Filter by minimum severity
-        findings.retain(|f| f.severity >= self.config.min_severity);
-
         // Sort: severity desc, then file, then line
         findings.sort_by_key(|a| a.sort_key());
 
         findings
     }
 
+    /// Run the active rules over one file and its derived views, with
+    /// severity overrides and tags applied. Placeholder credentials are
+    /// relabelled and go through the policy for their own rule ID.
+    fn check_file(&self, file: &ScannedFile) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let views = transform::views(file);
+        for view in std::iter::once(file).chain(&views) {
+            let rules = self.active_rules(view);
+            if trace::enabled() {
+                self.trace_allowlisted_rules(view);
+            }
+            let results = match self.rule_cache {
+                Some(ref cache) => cache.check(&rules, view),
+                None => rules
+                    .into_iter()
+                    .map(|r| (r, traced_check(r, view)))
+                    .collect(),
+            };
+            for (rule, rule_findings) in results {
+                if self.rule_cache.is_some() && trace::enabled() {
+                    trace::event(
+                        "rule_finished",
+                        serde_json::json!({
+                            "rule": rule.id(),
+                            "file": report_path(&view.relative_path),
+                            "findings": rule_findings.len(),
+                            "rule_cache": true,
+                        }),
+                    );
+                }
+                findings.extend(rule_findings.into_iter().filter_map(|mut f| {
+                    f.tags = rule.tags().into_iter().map(str::to_string).collect();
+                    if placeholder::relabel(&mut f) {
+                        return self.admit_target_finding(f);
+                    }
+                    f.severity = self.config.effective_severity(&f.rule_id, f.severity);
+                    Some(f)
+                }));
+            }
+        }
+        if trace::enabled() {
+            trace::event(
+                "file_scanned",
+                serde_json::json!({
+                    "file": report_path(&file.relative_path),
+                    "file_type": format!("{:?}", file.file_type),
+                    "views": views.len(),
+                    "findings": findings.len(),
+                }),
+            );
+        }
+        findings
+    }
+
+    /// Trace the rules `[[allowlist]]` keeps from running on a file.
+    fn trace_allowlisted_rules(&self, file: &ScannedFile) {
+        let file_path = file.relative_path.to_string_lossy();
+        for rule in self.registry.rules_for_file(file.file_type) {
+            if self.config.is_allowlisted(rule.id(), &file_path) {
+                trace::event(
+                    "suppression_applied",
+                    serde_json::json!({
+                        "kind": "allowlist",
+                        "rule": rule.id(),
+                        "file": report_path(&file.relative_path),
+                    }),
+                );
+            }
+        }
+    }
+
+    /// `check_file` for every file on `config.jobs` threads. Each thread takes
+    /// a contiguous run of files and results come back in file order, so the
+    /// report is the same as a sequential run.
+    fn check_files_parallel(&self, files: &[ScannedFile]) -> Vec<Vec<Finding>> {
+        let chunk = files.len().div_ceil(self.config.jobs);
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = files
+                .chunks(chunk)
+                .map(|run| {
+                    scope.spawn(move || run.iter().map(|f| self.check_file(f)).collect::<Vec<_>>())
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|w| w.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        })
+    }
+
+    /// Apply the ignore/allowlist/severity policy to a finding no active rule
+    /// produced, such as a target-level check.
+    fn admit_target_finding(&self, mut f: Finding) -> Option<Finding> {
+        let kind = if !self.config.is_rule_enabled(&f.rule_id) {
+            "disabled"
+        } else if !self.config.meets_min_rule_severity(&f.rule_id, f.severity) {
+            "min_rule_severity"
+        } else if self.config.is_rule_ignored(&f.rule_id) {
+            "ignore"
+        } else if self
+            .config
+            .is_allowlisted(&f.rule_id, &f.location.file.to_string_lossy())
+        {
+            "allowlist"
+        } else {
+            f.severity = self.config.effective_severity(&f.rule_id, f.severity);
+            return Some(f);
+        };
+        trace_suppression(kind, &f);
+        None
+    }
+
+    /// Whether any rule will check the file under the current mode and policy.
+    pub fn has_active_rules(&self, file: &ScannedFile) -> bool {
+        !self.active_rules(file).is_empty()
+    }
+
+    /// Rules that apply to a file under the current mode and policy.
+    fn active_rules(&self, file: &ScannedFile) -> Vec<&dyn Rule> {
+        let file_path = file.relative_path.to_string_lossy();
+        self.registry
+            .rules_for_file(file.file_type)
+            .into_iter()
+            .filter(|rule| {
+                self.config.mode.includes_rule(rule.id())
+                    && self.config.is_rule_enabled(rule.id())
+                    && self
+                        .config
+                        .meets_min_rule_severity(rule.id(), rule.default_severity())
+                    && !self.config.is_rule_ignored(rule.id())
+                    && !self.config.is_allowlisted(rule.id(), &file_path)
+            })
+            .collect()
+    }
+
+    /// Re-run the injection rules over de-obfuscated text (leetspeak, spaced
+    /// letters, invisible separators), keeping only lines the raw text did
+    /// not already flag. These matches are reported at medium confidence.
+    fn normalized_findings(&self, files: &[ScannedFile], raw: &[Finding]) -> Vec<Finding> {
+        let flagged: HashSet<(&str, &PathBuf, usize)> = raw
+            .iter()
+            .map(|f| (f.rule_id.as_str(), &f.location.file, f.location.line))
+            .collect();
+        let mut findings = Vec::new();
+
+        for file in files {
+            let (lines, offsets): (Vec<String>, Vec<Vec<usize>>) = file
+                .content
+                .lines()
+                .map(normalize::deobfuscate_line)
+                .unzip();
+            if lines.iter().zip(file.content.lines()).all(|(n, o)| n == o) {
+                continue;
+            }
+            let view = ScannedFile {
+                content: lines.join("\n"),
+                ..file.clone()
+            };
+
+            for rule in self.active_rules(file) {
+                if !NORMALIZED_RULE_PREFIXES
+                    .iter()
+                    .any(|p| rule.id().starts_with(p))
+                {
+                    continue;
+                }
+                for mut f in rule.check(&view) {
+                    let key = (f.rule_id.as_str(), &f.location.file, f.location.line);
+                    if flagged.contains(&key) {
+                        continue;
+                    }
+                    if let Some(&orig) = offsets
+                        .get(f.location.line - 1)
+                        .and_then(|o| o.get(f.location.column - 1))
+                    {
+                        f.location.column = orig + 1;
+                    }
+                    f.severity = self.config.effective_severity(&f.rule_id, f.severity);
+                    f.tags = rule.tags().into_iter().map(str::to_string).collect();
+                    f.message = format!("{} (after de-obfuscation)", f.message);
+                    f.confidence = Confidence::Medium;
+                    findings.push(f);
+                }
+            }
+        }
+        findings
+    }
+
+    /// Run the pattern rules over text hidden in encoded blobs, as prose and
+    /// as a script. Findings point at the blob and say where it was decoded.
+    fn decoded_findings(&self, files: &[ScannedFile]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for file in files {
+            for decoded in decode::decode(&file.content) {
+                let mut seen = HashSet::new();
+                for file_type in [FileType::Markdown, FileType::Script] {
+                    let view = ScannedFile {
+                        file_type,
+                        content: decoded.text.clone(),
+                        ..file.clone()
+                    };
+                    for rule in self.active_rules(&view) {
+                        if rule.pattern().is_none()
+                            || decode::Encoding::is_blob_rule(rule.id())
+                            || !seen.insert(rule.id().to_string())
+                        {
+                            continue;
+                        }
+                        for mut f in traced_check(rule, &view) {
+                            f.location.line = decoded.line;
+                            f.location.column = decoded.column;
+                            f.tags = rule.tags().into_iter().map(str::to_string).collect();
+                            f.message = format!(
+                                "{} (decoded from {} at line {})",
+                                f.message, decoded.encoding, decoded.line
+                            );
+                            if placeholder::relabel(&mut f) {
+                                findings.extend(self.admit_target_finding(f));
+                                continue;
+                            }
+                            f.severity = self.config.effective_severity(&f.rule_id, f.severity);
+                            findings.push(f);
+                        }
+                    }
+                }
+            }
+        }
+        findings
+    }
+
+    /// Run the pattern rules over text a rendered Markdown page does not
+    /// show: HTML comments, link titles, image alt text, link reference
+    /// definitions and footnotes, each joined into one line so a phrase split
+    /// across lines still matches. A match the raw text already reported
+    /// gets a note on where it is hidden; the rest are new findings at the
+    /// start of the element.
+    fn hidden_findings(&self, files: &[ScannedFile], raw: &mut [Finding]) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for file in files {
+            if file.file_type != FileType::Markdown {
+                continue;
+            }
+            for hidden in markdown::Structure::parse(&file.content).hidden {
+                let place = format!("in {} at line {}", hidden.kind, hidden.line);
+                let mut seen = HashSet::new();
+                for file_type in [FileType::Markdown, FileType::MarkdownText] {
+                    let view = ScannedFile {
+                        file_type,
+                        content: hidden.text.split_whitespace().collect::<Vec<_>>().join(" "),
+                        ..file.clone()
+                    };
+                    for rule in self.active_rules(&view) {
+                        if rule.pattern().is_none()
+                            || decode::Encoding::is_blob_rule(rule.id())
+                            || !seen.insert(rule.id().to_string())
+                        {
+                            continue;
+                        }
+                        for mut f in traced_check(rule, &view) {
+                            let note = format!("({place})");
+                            let reported = raw.iter_mut().find(|r| {
+                                r.rule_id == f.rule_id
+                                    && r.location.file == f.location.file
+                                    && r.matched_text == f.matched_text
+                                    && (hidden.line, hidden.column)
+                                        <= (r.location.line, r.location.column)
+                                    && r.location.line <= hidden.end_line
+                                    && !r.message.ends_with(&note)
+                            });
+                            if let Some(r) = reported {
+                                r.message = format!("{} {note}", r.message);
+                                continue;
+                            }
+                            f.location.line = hidden.line;
+                            f.location.column = hidden.column;
+                            f.tags = rule.tags().into_iter().map(str::to_string).collect();
+                            f.message = format!("{} {note}", f.message);
+                            if placeholder::relabel(&mut f) {
+                                findings.extend(self.admit_target_finding(f));
+                                continue;
+                            }
+                            f.severity = self.config.effective_severity(&f.rule_id, f.severity);
+                            findings.push(f);
+                        }
+                    }
+                }
+            }
+        }
+        findings
+    }
+
     pub fn max_severity(findings: &[Finding]) -> Option<Severity> {
         findings.iter().map(|f| f.severity).max()
     }
@@ -66,26 +504,74 @@ impl<'a> Engine<'a> {
     }
 }
 
+/// Run one rule over a file, tracing its start and finish.
+fn traced_check(rule: &dyn Rule, file: &ScannedFile) -> Vec<Finding> {
+    if !trace::enabled() {
+        return rule.check(file);
+    }
+    let fields = |extra: serde_json::Value| {
+        let mut fields = serde_json::json!({
+            "rule": rule.id(),
+            "file": report_path(&file.relative_path),
+        });
+        if let (Some(fields), serde_json::Value::Object(extra)) = (fields.as_object_mut(), extra) {
+            fields.extend(extra);
+        }
+        fields
+    };
+    trace::event("rule_started", fields(serde_json::Value::Null));
+    let started = std::time::Instant::now();
+    let findings = rule.check(file);
+    trace::event(
+        "rule_finished",
+        fields(serde_json::json!({
+            "findings": findings.len(),
+            "duration_us": started.elapsed().as_micros() as u64,
+        })),
+    );
+    findings
+}
+
+/// A finding the policy hid, and which part of it did.
+fn trace_suppression(kind: &str, f: &Finding) {
+    if !trace::enabled() {
+        return;
+    }
+    trace::event(
+        "suppression_applied",
+        serde_json::json!({
+            "kind": kind,
+            "rule": f.rule_id,
+            "file": report_path(&f.location.file),
+            "line": f.location.line,
+            "fingerprint": f.fingerprint(),
+        }),
+    );
+}
+
+/// Convert a 1-based byte column to `unit`. Offsets inside a character count
+/// as that character.
+fn convert_column(content: &str, location: &Location, unit: ColumnUnit) -> usize {
+    let Some(line) = content.lines().nth(location.line.saturating_sub(1)) else {
+        return location.column;
+    };
+    let mut end = (location.column - 1).min(line.len());
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    let prefix = &line[..end];
+    1 + match unit {
+        ColumnUnit::Char => prefix.chars().count(),
+        ColumnUnit::Utf16 => prefix.encode_utf16().count(),
+        ColumnUnit::Byte => end,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::finding::Location;
 
-    fn make_finding(severity: Severity) -> Finding {
-        Finding {
-            rule_id: "TEST-001".into(),
-            rule_name: "Test Rule".into(),
-            severity,
-            message: "test".into(),
-            location: Location {
-                file: "test.md".into(),
-                line: 1,
-                column: 1,
-            },
-            matched_text: "test".into(),
-        }
-    }
-
     #[test]
     fn test_exit_code_no_findings() {
         assert_eq!(Engine::exit_code(&[], Severity::Error), 0);
@@ -93,35 +579,58 @@ mod tests {
 
     #[test]
     fn test_exit_code_errors() {
-        let findings = vec![make_finding(Severity::Error)];
+        let findings = vec![Finding::test("TEST-001", Severity::Error, "test.md", 1)];
         assert_eq!(Engine::exit_code(&findings, Severity::Error), 2);
     }
 
     #[test]
     fn test_exit_code_warnings_only() {
-        let findings = vec![make_finding(Severity::Warning)];
+        let findings = vec![Finding::test("TEST-001", Severity::Warning, "test.md", 1)];
         assert_eq!(Engine::exit_code(&findings, Severity::Error), 1);
     }
 
     #[test]
     fn test_exit_code_info_only() {
-        let findings = vec![make_finding(Severity::Info)];
+        let findings = vec![Finding::test("TEST-001", Severity::Info, "test.md", 1)];
         assert_eq!(Engine::exit_code(&findings, Severity::Error), 0);
     }
 
     #[test]
     fn test_exit_code_error_on_warning() {
-        let findings = vec![make_finding(Severity::Warning)];
+        let findings = vec![Finding::test("TEST-001", Severity::Warning, "test.md", 1)];
         assert_eq!(Engine::exit_code(&findings, Severity::Warning), 2);
     }
 
+    #[test]
+    fn test_exit_code_error_on_critical() {
+        let findings = vec![Finding::test("TEST-001", Severity::Error, "test.md", 1)];
+        assert_eq!(Engine::exit_code(&findings, Severity::Critical), 1);
+        let findings = vec![Finding::test("TEST-001", Severity::Critical, "test.md", 1)];
+        assert_eq!(Engine::exit_code(&findings, Severity::Critical), 2);
+    }
+
+    #[test]
+    fn test_convert_column() {
+        // "é" is 2 bytes / 1 UTF-16 unit, "😀" is 4 bytes / 2 UTF-16 units.
+        let content = "line one\né😀 eval(x)";
+        let at = |column| Location {
+            file: "test.md".into(),
+            line: 2,
+            column,
+        };
+        assert_eq!(convert_column(content, &at(8), ColumnUnit::Char), 4);
+        assert_eq!(convert_column(content, &at(8), ColumnUnit::Utf16), 5);
+        assert_eq!(convert_column(content, &at(8), ColumnUnit::Byte), 8);
+        assert_eq!(convert_column(content, &at(1), ColumnUnit::Char), 1);
+    }
+
     #[test]
     fn test_max_severity() {
         assert_eq!(Engine::max_severity(&[]), None);
         let findings = vec![
-            make_finding(Severity::Info),
-            make_finding(Severity::Error),
-            make_finding(Severity::Warning),
+            Finding::test("TEST-001", Severity::Info, "test.md", 1),
+            Finding::test("TEST-001", Severity::Error, "test.md", 1),
+            Finding::test("TEST-001", Severity::Warning, "test.md", 1),
         ];
         assert_eq!(Engine::max_severity(&findings), Some(Severity::Error));
     }
@@ -0,0 +1,117 @@
+use crate::finding::{Finding, Severity};
+use crate::scanner;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Raise findings to `severity` when every entry of `when` matches a finding
+/// in the same skill. Entries are category names (`network`) or rule IDs
+/// (`SL-FS-001`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Escalation {
+    pub name: String,
+    pub when: Vec<String>,
+    pub severity: Severity,
+}
+
+/// Combinations that are worse than their parts, used unless the config file
+/// has an `escalations` list of its own.
+pub fn defaults() -> Vec<Escalation> {
+    let rule = |name: &str, when: &[&str], severity| Escalation {
+        name: name.to_string(),
+        when: when.iter().map(|s| s.to_string()).collect(),
+        severity,
+    };
+    vec![
+        rule(
+            "credential exfiltration",
+            &["secrets", "network"],
+            Severity::Critical,
+        ),
+        rule(
+            "sensitive file exfiltration",
+            &["SL-FS-001", "network"],
+            Severity::Critical,
+        ),
+        rule("key exfiltration", &["keys", "network"], Severity::Critical),
+        rule(
+            "hidden execution",
+            &["hidden", "execution"],
+            Severity::Error,
+        ),
+    ]
+}
+
+fn matches(entry: &str, finding: &Finding) -> bool {
+    entry == finding.rule_id || entry == finding.category().to_string()
+}
+
+/// Apply escalations per skill (per `roots`; findings outside every root form
+/// one group). Only the findings that make up a combination are raised, and
+/// never lowered. Info findings (a bare URL, a temp file) take no part.
+pub fn apply(findings: &mut [Finding], escalations: &[Escalation], roots: &[PathBuf]) {
+    let group = |f: &Finding| scanner::skill_root_for(&f.location.file, roots).cloned();
+    let mut groups: Vec<Option<PathBuf>> = findings.iter().map(group).collect();
+    groups.sort();
+    groups.dedup();
+
+    for skill in groups {
+        for escalation in escalations {
+            let in_skill = |f: &Finding| f.severity > Severity::Info && group(f) == skill;
+            let complete = escalation
+                .when
+                .iter()
+                .all(|entry| findings.iter().any(|f| in_skill(f) && matches(entry, f)));
+            if !complete {
+                continue;
+            }
+            for f in findings.iter_mut() {
+                if in_skill(f)
+                    && f.severity < escalation.severity
+                    && escalation.when.iter().any(|entry| matches(entry, f))
+                {
+                    f.severity = escalation.severity;
+                    f.message = format!(
+                        "{} (escalated to {}: {}, {} in the same skill)",
+                        f.message,
+                        escalation.severity,
+                        escalation.name,
+                        escalation.when.join(" + ")
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escalates_co_occurring_categories_per_skill() {
+        let roots = [PathBuf::from("a"), PathBuf::from("b")];
+        let mut findings = vec![
+            Finding::test("SL-SEC-007", Severity::Warning, "a/SKILL.md", 1),
+            Finding::test("SL-NET-001", Severity::Warning, "a/run.sh", 1),
+            Finding::test("SL-FS-004", Severity::Info, "a/run.sh", 1),
+            Finding::test("SL-NET-001", Severity::Info, "a/notes.md", 1),
+            // Only half of the pair in skill b.
+            Finding::test("SL-SEC-007", Severity::Warning, "b/SKILL.md", 1),
+        ];
+        apply(&mut findings, &defaults(), &roots);
+        let severities: Vec<Severity> = findings.iter().map(|f| f.severity).collect();
+        assert_eq!(
+            severities,
+            [
+                Severity::Critical,
+                Severity::Critical,
+                Severity::Info,
+                Severity::Info,
+                Severity::Warning,
+            ]
+        );
+        assert!(findings[0].message.ends_with(
+            "(escalated to critical: credential exfiltration, secrets + network in the same skill)"
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::RuleDoc;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::path::PathBuf;
+
+const RECENT_RULE_ID: &str = "SL-META-011";
+const RECENT_RULE_NAME: &str = "Recently Modified Skill";
+const STALE_RULE_ID: &str = "SL-META-012";
+const STALE_RULE_NAME: &str = "Unmaintained Skill";
+
+pub const RULE_DOCS: &[RuleDoc] = &[
+    RuleDoc {
+        id: RECENT_RULE_ID,
+        name: RECENT_RULE_NAME,
+        severity: Severity::Warning,
+        applies_to: &[],
+        description: "A remote skill whose directory changed more recently than `--min-age`. A compromised publisher account or a malicious update is most dangerous in the hours before anyone has looked at it.",
+        remediation: "Review the latest commits to the skill, or pin the scan to an earlier commit until the change has been reviewed.",
+    },
+    RuleDoc {
+        id: STALE_RULE_ID,
+        name: STALE_RULE_NAME,
+        severity: Severity::Info,
+        applies_to: &[],
+        description: "A remote skill whose directory has not changed for longer than `--max-age`. Unmaintained skills keep outdated commands and dependencies, and abandoned repositories can change hands.",
+        remediation: "Check that the skill still works as described and that its publisher is still active, or vendor a reviewed copy.",
+    },
+];
+
+/// When a skill last changed, and where to report it.
+#[derive(Clone)]
+pub struct SkillAge {
+    pub location: PathBuf,
+    pub committed_at: DateTime<Utc>,
+}
+
+/// Parse an age such as `24h`, `7d`, `2w` or `2y` (a year is 365 days).
+pub fn parse_age(s: &str) -> Result<TimeDelta, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let n: i64 = number
+        .parse()
+        .map_err(|_| format!("invalid age '{s}': expected a number and unit, e.g. 24h or 2y"))?;
+    let hours = match unit {
+        "h" => 1,
+        "d" => 24,
+        "w" => 24 * 7,
+        "y" => 24 * 365,
+        _ => return Err(format!("invalid age unit in '{s}': use h, d, w or y")),
+    };
+    Ok(TimeDelta::hours(n * hours))
+}
+
+/// Coarse human description of an age, in its largest whole unit.
+fn describe(age: TimeDelta) -> String {
+    let (n, unit) = match age.num_hours() {
+        h if h >= 24 * 365 => (h / (24 * 365), "year"),
+        h if h >= 24 * 7 => (h / (24 * 7), "week"),
+        h if h >= 24 => (h / 24, "day"),
+        h => (h, "hour"),
+    };
+    format!("{n} {unit}{}", if n == 1 { "" } else { "s" })
+}
+
+/// Flag skills changed within `min_age` of `now`, or not changed for more
+/// than `max_age`.
+pub fn check(
+    ages: &[SkillAge],
+    now: DateTime<Utc>,
+    min_age: Option<TimeDelta>,
+    max_age: Option<TimeDelta>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for skill in ages {
+        let age = now - skill.committed_at;
+        let date = skill.committed_at.format("%Y-%m-%d %H:%M UTC");
+        let finding = if min_age.is_some_and(|min| age < min) {
+            (
+                RECENT_RULE_ID,
+                RECENT_RULE_NAME,
+                Severity::Warning,
+                format!(
+                    "Skill last changed {} ago ({date}), within the --min-age window of {}",
+                    describe(age),
+                    describe(min_age.unwrap_or_default())
+                ),
+            )
+        } else if max_age.is_some_and(|max| age > max) {
+            (
+                STALE_RULE_ID,
+                STALE_RULE_NAME,
+                Severity::Info,
+                format!(
+                    "Skill last changed {} ago ({date}), beyond the --max-age of {}",
+                    describe(age),
+                    describe(max_age.unwrap_or_default())
+                ),
+            )
+        } else {
+            continue;
+        };
+        let (rule_id, rule_name, severity, message) = finding;
+        findings.push(Finding {
+            rule_id: rule_id.to_string(),
+            rule_name: rule_name.to_string(),
+            severity,
+            message,
+            location: Location {
+                file: skill.location.clone(),
+                line: 1,
+                column: 1,
+            },
+            matched_text: skill.committed_at.to_rfc3339(),
+            confidence: Confidence::High,
+            tags: vec!["supply-chain".to_string()],
+            domain: None,
+        });
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("24h").unwrap(), TimeDelta::hours(24));
+        assert_eq!(parse_age("2y").unwrap(), TimeDelta::days(730));
+        assert!(parse_age("2 years").is_err());
+        assert!(parse_age("h").is_err());
+    }
+
+    #[test]
+    fn test_flags_fresh_and_stale_skills() {
+        let now = DateTime::parse_from_rfc3339("2026-06-01T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let skill = |path: &str, hours_ago| SkillAge {
+            location: PathBuf::from(path),
+            committed_at: now - TimeDelta::hours(hours_ago),
+        };
+        let ages = [
+            skill("fresh/SKILL.md", 3),
+            skill("steady/SKILL.md", 24 * 90),
+            skill("stale/SKILL.md", 24 * 365 * 3),
+        ];
+        let findings = check(
+            &ages,
+            now,
+            Some(TimeDelta::hours(24)),
+            Some(TimeDelta::days(730)),
+        );
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].rule_id, RECENT_RULE_ID);
+        assert!(findings[0]
+            .message
+            .starts_with("Skill last changed 3 hours ago"));
+        assert_eq!(findings[1].rule_id, STALE_RULE_ID);
+        assert!(findings[1].message.contains("3 years ago"));
+    }
+}
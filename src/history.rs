@@ -0,0 +1,263 @@
+use crate::finding::{report_path, Severity};
+use crate::pipeline::ScanOutcome;
+use crate::scoring;
+use rusqlite::{params, Connection};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS scans (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    target TEXT NOT NULL,
+    commit_sha TEXT,
+    scanned_at TEXT NOT NULL,
+    tool_version TEXT NOT NULL,
+    ruleset_version TEXT NOT NULL,
+    risk_score INTEGER NOT NULL,
+    grade TEXT NOT NULL,
+    critical INTEGER NOT NULL,
+    errors INTEGER NOT NULL,
+    warnings INTEGER NOT NULL,
+    info INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS scans_target ON scans (target, commit_sha);
+CREATE TABLE IF NOT EXISTS findings (
+    scan_id INTEGER NOT NULL REFERENCES scans (id) ON DELETE CASCADE,
+    rule_id TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL,
+    message TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS findings_scan ON findings (scan_id);
+";
+
+/// One recorded scan of a target.
+#[derive(Debug, Clone)]
+pub struct ScanRecord {
+    pub id: i64,
+    pub commit: Option<String>,
+    pub scanned_at: String,
+    pub risk_score: u32,
+    pub grade: String,
+    pub critical: u32,
+    pub errors: u32,
+    pub warnings: u32,
+    pub info: u32,
+}
+
+/// Location of the history database: an explicit path, or
+/// `$XDG_DATA_HOME/skill-issue/history.db` (`~/.local/share` by default).
+pub fn db_path(explicit: Option<&Path>) -> Result<PathBuf, String> {
+    if let Some(path) = explicit {
+        return Ok(path.to_path_buf());
+    }
+    data_dir()
+        .map(|dir| dir.join("history.db"))
+        .ok_or_else(|| "cannot locate a data directory; pass --history-db".to_string())
+}
+
+/// skill-issue's directory under `$XDG_DATA_HOME`, or `~/.local/share`.
+pub fn data_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+        .map(|dir| dir.join("skill-issue"))
+}
+
+/// Key a target is stored under: the canonical path for local directories,
+/// the specifier as given for remote ones.
+pub fn target_key(target: &str) -> String {
+    match Path::new(target).canonicalize() {
+        Ok(path) => path.display().to_string(),
+        Err(_) => target.to_string(),
+    }
+}
+
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+        }
+        let conn = Connection::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        conn.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+        Ok(History { conn })
+    }
+
+    pub fn record(&mut self, target: &str, outcome: &ScanOutcome) -> Result<i64, String> {
+        let trust = scoring::score(&outcome.findings);
+        let count = |s| outcome.findings.iter().filter(|f| f.severity == s).count() as u32;
+        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        tx.execute(
+            "INSERT INTO scans (target, commit_sha, scanned_at, tool_version, ruleset_version,
+                                risk_score, grade, critical, errors, warnings, info)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                target,
+                outcome.provenance.commit,
+                outcome.provenance.started_at,
+                outcome.provenance.tool_version,
+                outcome.provenance.ruleset_version,
+                trust.risk_score,
+                trust.grade.to_string(),
+                count(Severity::Critical),
+                count(Severity::Error),
+                count(Severity::Warning),
+                count(Severity::Info),
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        let scan_id = tx.last_insert_rowid();
+        for f in &outcome.findings {
+            tx.execute(
+                "INSERT INTO findings (scan_id, rule_id, severity, file, line, message)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    scan_id,
+                    f.rule_id,
+                    f.severity.to_string(),
+                    report_path(&f.location.file),
+                    f.location.line as i64,
+                    f.message,
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        Ok(scan_id)
+    }
+
+    /// The most recent `limit` scans of a target, oldest first.
+    pub fn scans(&self, target: &str, limit: usize) -> Result<Vec<ScanRecord>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, commit_sha, scanned_at, risk_score, grade, critical, errors, warnings, info
+                 FROM scans WHERE target = ?1 ORDER BY id DESC LIMIT ?2",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![target, limit as i64], |row| {
+                Ok(ScanRecord {
+                    id: row.get(0)?,
+                    commit: row.get(1)?,
+                    scanned_at: row.get(2)?,
+                    risk_score: row.get(3)?,
+                    grade: row.get(4)?,
+                    critical: row.get(5)?,
+                    errors: row.get(6)?,
+                    warnings: row.get(7)?,
+                    info: row.get(8)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+        let mut scans = rows
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        scans.reverse();
+        Ok(scans)
+    }
+
+    /// Rule IDs reported by a scan.
+    pub fn rule_ids(&self, scan_id: i64) -> Result<BTreeSet<String>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT rule_id FROM findings WHERE scan_id = ?1")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![scan_id], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+    }
+}
+
+/// Rules reported by `current` that were absent from `previous`.
+pub fn new_rules(previous: &BTreeSet<String>, current: &BTreeSet<String>) -> Vec<String> {
+    current.difference(previous).cloned().collect()
+}
+
+/// A one-line trend of risk scores, scaled to the highest score shown.
+pub fn sparkline(scores: &[u32]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = scores.iter().copied().max().unwrap_or(0).max(1);
+    scores
+        .iter()
+        .map(|&s| BARS[(s as usize * (BARS.len() - 1)) / max as usize])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Finding;
+    use crate::provenance::Provenance;
+    use std::time::Duration;
+
+    fn outcome(rule_ids: &[&str]) -> ScanOutcome {
+        ScanOutcome {
+            files: Vec::new(),
+            findings: rule_ids
+                .iter()
+                .map(|id| Finding::test(id, Severity::Error, "SKILL.md", 1))
+                .collect(),
+            display_path: PathBuf::from("skill"),
+            empty: None,
+            stopped_early: false,
+            baselined: 0,
+            diagnostics: Vec::new(),
+            provenance: Provenance::new(
+                "skill".to_string(),
+                None,
+                &crate::rules::RuleRegistry::new(),
+                chrono::Utc::now(),
+                Duration::ZERO,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_record_and_read_back() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut db = History::open(&dir.path().join("nested/history.db")).unwrap();
+        let first = db.record("skill", &outcome(&[])).unwrap();
+        let second = db
+            .record("skill", &outcome(&["SL-NET-001", "SL-EXEC-001"]))
+            .unwrap();
+        db.record("other", &outcome(&["SL-NET-001"])).unwrap();
+
+        let scans = db.scans("skill", 10).unwrap();
+        assert_eq!(scans.len(), 2);
+        assert_eq!(scans[0].id, first);
+        assert_eq!(scans[1].errors, 2);
+        assert_eq!(scans[1].grade, "D");
+
+        let before = db.rule_ids(first).unwrap();
+        let after = db.rule_ids(second).unwrap();
+        assert_eq!(
+            new_rules(&before, &after),
+            vec!["SL-EXEC-001", "SL-NET-001"]
+        );
+        assert!(db.scans("missing", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_scans_limit_keeps_latest() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut db = History::open(&dir.path().join("history.db")).unwrap();
+        for _ in 0..3 {
+            db.record("skill", &outcome(&[])).unwrap();
+        }
+        let scans = db.scans("skill", 2).unwrap();
+        assert_eq!(scans.iter().map(|s| s.id).collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[0, 35, 70]), "▁▄█");
+        assert_eq!(sparkline(&[0, 0]), "▁▁");
+    }
+}
@@ -0,0 +1,415 @@
+use crate::diagnostics::{self, Code, Diagnostic};
+use crate::domains::{self, DomainInfo};
+use crate::finding::{report_path, Confidence, Finding, Location, Severity};
+use crate::rules::RuleDoc;
+use crate::scanner::ScannedFile;
+use crate::trace;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use ureq::ResponseExt;
+
+const REDIRECT_RULE_ID: &str = "SL-NET-013";
+const REDIRECT_RULE_NAME: &str = "Cross-Domain Redirect";
+const EXECUTABLE_RULE_ID: &str = "SL-NET-014";
+const EXECUTABLE_RULE_NAME: &str = "Link Serves Executable";
+const DEAD_RULE_ID: &str = "SL-NET-015";
+const DEAD_RULE_NAME: &str = "Dead Link";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const USER_AGENT: &str = concat!("skill-issue/", env!("CARGO_PKG_VERSION"));
+/// Distinct URLs fetched per scan; a skill with more is not a handful of
+/// documentation links.
+const MAX_LINKS: usize = 100;
+/// Bytes of a response read to recognize a script or binary.
+const SNIFF_BYTES: u64 = 512;
+
+pub const RULE_DOCS: &[RuleDoc] = &[
+    RuleDoc {
+        id: REDIRECT_RULE_ID,
+        name: REDIRECT_RULE_NAME,
+        severity: Severity::Warning,
+        applies_to: &[],
+        description: "With `--check-links`: the URL redirects to a different registered domain. What the link says is not where it goes, and the destination can be changed at any time without touching the skill.",
+        remediation: "Link to the final destination directly, or confirm that the redirect is one you trust and allowlist it.",
+    },
+    RuleDoc {
+        id: EXECUTABLE_RULE_ID,
+        name: EXECUTABLE_RULE_NAME,
+        severity: Severity::Error,
+        applies_to: &[],
+        description: "With `--check-links`: the URL does not look like a download, but it serves a script or executable, judged by its content type or its first bytes (`#!`, `MZ`, ELF). A \"documentation\" link that serves a script is a way to get an agent to fetch and run code.",
+        remediation: "Remove the link, or ship the script with the skill where it can be reviewed.",
+    },
+    RuleDoc {
+        id: DEAD_RULE_ID,
+        name: DEAD_RULE_NAME,
+        severity: Severity::Warning,
+        applies_to: &[],
+        description: "With `--check-links`: the URL's host does not resolve (warning; anyone could register the domain and serve what they like), or the server answers 404 or 410 (info).",
+        remediation: "Update or remove the link.",
+    },
+];
+
+/// Served types that are programs rather than documents.
+const EXECUTABLE_TYPES: &[&str] = &[
+    "application/x-sh",
+    "application/x-shellscript",
+    "text/x-sh",
+    "text/x-shellscript",
+    "application/x-csh",
+    "application/x-powershell",
+    "text/x-python",
+    "application/x-python",
+    "text/x-perl",
+    "application/x-perl",
+    "application/x-msdownload",
+    "application/x-msdos-program",
+    "application/x-msi",
+    "application/vnd.microsoft.portable-executable",
+    "application/x-executable",
+    "application/x-elf",
+    "application/x-mach-binary",
+    "application/java-archive",
+    "application/x-apple-diskimage",
+];
+
+/// A URL ending in one of these says it is a download; other rules judge
+/// what is done with it.
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    ".sh",
+    ".bash",
+    ".ps1",
+    ".py",
+    ".pl",
+    ".rb",
+    ".exe",
+    ".msi",
+    ".bin",
+    ".jar",
+    ".dmg",
+    ".pkg",
+    ".deb",
+    ".rpm",
+    ".appimage",
+];
+
+/// Leading bytes of scripts and executables.
+const MAGIC: &[&[u8]] = &[
+    b"#!",
+    b"MZ",
+    b"\x7fELF",
+    b"\xcf\xfa\xed\xfe",
+    b"\xce\xfa\xed\xfe",
+    b"\xca\xfe\xba\xbe",
+];
+
+/// Domains that redirect among themselves as a matter of course.
+const SAME_OWNER: &[&[&str]] = &[&["github.com", "githubusercontent.com", "github.io"]];
+
+/// Reserved names that never resolve to anything worth fetching.
+const RESERVED: &[&str] = &[
+    "example.com",
+    "example.org",
+    "example.net",
+    "example",
+    "test",
+    "invalid",
+    "localhost",
+];
+
+/// What fetching a URL found.
+#[derive(Debug, Clone, PartialEq)]
+enum Resolution {
+    Reached {
+        final_url: String,
+        content_type: Option<String>,
+        head: Vec<u8>,
+    },
+    /// 404 or 410.
+    Missing(u16),
+    Unresolvable,
+    /// Anything else: a timeout, a refused connection, a 5xx. Not reported,
+    /// as it may be gone next time.
+    Failed(String),
+}
+
+/// Resolutions are kept for the process, so `--batch` and
+/// `--simulate-profile` fetch each URL once.
+static RESOLVED: LazyLock<Mutex<HashMap<String, Resolution>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Fetch every URL in `files` and report each place it appears when the URL
+/// redirects to another domain, serves executable content, or is dead. A
+/// warning that not every URL was checked goes to `diagnostics`.
+pub fn check(
+    files: &[ScannedFile],
+    verbose: bool,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<Finding> {
+    // URL -> every place it appears, in file order.
+    let mut places: BTreeMap<String, Vec<Location>> = BTreeMap::new();
+    let mut seen = HashSet::new();
+    for file in files {
+        for (i, line) in file.content.lines().enumerate() {
+            for (offset, url, info) in domains::find_urls(line) {
+                if is_reserved(&info) {
+                    continue;
+                }
+                let location = Location {
+                    file: file.relative_path.clone(),
+                    line: i + 1,
+                    column: offset + 1,
+                };
+                // Package script views repeat their package.json.
+                if seen.insert((url.to_string(), report_path(&location.file), i, offset)) {
+                    places.entry(url.to_string()).or_default().push(location);
+                }
+            }
+        }
+    }
+    if places.len() > MAX_LINKS {
+        diagnostics.push(diagnostics::warn(
+            Code::LinksTruncated,
+            format!(
+                "--check-links: checking the first {MAX_LINKS} of {} URLs",
+                places.len()
+            ),
+        ));
+    }
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let mut findings = Vec::new();
+    for (url, locations) in places.into_iter().take(MAX_LINKS) {
+        let cached = RESOLVED.lock().unwrap().get(&url).cloned();
+        let resolution = cached.unwrap_or_else(|| {
+            if verbose {
+                eprintln!("Checking link {url}");
+            }
+            let resolution = resolve(&agent, &url);
+            RESOLVED
+                .lock()
+                .unwrap()
+                .insert(url.clone(), resolution.clone());
+            resolution
+        });
+        if let (true, Resolution::Failed(ref reason)) = (verbose, &resolution) {
+            eprintln!("Could not check link {url}: {reason}");
+        }
+        let Some((rule_id, rule_name, severity, message)) = judge(&url, &resolution) else {
+            continue;
+        };
+        for location in locations {
+            findings.push(Finding {
+                rule_id: rule_id.to_string(),
+                rule_name: rule_name.to_string(),
+                severity,
+                message: message.clone(),
+                location,
+                matched_text: url.clone(),
+                confidence: Confidence::High,
+                tags: vec!["supply-chain".to_string()],
+                domain: None,
+            });
+        }
+    }
+    findings
+}
+
+fn is_reserved(info: &DomainInfo) -> bool {
+    RESERVED
+        .iter()
+        .any(|r| domains::host_matches(&info.host, r))
+}
+
+/// GET `url`, following redirects, and read just enough of the body to tell
+/// a script from a page.
+fn resolve(agent: &ureq::Agent, url: &str) -> Resolution {
+    let started = Instant::now();
+    let result = agent.get(url).header("User-Agent", USER_AGENT).call();
+    let status = result.as_ref().ok().map(|r| r.status().as_u16());
+    trace::http("GET", url, status, started.elapsed());
+    let mut resp = match result {
+        Ok(resp) => resp,
+        Err(ureq::Error::HostNotFound) => return Resolution::Unresolvable,
+        Err(e) => return Resolution::Failed(e.to_string()),
+    };
+    match resp.status().as_u16() {
+        404 | 410 => return Resolution::Missing(resp.status().as_u16()),
+        status if status >= 400 => return Resolution::Failed(format!("http status: {status}")),
+        _ => {}
+    }
+    let final_url = resp.get_uri().to_string();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let mut head = Vec::new();
+    let _ = resp
+        .body_mut()
+        .as_reader()
+        .take(SNIFF_BYTES)
+        .read_to_end(&mut head);
+    Resolution::Reached {
+        final_url,
+        content_type,
+        head,
+    }
+}
+
+/// The finding a resolution calls for, if any: rule ID, name, severity and
+/// message. Executable content outranks a redirect.
+fn judge(
+    url: &str,
+    resolution: &Resolution,
+) -> Option<(&'static str, &'static str, Severity, String)> {
+    match resolution {
+        Resolution::Reached {
+            final_url,
+            content_type,
+            head,
+        } => {
+            if let Some(served) = executable(content_type.as_deref(), head) {
+                if !names_executable(url) && !names_executable(final_url) {
+                    return Some((
+                        EXECUTABLE_RULE_ID,
+                        EXECUTABLE_RULE_NAME,
+                        Severity::Error,
+                        format!("Link serves executable content ({served}): {url}"),
+                    ));
+                }
+            }
+            let (from, to) = (domains::lookup(url)?, domains::lookup(final_url)?);
+            (!same_owner(&from.registered_domain, &to.registered_domain)).then(|| {
+                (
+                    REDIRECT_RULE_ID,
+                    REDIRECT_RULE_NAME,
+                    Severity::Warning,
+                    format!("Link redirects to another domain: {url} -> {final_url}"),
+                )
+            })
+        }
+        Resolution::Missing(status) => Some((
+            DEAD_RULE_ID,
+            DEAD_RULE_NAME,
+            Severity::Info,
+            format!("Dead link (HTTP {status}): {url}"),
+        )),
+        Resolution::Unresolvable => Some((
+            DEAD_RULE_ID,
+            DEAD_RULE_NAME,
+            Severity::Warning,
+            format!("Link host does not resolve and could be registered by anyone: {url}"),
+        )),
+        Resolution::Failed(_) => None,
+    }
+}
+
+/// How the response shows itself to be a program: its content type, or its
+/// first bytes.
+fn executable(content_type: Option<&str>, head: &[u8]) -> Option<String> {
+    let mime = content_type
+        .and_then(|t| t.split(';').next())
+        .map(|t| t.trim().to_lowercase());
+    if let Some(mime) = mime.filter(|m| EXECUTABLE_TYPES.contains(&m.as_str())) {
+        return Some(mime);
+    }
+    MAGIC
+        .iter()
+        .find(|m| head.starts_with(m))
+        .map(|m| match *m {
+            b"#!" => "script".to_string(),
+            _ => "binary".to_string(),
+        })
+}
+
+fn names_executable(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    EXECUTABLE_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+fn same_owner(a: &str, b: &str) -> bool {
+    a == b
+        || SAME_OWNER
+            .iter()
+            .any(|group| group.contains(&a) && group.contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reached(final_url: &str, content_type: &str, head: &[u8]) -> Resolution {
+        Resolution::Reached {
+            final_url: final_url.to_string(),
+            content_type: Some(content_type.to_string()),
+            head: head.to_vec(),
+        }
+    }
+
+    fn rule(url: &str, resolution: Resolution) -> Option<(&'static str, Severity)> {
+        judge(url, &resolution).map(|(id, _, severity, _)| (id, severity))
+    }
+
+    #[test]
+    fn test_judges_redirects_executables_and_dead_links() {
+        let docs = "https://docs.tool.dev/setup";
+        assert_eq!(
+            rule(
+                docs,
+                reached(docs, "text/html; charset=utf-8", b"<!doctype html>")
+            ),
+            None
+        );
+        assert_eq!(
+            rule(
+                docs,
+                reached("https://cdn.other.net/a", "text/plain", b"#!/bin/sh\ncurl")
+            ),
+            Some((EXECUTABLE_RULE_ID, Severity::Error))
+        );
+        assert_eq!(
+            rule(docs, reached(docs, "application/x-sh", b"echo")),
+            Some((EXECUTABLE_RULE_ID, Severity::Error))
+        );
+        // The link says it is a script.
+        let script = "https://tool.dev/install.sh";
+        assert_eq!(
+            rule(script, reached(script, "text/plain", b"#!/bin/sh")),
+            None
+        );
+        assert_eq!(
+            rule(
+                docs,
+                reached("https://cdn.other.net/setup", "text/html", b"")
+            ),
+            Some((REDIRECT_RULE_ID, Severity::Warning))
+        );
+        assert_eq!(
+            rule(
+                "https://github.com/a/b/releases/download/v1/notes.txt",
+                reached(
+                    "https://objects.githubusercontent.com/x",
+                    "text/plain",
+                    b"notes"
+                )
+            ),
+            None
+        );
+        assert_eq!(
+            rule(docs, Resolution::Missing(404)),
+            Some((DEAD_RULE_ID, Severity::Info))
+        );
+        assert_eq!(
+            rule(docs, Resolution::Unresolvable),
+            Some((DEAD_RULE_ID, Severity::Warning))
+        );
+        assert_eq!(rule(docs, Resolution::Failed("timeout".into())), None);
+    }
+}
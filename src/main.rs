@@ -1,122 +1,468 @@
-mod config;
-mod engine;
-mod finding;
-mod output;
-mod remote;
-mod rules;
-mod scanner;
-
 use clap::Parser;
-use config::{CliArgs, Config, ConfigFile};
-use engine::Engine;
-use rules::RuleRegistry;
-use std::path::PathBuf;
+use skill_issue::config::{CliArgs, Config, ConfigFile, OutputFormat};
+use skill_issue::diagnostics::Diagnostic;
+use skill_issue::engine::Engine;
+use skill_issue::finding::report_path;
+use skill_issue::history::{self, History};
+use skill_issue::output::SkillFindings;
+use skill_issue::provenance::Provenance;
+use skill_issue::simulate::{self, Profile};
+use skill_issue::{
+    attestation, commands, config, email, meta, output, pipeline, remote, report_signing, rules,
+    scanner, signature, trace, update,
+};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Exit code for a target with nothing to scan, distinct from findings (1, 2).
+const EMPTY_TARGET_EXIT_CODE: i32 = 3;
 
 fn main() {
-    let args = CliArgs::parse();
+    let mut args = CliArgs::parse();
 
     if args.no_color {
         colored::control::set_override(false);
     }
 
+    remote::http::configure(remote::http::HttpSettings {
+        connect_timeout: Duration::from_secs(args.connect_timeout),
+        read_timeout: Duration::from_secs(args.read_timeout),
+        retries: args.retries,
+        max_rate_limit_wait: Duration::from_secs(args.max_rate_limit_wait),
+    });
+
+    if let Some(ref path) = args.trace_log {
+        if let Err(e) = trace::open(path) {
+            eprintln!("error: {e}");
+            std::process::exit(2);
+        }
+    }
+
+    if args.version {
+        print_version(args.verbose, args.check_updates, args.offline);
+        return;
+    }
+
+    if let Some(ref command) = args.command {
+        let exit_code = commands::run(command, &args);
+        std::process::exit(exit_code);
+    }
+
     let quiet = args.quiet;
     let verbose = args.verbose;
+
+    // `--remote .` scans this checkout's GitHub repository at its current ref.
+    if args.remote.as_deref() == Some(".") {
+        match remote::git::target_from_checkout(&args.path) {
+            Ok(target) => {
+                if verbose {
+                    eprintln!("Resolved --remote . to {target}");
+                }
+                args.remote = Some(target.display());
+            }
+            Err(e) => {
+                eprintln!("error: --remote .: {e}");
+                std::process::exit(2);
+            }
+        }
+    }
     let is_remote = args.remote.is_some();
+    if args.offline && !quiet {
+        let skipped = [
+            (args.check_links, "--check-links"),
+            (args.verify_secrets, "--verify-secrets"),
+            (args.reputation, "--reputation"),
+        ];
+        for (_, flag) in skipped.iter().filter(|(requested, _)| *requested) {
+            eprintln!("note: --offline: skipping {flag}");
+        }
+    }
+    if is_remote && !args.more_paths.is_empty() {
+        eprintln!("error: --remote scans one target; drop the extra paths");
+        std::process::exit(2);
+    }
 
-    // Skip config file loading for remote scans
-    let config_file = if is_remote {
-        None
-    } else {
-        let config_path = args
-            .config
-            .clone()
-            .unwrap_or_else(|| args.path.join(".skill-issue.toml"));
-        if config_path.exists() {
-            match std::fs::read_to_string(&config_path) {
-                Ok(contents) => match toml::from_str::<ConfigFile>(&contents) {
-                    Ok(cf) => Some(cf),
-                    Err(e) => {
-                        eprintln!("warning: failed to parse config file: {e}");
-                        None
-                    }
-                },
+    // Each path is scanned with its own config file, as if given alone. In
+    // --batch mode every skill found under PATH is a target, and all share
+    // PATH's config file.
+    let batch_config = args.batch.then(|| load_config_file(&args, false));
+    let targets: Vec<_> = match batch_config {
+        Some(ref file) => {
+            let markers = config::skill_markers(
+                &args.skill_markers,
+                file.as_ref()
+                    .ok()
+                    .and_then(Option::as_ref)
+                    .map_or(&[], |f| &f.settings.skill_markers),
+            );
+            match scanner::discover_skills(&args.path, &markers) {
+                Ok(skills) if skills.is_empty() && !args.allow_empty => {
+                    eprintln!(
+                        "error: no skills found under {} (looked for {})",
+                        args.path.display(),
+                        markers.join(", ")
+                    );
+                    std::process::exit(EMPTY_TARGET_EXIT_CODE);
+                }
+                Ok(skills) => skills,
                 Err(e) => {
-                    eprintln!("warning: failed to read config file: {e}");
-                    None
+                    eprintln!("error: {e}");
+                    std::process::exit(2);
                 }
             }
-        } else {
-            None
         }
+        None => std::iter::once(args.path.clone())
+            .chain(args.more_paths.iter().cloned())
+            .collect(),
     };
+    let mut configs: Vec<Config> = targets
+        .iter()
+        .map(|target| {
+            let mut target_args = args.clone();
+            target_args.path = target.clone();
+            target_args.more_paths.clear();
+            let config_file = match batch_config {
+                Some(ref file) => file.clone(),
+                None => load_config_file(&target_args, is_remote),
+            };
+            config_with(target_args, config_file)
+        })
+        .collect();
+    if configs.is_empty() {
+        // --batch --allow-empty with no skills: report PATH as clean.
+        configs.push(config_with(args.clone(), batch_config.unwrap_or(Ok(None))));
+    }
+    if let Some(ref profile) = args.simulate_profile {
+        let exit_code = simulate(&args, &configs[0], profile, is_remote);
+        std::process::exit(exit_code);
+    }
+    if configs[0].sign_report.is_some()
+        && !matches!(configs[0].format, OutputFormat::Json | OutputFormat::Sarif)
+    {
+        eprintln!("error: --sign-report requires --format json or --format sarif");
+        std::process::exit(2);
+    }
 
-    let config = Config::from_args_and_file(args, config_file);
-
-    // Scan files — either remote or local
-    let (files, display_path) = if let Some(ref spec) = config.remote {
-        if verbose {
-            eprintln!("Scanning remote: {spec}");
-        }
-
-        let files = match remote::fetch_remote_skill(spec, config.github_token.as_deref(), verbose)
-        {
-            Ok(f) => f,
+    let mut outcomes = Vec::new();
+    // Files the attestation covers, from every target.
+    let mut subjects = Vec::new();
+    for (i, config) in configs.iter().enumerate() {
+        // Scan files — either remote or local — and run the rules
+        let outcome = match pipeline::run(config) {
+            Ok(o) => o,
             Err(e) => {
                 eprintln!("error: {e}");
                 std::process::exit(2);
             }
         };
-
-        let display_path = PathBuf::from(spec);
-        (files, display_path)
-    } else {
-        if verbose {
-            eprintln!("Scanning: {}", config.path.display());
+        if let Some(ref reason) = outcome.empty {
+            if !config.allow_empty {
+                eprintln!(
+                    "error: nothing to scan in {}: {reason} (pass --allow-empty to report a clean result anyway)",
+                    outcome.display_path.display()
+                );
+                write_meta(
+                    config,
+                    &outcome,
+                    EMPTY_TARGET_EXIT_CODE,
+                    format!("nothing to scan: {reason}"),
+                );
+                std::process::exit(EMPTY_TARGET_EXIT_CODE);
+            }
         }
 
-        let files = match scanner::scan_directory(&config.path) {
-            Ok(f) => f,
-            Err(e) => {
-                eprintln!("error: {e}");
+        if config.history {
+            let target = match config.remote {
+                Some(ref spec) => spec.clone(),
+                None => history::target_key(&config.path.display().to_string()),
+            };
+            let recorded = history::db_path(config.history_db.as_deref())
+                .and_then(|p| History::open(&p))
+                .and_then(|mut db| db.record(&target, &outcome));
+            if let Err(e) = recorded {
+                eprintln!("error: failed to record scan history: {e}");
                 std::process::exit(2);
             }
+        }
+        // Subjects are named like the findings: relative to PATH in --batch
+        // mode, under their own path when several are given.
+        let prefix = match targets.get(i) {
+            Some(target) if args.batch => Some(target.strip_prefix(&args.path).unwrap_or(target)),
+            Some(target) if targets.len() > 1 => Some(target.as_path()),
+            Some(_) => Some(Path::new("")),
+            // --batch --allow-empty with no skills: no files to attest.
+            None => None,
         };
-
-        let display_path = config.path.clone();
-        (files, display_path)
+        if let (Some(prefix), Some(_)) = (prefix, &config.attest) {
+            match signature::target_entries(config, &outcome.files) {
+                Ok(entries) => subjects.extend(attestation::prefixed(prefix, entries)),
+                Err(e) => {
+                    eprintln!("error: failed to write attestation: {e}");
+                    std::process::exit(2);
+                }
+            }
+        }
+        outcomes.push(outcome);
+    }
+    // Output settings and thresholds come from the first path.
+    let config = configs.swap_remove(0);
+    // Batch findings are named relative to PATH, so each skill's share of
+    // the report can be picked out again.
+    let skills: Vec<PathBuf> = targets
+        .iter()
+        .map(|t| t.strip_prefix(&args.path).unwrap_or(t).to_path_buf())
+        .collect();
+    let outcome = if args.batch {
+        let mut outcome = if outcomes.is_empty() {
+            pipeline::ScanOutcome {
+                files: Vec::new(),
+                findings: Vec::new(),
+                display_path: PathBuf::new(),
+                provenance: Provenance::new(
+                    String::new(),
+                    None,
+                    &rules::RuleRegistry::new(),
+                    chrono::Utc::now(),
+                    Duration::ZERO,
+                ),
+                empty: None,
+                stopped_early: false,
+                baselined: 0,
+                diagnostics: config.diagnostics.clone(),
+            }
+        } else {
+            pipeline::merge(&skills, outcomes)
+        };
+        outcome.display_path = args.path.clone();
+        outcome.provenance.target = report_path(&args.path);
+        outcome
+    } else if outcomes.len() == 1 {
+        outcomes.swap_remove(0)
+    } else {
+        pipeline::merge(&targets, outcomes)
     };
 
-    if verbose {
-        eprintln!("Found {} files to analyze", files.len());
+    if let Some(ref path) = config.attest {
+        if let Err(e) = attestation::write(path, &subjects, &outcome) {
+            eprintln!("error: failed to write attestation: {e}");
+            std::process::exit(2);
+        }
     }
 
-    // Load rules
-    let mut registry = RuleRegistry::new();
-    registry.load_defaults();
-
-    if verbose {
-        eprintln!("Loaded {} rules", registry.all_rules().len());
+    // Output
+    let findings = &outcome.findings;
+    let provenance = if config.deterministic {
+        outcome.provenance.reproducible()
+    } else {
+        outcome.provenance.clone()
+    };
+    let output = match config.format {
+        OutputFormat::Table if args.batch => output::table::format_batch_table(
+            &SkillFindings::group(&skills, findings),
+            config.error_on,
+        ),
+        OutputFormat::Json if args.batch => output::json::format_batch_json(
+            &SkillFindings::group(&skills, findings),
+            &outcome.display_path,
+            Some(&provenance),
+            &outcome.diagnostics,
+            config.error_on,
+        ),
+        _ => output::format_findings(
+            &config.format,
+            findings,
+            &outcome.display_path,
+            &scanner::skill_roots(&outcome.files, &config.skill_markers),
+            &provenance,
+            &outcome.diagnostics,
+            config.columns,
+            // Snippets would show redacted secrets in full.
+            if config.redact { &[] } else { &outcome.files },
+        ),
+    };
+    // A signed report is always printed, so there is something to verify.
+    if let (Some(key), Some(out)) = (&config.sign_report, &config.signature_out) {
+        let signed = report_signing::sign(format!("{output}\n").as_bytes(), key)
+            .and_then(|jws| std::fs::write(out, jws + "\n").map_err(|e| e.to_string()));
+        if let Err(e) = signed {
+            eprintln!("error: failed to sign report: {e}");
+            std::process::exit(2);
+        }
+        println!("{output}");
+    } else if !quiet || !findings.is_empty() {
+        println!("{output}");
     }
 
-    // Run engine
-    let engine = Engine::new(&config, &registry);
-    let findings = engine.run(&files);
+    if outcome.stopped_early && !quiet {
+        eprintln!(
+            "note: --fail-fast stopped at the first finding at or above {}; the report is partial",
+            config.error_on
+        );
+    }
 
-    // Output
-    let output = output::format_findings(&config.format, &findings, &display_path);
-    if !quiet || !findings.is_empty() {
-        println!("{output}");
+    if outcome.baselined > 0 && !quiet {
+        if let Some(ref path) = config.baseline {
+            eprintln!(
+                "note: {} finding(s) hidden by baseline {}",
+                outcome.baselined,
+                path.display()
+            );
+        }
     }
 
     // Summary on stderr if not quiet
     if !quiet && verbose {
         eprintln!(
             "Scan complete: {} files, {} findings",
-            files.len(),
+            outcome.files.len(),
             findings.len()
         );
     }
 
-    let exit_code = Engine::exit_code(&findings, config.error_on);
+    if !quiet && update::enabled(config.check_updates, config.offline) {
+        if let Some(note) = update::latest_version().and_then(|v| update::notice(&v)) {
+            eprintln!("{note}");
+        }
+    }
+
+    if config.email.is_some() && config.offline {
+        if !quiet {
+            eprintln!("note: --offline: skipping the [email] report");
+        }
+    } else if let Some(ref settings) = config.email {
+        let target = outcome.display_path.display().to_string();
+        match email::send_if_needed(settings, findings, &target) {
+            Ok(true) if verbose => eprintln!("Emailed report to {}", settings.to.join(", ")),
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("error: failed to email report: {e}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let exit_code = Engine::exit_code(findings, config.error_on);
+    write_meta(
+        &config,
+        &outcome,
+        exit_code,
+        meta::exit_reason(findings, config.error_on),
+    );
     std::process::exit(exit_code);
 }
+
+/// `--simulate-profile`: collect the files once, run the rules under the
+/// current config and under `profile`, and print the two side by side.
+/// Exits as the current config would, so a simulation never loosens a gate.
+fn simulate(args: &CliArgs, config: &Config, profile: &Profile, is_remote: bool) -> i32 {
+    let started_at = chrono::Utc::now();
+    let timer = std::time::Instant::now();
+    let run = || -> Result<_, String> {
+        let file = load_config_file(args, is_remote).ok().flatten();
+        let proposed = profile.config(args, file)?;
+        let collected = pipeline::collect_files(config)?;
+        let current = pipeline::analyze(config, collected.clone(), started_at, timer)?;
+        let simulated = pipeline::analyze(&proposed, collected, started_at, timer)?;
+        Ok((proposed, current, simulated))
+    };
+    let (proposed, current, simulated) = match run() {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return 2;
+        }
+    };
+    if let (Some(reason), false) = (&current.empty, config.allow_empty) {
+        eprintln!(
+            "error: nothing to scan in {}: {reason} (pass --allow-empty to report a clean result anyway)",
+            current.display_path.display()
+        );
+        return EMPTY_TARGET_EXIT_CODE;
+    }
+    let exit_code = Engine::exit_code(&current.findings, config.error_on);
+    let simulation = simulate::compare(
+        config,
+        current.findings,
+        profile,
+        &proposed,
+        simulated.findings,
+    );
+    match config.format {
+        OutputFormat::Json => println!("{}", simulate::format_json(&simulation)),
+        _ => println!("{}", simulate::format_table(&simulation)),
+    }
+    exit_code
+}
+
+/// The config file for a scan: `--config`, or `.skill-issue.toml` in the
+/// target without the settings a target may not make. Remote scans read none.
+fn load_config_file(args: &CliArgs, is_remote: bool) -> Result<Option<ConfigFile>, Diagnostic> {
+    if is_remote {
+        return Ok(None);
+    }
+    let config_dir = if args.path.is_file() {
+        args.path.parent().unwrap_or(&args.path)
+    } else {
+        &args.path
+    };
+    let config_path = args
+        .config
+        .clone()
+        .unwrap_or_else(|| config_dir.join(".skill-issue.toml"));
+    let file = pipeline::read_config_file(&config_path)?;
+    Ok(match args.config {
+        Some(_) => file,
+        None => file.map(ConfigFile::from_target),
+    })
+}
+
+/// The config for `args`, with any problem reading its file kept for the
+/// report.
+fn config_with(args: CliArgs, file: Result<Option<ConfigFile>, Diagnostic>) -> Config {
+    let (file, problem) = match file {
+        Ok(file) => (file, None),
+        Err(diagnostic) => (None, Some(diagnostic)),
+    };
+    let mut config = Config::from_args_and_file(args, file);
+    config.diagnostics.extend(problem);
+    config
+}
+
+fn write_meta(config: &Config, outcome: &pipeline::ScanOutcome, exit_code: i32, reason: String) {
+    let Some(ref path) = config.meta_out else {
+        return;
+    };
+    if let Err(e) = meta::write(path, &meta::build(config, outcome, exit_code, reason)) {
+        eprintln!("error: failed to write run metadata: {e}");
+        std::process::exit(2);
+    }
+}
+
+fn print_version(verbose: bool, check_updates: bool, offline: bool) {
+    println!("skill-issue {}", env!("CARGO_PKG_VERSION"));
+    if !verbose {
+        return;
+    }
+
+    println!("rule set: {}", rules::ruleset_version());
+    for (name, digest) in rules::pattern_versions() {
+        println!("  {name:<12} {digest}");
+    }
+
+    let latest = if update::enabled(check_updates, offline) {
+        update::latest_version()
+    } else {
+        update::read_cache().and_then(|c| c.latest_version)
+    };
+    match latest {
+        Some(v) => match update::notice(&v) {
+            Some(note) => println!("{note}"),
+            None => println!("latest release: {v} (up to date)"),
+        },
+        None if !update::enabled(check_updates, offline) => println!(
+            "latest release: unknown (update checks are off; use --check-updates or {}=1)",
+            update::OPT_IN_ENV
+        ),
+        None => println!("latest release: unknown (check failed)"),
+    }
+}
@@ -0,0 +1,128 @@
+use crate::config::Config;
+use crate::diagnostics::Diagnostic;
+use crate::engine::Engine;
+use crate::finding::{Confidence, Finding, Severity};
+use crate::pipeline::ScanOutcome;
+use crate::provenance::Provenance;
+use serde::Serialize;
+use std::path::Path;
+
+/// Machine-readable facts about a run, written by `--meta-out` so CI can
+/// branch on structured data rather than exit codes and stderr.
+#[derive(Debug, Serialize)]
+pub struct RunMeta<'a> {
+    pub exit_code: i32,
+    /// Why the run exited with `exit_code`.
+    pub exit_reason: String,
+    pub thresholds: Thresholds<'a>,
+    pub counts: Counts,
+    /// Runtime warnings; any entry means the scan was degraded.
+    pub diagnostics: &'a [Diagnostic],
+    pub provenance: &'a Provenance,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Thresholds<'a> {
+    pub min_severity: Severity,
+    pub min_rule_severity: Severity,
+    pub min_confidence: Confidence,
+    pub error_on: Severity,
+    pub ignored_rules: &'a [String],
+    pub allow_empty: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Counts {
+    pub files: usize,
+    pub findings: usize,
+    pub critical: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    /// Findings at or above `error_on`.
+    pub blocking: usize,
+    /// Findings hidden by `--baseline`, not included above.
+    pub baselined: usize,
+}
+
+/// Human-readable rationale for `Engine::exit_code`.
+pub fn exit_reason(findings: &[Finding], error_on: Severity) -> String {
+    let blocking = findings.iter().filter(|f| f.severity >= error_on).count();
+    match Engine::max_severity(findings) {
+        None => "no findings".to_string(),
+        Some(_) if blocking > 0 => format!("{blocking} finding(s) at or above {error_on}"),
+        Some(Severity::Info) => "only info findings".to_string(),
+        Some(max) => format!("highest severity {max} is below error-on threshold {error_on}"),
+    }
+}
+
+pub fn build<'a>(
+    config: &'a Config,
+    outcome: &'a ScanOutcome,
+    exit_code: i32,
+    exit_reason: String,
+) -> RunMeta<'a> {
+    let findings = &outcome.findings;
+    let count = |s: Severity| findings.iter().filter(|f| f.severity == s).count();
+    RunMeta {
+        exit_code,
+        exit_reason,
+        thresholds: Thresholds {
+            min_severity: config.min_severity,
+            min_rule_severity: config.min_rule_severity,
+            min_confidence: config.min_confidence,
+            error_on: config.error_on,
+            ignored_rules: &config.ignore,
+            allow_empty: config.allow_empty,
+        },
+        counts: Counts {
+            files: outcome.files.len(),
+            findings: findings.len(),
+            critical: count(Severity::Critical),
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            info: count(Severity::Info),
+            blocking: findings
+                .iter()
+                .filter(|f| f.severity >= config.error_on)
+                .count(),
+            baselined: outcome.baselined,
+        },
+        diagnostics: &outcome.diagnostics,
+        provenance: &outcome.provenance,
+    }
+}
+
+pub fn write(path: &Path, meta: &RunMeta) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(meta).map_err(|e| e.to_string())?;
+    std::fs::write(path, json + "\n").map_err(|e| format!("{}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_reason_matches_exit_code() {
+        assert_eq!(exit_reason(&[], Severity::Error), "no findings");
+        let findings = [
+            Finding::test("SL-EXEC-001", Severity::Error, "SKILL.md", 1),
+            Finding::test("SL-EXEC-001", Severity::Warning, "SKILL.md", 1),
+        ];
+        assert_eq!(
+            exit_reason(&findings, Severity::Error),
+            "1 finding(s) at or above error"
+        );
+        assert_eq!(
+            exit_reason(&findings, Severity::Critical),
+            "highest severity error is below error-on threshold critical"
+        );
+        assert_eq!(
+            exit_reason(
+                &[Finding::test("SL-EXEC-001", Severity::Info, "SKILL.md", 1)],
+                Severity::Error
+            ),
+            "only info findings"
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::RuleDoc;
+use crate::scanner::{FileType, ScannedFile};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const NOISE_RULE_ID: &str = "SL-META-007";
+const NOISE_RULE_NAME: &str = "Noisy Findings Suppressed";
+
+pub const RULE_DOC: RuleDoc = RuleDoc {
+    id: NOISE_RULE_ID,
+    name: NOISE_RULE_NAME,
+    severity: Severity::Info,
+    applies_to: &[],
+    description: "Reported by `--suppress-noise` when it collapses a rule that fires on most lines of a file, or downgrades findings in vendored or minified files. It records what was hidden so nothing disappears silently.",
+    remediation: "Review the collapsed findings with a scan without `--suppress-noise`, or exclude the vendored path from the scan.",
+};
+
+/// A rule must fire on more than this share of a file's lines to be collapsed.
+const FLOOD_RATIO: f64 = 0.5;
+/// Below this many findings a rule is never treated as flooding a file.
+const FLOOD_MIN_FINDINGS: usize = 5;
+/// Lines longer than this are only found in generated or minified code.
+const MINIFIED_LINE_LEN: usize = 1000;
+
+const VENDORED_DIRS: &[&str] = &[
+    "vendor",
+    "vendored",
+    "third_party",
+    "dist",
+    "bower_components",
+];
+const MINIFIED_SUFFIXES: &[&str] = &[".min.js", ".min.css", ".min.mjs", ".bundle.js"];
+
+/// Why a file's findings were treated as noise. Markdown never is: the
+/// agent reads it, however it was produced.
+fn asset_reason(file: &ScannedFile) -> Option<&'static str> {
+    if file.file_type == FileType::Markdown {
+        return None;
+    }
+    let path = &file.relative_path;
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase());
+    if name
+        .as_deref()
+        .is_some_and(|n| MINIFIED_SUFFIXES.iter().any(|s| n.ends_with(s)))
+    {
+        return Some("a minified asset");
+    }
+    if path.components().any(|c| {
+        VENDORED_DIRS
+            .iter()
+            .any(|d| c.as_os_str().eq_ignore_ascii_case(d))
+    }) {
+        return Some("a vendored dependency");
+    }
+    let code = matches!(
+        file.file_type,
+        FileType::Script | FileType::Json | FileType::Unknown
+    );
+    if code && file.content.lines().any(|l| l.len() > MINIFIED_LINE_LEN) {
+        return Some("minified code");
+    }
+    None
+}
+
+/// Collapse pathological finding floods so reports stay reviewable.
+///
+/// Warnings in vendored or minified files are downgraded to info; errors
+/// there are real whatever the file's origin, so they stay. A rule that
+/// fires on more than half the lines of a file is reduced to its first
+/// finding. Each adjustment is explained by an `SL-META-007` finding, returned
+/// separately so the caller can apply rule policy to it.
+pub fn suppress(findings: Vec<Finding>, files: &[ScannedFile]) -> (Vec<Finding>, Vec<Finding>) {
+    let mut meta = Vec::new();
+
+    let mut by_file: BTreeMap<PathBuf, Vec<Finding>> = BTreeMap::new();
+    for f in findings {
+        by_file.entry(f.location.file.clone()).or_default().push(f);
+    }
+
+    let mut kept = Vec::new();
+    for (path, mut file_findings) in by_file {
+        let file = files.iter().find(|f| f.relative_path == path);
+
+        if let Some(reason) = file.and_then(asset_reason) {
+            let downgraded = file_findings
+                .iter_mut()
+                .filter(|f| f.severity == Severity::Warning)
+                .map(|f| f.severity = Severity::Info)
+                .count();
+            if downgraded > 0 {
+                meta.push(meta_finding(
+                    &path,
+                    format!("{downgraded} finding(s) downgraded to info: file looks like {reason}"),
+                ));
+            }
+        }
+
+        let line_count = file.map_or(0, |f| f.content.lines().count()).max(1);
+        let mut per_rule: BTreeMap<String, Vec<Finding>> = BTreeMap::new();
+        for f in file_findings {
+            per_rule.entry(f.rule_id.clone()).or_default().push(f);
+        }
+        for (rule_id, mut rule_findings) in per_rule {
+            let mut lines: Vec<usize> = rule_findings.iter().map(|f| f.location.line).collect();
+            lines.sort_unstable();
+            lines.dedup();
+            let flooded = rule_findings.len() >= FLOOD_MIN_FINDINGS
+                && lines.len() as f64 / line_count as f64 > FLOOD_RATIO;
+            if flooded {
+                let collapsed = rule_findings.len() - 1;
+                rule_findings.truncate(1);
+                meta.push(meta_finding(
+                    &path,
+                    format!(
+                        "{rule_id} fired on {} of {line_count} lines; {collapsed} similar finding(s) collapsed into the first",
+                        lines.len()
+                    ),
+                ));
+            }
+            kept.extend(rule_findings);
+        }
+    }
+
+    (kept, meta)
+}
+
+fn meta_finding(path: &Path, message: String) -> Finding {
+    Finding {
+        rule_id: NOISE_RULE_ID.to_string(),
+        rule_name: NOISE_RULE_NAME.to_string(),
+        severity: Severity::Info,
+        message,
+        location: Location {
+            file: path.to_path_buf(),
+            line: 1,
+            column: 1,
+        },
+        matched_text: String::new(),
+        confidence: Confidence::High,
+        tags: Vec::new(),
+        domain: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, content: &str) -> ScannedFile {
+        ScannedFile {
+            path: PathBuf::from(path),
+            relative_path: PathBuf::from(path),
+            file_type: FileType::from_path(Path::new(path)),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_flooding_rule_is_collapsed() {
+        let files = vec![file("data.md", &"1.2.3.4\n".repeat(10))];
+        let findings = (1..=10)
+            .map(|l| Finding::test("SL-NET-008", Severity::Warning, "data.md", l))
+            .collect();
+        let (kept, meta) = suppress(findings, &files);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].location.line, 1);
+        assert_eq!(meta.len(), 1);
+        assert!(meta[0].message.contains("9 similar"));
+    }
+
+    #[test]
+    fn test_sparse_rule_untouched() {
+        let files = vec![file("SKILL.md", &"text\n".repeat(20))];
+        let findings = (1..=5)
+            .map(|l| Finding::test("SL-NET-008", Severity::Warning, "SKILL.md", l))
+            .collect();
+        let (kept, meta) = suppress(findings, &files);
+        assert_eq!(kept.len(), 5);
+        assert!(meta.is_empty());
+    }
+
+    #[test]
+    fn test_minified_asset_downgraded() {
+        let files = vec![file("assets/app.min.js", "eval(x)")];
+        let (kept, meta) = suppress(
+            vec![Finding::test(
+                "SL-EXEC-002",
+                Severity::Warning,
+                "assets/app.min.js",
+                1,
+            )],
+            &files,
+        );
+        assert_eq!(kept[0].severity, Severity::Info);
+        assert!(meta[0]
+            .message
+            .ends_with("file looks like a minified asset"));
+
+        let critical = Finding::test("SL-EXEC-011", Severity::Critical, "assets/app.min.js", 1);
+        let (kept, meta) = suppress(vec![critical], &files);
+        assert_eq!(kept[0].severity, Severity::Critical);
+        assert!(meta.is_empty());
+    }
+
+    #[test]
+    fn test_vendored_and_long_lines_detected() {
+        assert_eq!(
+            asset_reason(&file("vendor/lib.js", "x")),
+            Some("a vendored dependency")
+        );
+        assert_eq!(
+            asset_reason(&file("app.js", &"x".repeat(2000))),
+            Some("minified code")
+        );
+        assert_eq!(asset_reason(&file("SKILL.md", &"x".repeat(2000))), None);
+        assert_eq!(asset_reason(&file("vendor/SKILL.md", "x")), None);
+        assert_eq!(asset_reason(&file("scripts/run.sh", "echo hi")), None);
+    }
+}
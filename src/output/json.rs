@@ -1,44 +1,143 @@
-use crate::finding::{Finding, Severity};
+use super::SkillFindings;
+use crate::diagnostics::Diagnostic;
+use crate::finding::{report_path, Category, Finding, Severity};
+use crate::provenance::Provenance;
+use crate::scoring::{self, CategoryCounts, Grade};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 #[derive(Serialize)]
 struct JsonOutput<'a> {
     version: &'static str,
     skill_path: String,
-    findings: &'a [Finding],
+    findings: Vec<JsonFinding<'a>>,
     summary: JsonSummary,
+    /// Runtime warnings; any entry means the scan was degraded.
+    diagnostics: &'a [Diagnostic],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<&'a Provenance>,
+}
+
+/// A finding plus its fingerprint, for `report diff` and `feedback`.
+#[derive(Serialize)]
+struct JsonFinding<'a> {
+    #[serde(flatten)]
+    finding: &'a Finding,
+    fingerprint: String,
 }
 
 #[derive(Serialize)]
 struct JsonSummary {
     total: usize,
+    critical: usize,
     errors: usize,
     warnings: usize,
     info: usize,
+    risk_score: u32,
+    grade: Grade,
+    categories: BTreeMap<Category, CategoryCounts>,
 }
 
-pub fn format_json(findings: &[Finding], skill_path: &Path) -> String {
+pub fn format_json(
+    findings: &[Finding],
+    skill_path: &Path,
+    provenance: Option<&Provenance>,
+    diagnostics: &[Diagnostic],
+) -> String {
     let output = JsonOutput {
         version: env!("CARGO_PKG_VERSION"),
-        skill_path: skill_path.display().to_string(),
-        findings,
-        summary: JsonSummary {
-            total: findings.len(),
-            errors: findings
-                .iter()
-                .filter(|f| f.severity == Severity::Error)
-                .count(),
-            warnings: findings
-                .iter()
-                .filter(|f| f.severity == Severity::Warning)
-                .count(),
-            info: findings
-                .iter()
-                .filter(|f| f.severity == Severity::Info)
-                .count(),
-        },
+        skill_path: report_path(skill_path),
+        findings: json_findings(findings),
+        summary: summary(findings),
+        diagnostics,
+        provenance,
     };
 
     serde_json::to_string_pretty(&output).unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
 }
+
+fn json_findings(findings: &[Finding]) -> Vec<JsonFinding<'_>> {
+    findings
+        .iter()
+        .map(|finding| JsonFinding {
+            finding,
+            fingerprint: finding.fingerprint(),
+        })
+        .collect()
+}
+
+fn summary(findings: &[Finding]) -> JsonSummary {
+    let trust = scoring::score(findings);
+    let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+    JsonSummary {
+        total: findings.len(),
+        critical: count(Severity::Critical),
+        errors: count(Severity::Error),
+        warnings: count(Severity::Warning),
+        info: count(Severity::Info),
+        risk_score: trust.risk_score,
+        grade: trust.grade,
+        categories: trust.categories,
+    }
+}
+
+#[derive(Serialize)]
+struct BatchOutput<'a> {
+    version: &'static str,
+    skill_path: String,
+    skills: Vec<BatchSkill<'a>>,
+    summary: BatchSummary,
+    diagnostics: &'a [Diagnostic],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<&'a Provenance>,
+}
+
+#[derive(Serialize)]
+struct BatchSkill<'a> {
+    path: String,
+    passed: bool,
+    findings: Vec<JsonFinding<'a>>,
+    summary: JsonSummary,
+}
+
+#[derive(Serialize)]
+struct BatchSummary {
+    skills: usize,
+    passed: usize,
+    failed: usize,
+}
+
+/// `--batch` report: each skill's findings and summary, and whether it
+/// passed (no finding at or above `error_on`).
+pub fn format_batch_json(
+    skills: &[SkillFindings],
+    skill_path: &Path,
+    provenance: Option<&Provenance>,
+    diagnostics: &[Diagnostic],
+    error_on: Severity,
+) -> String {
+    let skills: Vec<BatchSkill> = skills
+        .iter()
+        .map(|skill| BatchSkill {
+            path: report_path(&skill.path),
+            passed: skill.passed(error_on),
+            findings: json_findings(&skill.findings),
+            summary: summary(&skill.findings),
+        })
+        .collect();
+    let passed = skills.iter().filter(|s| s.passed).count();
+    let output = BatchOutput {
+        version: env!("CARGO_PKG_VERSION"),
+        skill_path: report_path(skill_path),
+        summary: BatchSummary {
+            skills: skills.len(),
+            passed,
+            failed: skills.len() - passed,
+        },
+        skills,
+        diagnostics,
+        provenance,
+    };
+    serde_json::to_string_pretty(&output).unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
+}
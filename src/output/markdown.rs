@@ -0,0 +1,134 @@
+use crate::finding::{report_path, Finding, Severity};
+use crate::scoring;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const SEVERITIES: [Severity; 4] = [
+    Severity::Critical,
+    Severity::Error,
+    Severity::Warning,
+    Severity::Info,
+];
+
+fn emoji(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "⛔",
+        Severity::Error => "🔴",
+        Severity::Warning => "🟡",
+        Severity::Info => "🔵",
+    }
+}
+
+/// A GitHub-flavored Markdown summary for a pull request comment: grade and
+/// counts, then a collapsible section per file. Sections with an error or
+/// critical finding start open.
+pub fn format_markdown(findings: &[Finding], skill_path: &Path) -> String {
+    let trust = scoring::score(findings);
+    let mut out = vec![format!(
+        "## skill-issue: `{}`\n",
+        report_path(skill_path).replace('`', "'")
+    )];
+    if findings.is_empty() {
+        out.push(format!(
+            "✅ No issues found. **Grade {}** (risk score {})",
+            trust.grade, trust.risk_score
+        ));
+        return out.join("\n");
+    }
+
+    let counts: Vec<String> = SEVERITIES
+        .iter()
+        .map(|s| (s, findings.iter().filter(|f| f.severity == *s).count()))
+        .filter(|(_, n)| *n > 0)
+        .map(|(s, n)| format!("{} {n} {s}", emoji(*s)))
+        .collect();
+    out.push(format!(
+        "**Grade {}** (risk score {}) · {}\n",
+        trust.grade,
+        trust.risk_score,
+        counts.join(" · ")
+    ));
+
+    let mut by_file: BTreeMap<&PathBuf, Vec<&Finding>> = BTreeMap::new();
+    for f in findings {
+        by_file.entry(&f.location.file).or_default().push(f);
+    }
+    for (path, mut file_findings) in by_file {
+        file_findings.sort_by_key(|f| (f.location.line, f.location.column));
+        let open = if file_findings.iter().any(|f| f.severity >= Severity::Error) {
+            " open"
+        } else {
+            ""
+        };
+        out.push(format!(
+            "<details{open}>\n<summary><code>{}</code> ({} finding(s))</summary>\n",
+            cell(&report_path(path)),
+            file_findings.len()
+        ));
+        out.push("| Severity | Rule | Line | Message |".to_string());
+        out.push("|---|---|---|---|".to_string());
+        for f in file_findings {
+            out.push(format!(
+                "| {} {} | `{}` | {} | {} |",
+                emoji(f.severity),
+                f.severity,
+                f.rule_id,
+                f.location.line,
+                cell(&f.message)
+            ));
+        }
+        out.push("\n</details>\n".to_string());
+    }
+    out.join("\n").trim_end().to_string()
+}
+
+/// Text safe inside a table cell: one line, no column breaks, and no HTML.
+fn cell(text: &str) -> String {
+    text.replace(['\n', '\r'], " ")
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_per_file_open_when_blocking() {
+        let md = format_markdown(
+            &[
+                Finding {
+                    message: "curl x | sh".into(),
+                    ..Finding::test("SL-NET-002", Severity::Error, "run.sh", 7)
+                },
+                Finding {
+                    message: "see <docs>".into(),
+                    ..Finding::test("SL-NET-002", Severity::Info, "SKILL.md", 3)
+                },
+                Finding {
+                    message: "wget".into(),
+                    ..Finding::test("SL-NET-002", Severity::Warning, "run.sh", 2)
+                },
+            ],
+            Path::new("skills/x"),
+        );
+        assert!(md.starts_with("## skill-issue: `skills/x`\n"));
+        assert!(md.contains("· 🔴 1 error · 🟡 1 warning · 🔵 1 info"));
+        assert!(md.contains("<details>\n<summary><code>SKILL.md</code> (1 finding(s))"));
+        assert!(md.contains("<details open>\n<summary><code>run.sh</code> (2 finding(s))"));
+        assert!(md.contains("| 🔵 info | `SL-NET-002` | 3 | see &lt;docs&gt; |"));
+        // Lines in order within a file; pipes do not split the cell.
+        let warning = md.find("| 2 | wget |").unwrap();
+        assert!(warning < md.find("| 7 | curl x \\| sh |").unwrap());
+    }
+
+    #[test]
+    fn test_clean_report() {
+        assert_eq!(
+            format_markdown(&[], Path::new(".")),
+            "## skill-issue: `.`\n\n✅ No issues found. **Grade A** (risk score 0)"
+        );
+    }
+}
@@ -1,18 +1,74 @@
+pub mod html;
 pub mod json;
+pub mod markdown;
 pub mod sarif;
+pub mod stylish;
 pub mod table;
+pub mod vscode;
 
+use crate::diagnostics::Diagnostic;
 use crate::finding::Finding;
-use std::path::Path;
+use crate::provenance::Provenance;
+use crate::scanner::ScannedFile;
+use std::path::{Path, PathBuf};
 
+/// One skill's findings in a `--batch` scan.
+pub struct SkillFindings {
+    pub path: PathBuf,
+    pub findings: Vec<Finding>,
+}
+
+impl SkillFindings {
+    /// Split a combined report by skill. `skills` must not nest.
+    pub fn group(skills: &[PathBuf], findings: &[Finding]) -> Vec<Self> {
+        skills
+            .iter()
+            .map(|skill| SkillFindings {
+                path: skill.clone(),
+                findings: findings
+                    .iter()
+                    .filter(|f| f.location.file.starts_with(skill))
+                    .cloned()
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// No finding reaches `error_on`.
+    pub fn passed(&self, error_on: crate::finding::Severity) -> bool {
+        self.findings.iter().all(|f| f.severity < error_on)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn format_findings(
     format: &crate::config::OutputFormat,
     findings: &[Finding],
     skill_path: &Path,
+    skill_roots: &[PathBuf],
+    provenance: &Provenance,
+    diagnostics: &[Diagnostic],
+    columns: crate::config::ColumnUnit,
+    files: &[ScannedFile],
 ) -> String {
     match format {
         crate::config::OutputFormat::Table => table::format_table(findings),
-        crate::config::OutputFormat::Json => json::format_json(findings, skill_path),
-        crate::config::OutputFormat::Sarif => sarif::format_sarif(findings, skill_path),
+        crate::config::OutputFormat::Json => {
+            json::format_json(findings, skill_path, Some(provenance), diagnostics)
+        }
+        crate::config::OutputFormat::Sarif => sarif::format_sarif_per_skill(
+            findings,
+            skill_path,
+            skill_roots,
+            Some(provenance),
+            diagnostics,
+            columns,
+        ),
+        crate::config::OutputFormat::Vscode => vscode::format_vscode(findings, skill_path),
+        crate::config::OutputFormat::Markdown => markdown::format_markdown(findings, skill_path),
+        crate::config::OutputFormat::Stylish => stylish::format_stylish(findings, files),
+        crate::config::OutputFormat::Html => {
+            html::format_html(findings, skill_path, files, Some(provenance))
+        }
     }
 }
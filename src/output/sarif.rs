@@ -1,20 +1,83 @@
-use crate::finding::{Finding, Severity};
+use crate::config::ColumnUnit;
+use crate::diagnostics::{Code, Diagnostic};
+use crate::domains::DomainInfo;
+use crate::finding::{report_path, Finding, Severity};
+use crate::provenance::Provenance;
 use crate::rules::RuleRegistry;
+use crate::scanner;
 use serde::Serialize;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Serialize)]
-struct SarifLog {
+struct SarifLog<'a> {
     #[serde(rename = "$schema")]
     schema: &'static str,
     version: &'static str,
-    runs: Vec<SarifRun>,
+    runs: Vec<SarifRun<'a>>,
 }
 
 #[derive(Serialize)]
-struct SarifRun {
+struct SarifRun<'a> {
     tool: SarifTool,
+    #[serde(rename = "columnKind", skip_serializing_if = "Option::is_none")]
+    column_kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    invocations: Vec<SarifInvocation>,
+    #[serde(
+        rename = "versionControlProvenance",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    version_control_provenance: Vec<SarifVersionControl>,
     results: Vec<SarifResult>,
+    #[serde(rename = "automationDetails", skip_serializing_if = "Option::is_none")]
+    automation_details: Option<SarifAutomationDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<SarifRunProperties<'a>>,
+}
+
+/// Identifies a run so code-scanning tools track its alerts separately.
+#[derive(Serialize)]
+struct SarifAutomationDetails {
+    id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifInvocation {
+    execution_successful: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tool_execution_notifications: Vec<SarifNotification>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_time_utc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    machine: Option<String>,
+}
+
+/// A diagnostic, as SARIF reports problems with the run itself.
+#[derive(Serialize)]
+struct SarifNotification {
+    level: &'static str,
+    message: SarifMessage,
+    descriptor: SarifNotificationDescriptor,
+}
+
+#[derive(Serialize)]
+struct SarifNotificationDescriptor {
+    id: Code,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifVersionControl {
+    repository_uri: String,
+    revision_id: String,
+    branch: String,
+}
+
+#[derive(Serialize)]
+struct SarifRunProperties<'a> {
+    provenance: &'a Provenance,
 }
 
 #[derive(Serialize)]
@@ -51,6 +114,23 @@ struct SarifResult {
     level: String,
     message: SarifMessage,
     locations: Vec<SarifLocation>,
+    #[serde(rename = "partialFingerprints")]
+    partial_fingerprints: BTreeMap<&'static str, String>,
+    /// `new`, `absent` or `unchanged` when the results come from a report diff.
+    #[serde(rename = "baselineState", skip_serializing_if = "Option::is_none")]
+    baseline_state: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<SarifResultProperties>,
+}
+
+/// SARIF's property bag carries tags for filtering in code-scanning tools,
+/// and what is known about a network finding's domain.
+#[derive(Serialize)]
+struct SarifResultProperties {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    domain: Option<DomainInfo>,
 }
 
 #[derive(Serialize)]
@@ -86,21 +166,175 @@ struct SarifRegion {
 
 fn severity_to_level(severity: Severity) -> &'static str {
     match severity {
-        Severity::Error => "error",
+        Severity::Critical | Severity::Error => "error",
         Severity::Warning => "warning",
         Severity::Info => "note",
     }
 }
 
-pub fn format_sarif(findings: &[Finding], _skill_path: &Path) -> String {
-    format_sarif_with_rules(findings, _skill_path, None)
+pub fn format_sarif(
+    findings: &[Finding],
+    _skill_path: &Path,
+    provenance: Option<&Provenance>,
+    diagnostics: &[Diagnostic],
+    columns: ColumnUnit,
+) -> String {
+    format_sarif_with_rules(
+        findings,
+        _skill_path,
+        None,
+        provenance,
+        diagnostics,
+        columns,
+    )
+}
+
+/// One run per skill when the scan covered several, each identified by the
+/// skill's name in `automationDetails.id`; a single run otherwise.
+pub fn format_sarif_per_skill(
+    findings: &[Finding],
+    skill_path: &Path,
+    skill_roots: &[PathBuf],
+    provenance: Option<&Provenance>,
+    diagnostics: &[Diagnostic],
+    columns: ColumnUnit,
+) -> String {
+    if skill_roots.len() < 2 {
+        return format_sarif(findings, skill_path, provenance, diagnostics, columns);
+    }
+    let target_name = skill_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| skill_path.display().to_string());
+
+    // A skill is named by its directory, or by its full path when two skill
+    // directories share a name.
+    let dir_name = |root: &PathBuf| root.file_name().map(|n| n.to_string_lossy().into_owned());
+    let run_name = |root: Option<&PathBuf>| match root {
+        Some(root) if !root.as_os_str().is_empty() => {
+            let name = dir_name(root);
+            let shared = skill_roots.iter().filter(|r| dir_name(r) == name).count() > 1;
+            match name {
+                Some(name) if !shared => name,
+                _ => report_path(root),
+            }
+        }
+        _ => target_name.clone(),
+    };
+
+    // Runs in skill-root order (innermost first), findings in report order.
+    let mut groups: Vec<(String, Vec<Finding>)> = Vec::new();
+    for f in findings {
+        let name = run_name(scanner::skill_root_for(&f.location.file, skill_roots));
+        match groups.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, group)) => group.push(f.clone()),
+            None => groups.push((name, vec![f.clone()])),
+        }
+    }
+    // Skills without findings still get a run, so consumers see them as clean.
+    for root in skill_roots {
+        let name = run_name(Some(root));
+        if !groups.iter().any(|(n, _)| *n == name) {
+            groups.push((name, Vec::new()));
+        }
+    }
+
+    let runs = groups
+        .into_iter()
+        .map(|(name, group)| {
+            sarif_run(
+                &group,
+                None,
+                None,
+                provenance,
+                diagnostics,
+                Some(name),
+                columns,
+            )
+        })
+        .collect();
+    sarif_log(runs)
+}
+
+/// SARIF for a report diff: each finding carries its `baselineState`. Saved
+/// JSON reports count columns in characters unless `--columns` said otherwise.
+pub fn format_sarif_baseline(findings: &[Finding], states: &[&'static str]) -> String {
+    sarif_log(vec![sarif_run(
+        findings,
+        Some(states),
+        None,
+        None,
+        &[],
+        None,
+        ColumnUnit::Char,
+    )])
 }
 
 pub fn format_sarif_with_rules(
     findings: &[Finding],
     _skill_path: &Path,
     registry: Option<&RuleRegistry>,
+    provenance: Option<&Provenance>,
+    diagnostics: &[Diagnostic],
+    columns: ColumnUnit,
 ) -> String {
+    sarif_log(vec![sarif_run(
+        findings,
+        None,
+        registry,
+        provenance,
+        diagnostics,
+        None,
+        columns,
+    )])
+}
+
+fn sarif_log(runs: Vec<SarifRun>) -> String {
+    let log = SarifLog {
+        schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/sarif-2.1/schema/sarif-schema-2.1.0.json",
+        version: "2.1.0",
+        runs,
+    };
+
+    serde_json::to_string_pretty(&log).unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
+}
+
+/// The run's invocation: when and where it ran, and any diagnostics. Left
+/// out when there is nothing to say.
+fn invocation(
+    provenance: Option<&Provenance>,
+    diagnostics: &[Diagnostic],
+) -> Option<SarifInvocation> {
+    let notifications: Vec<SarifNotification> = diagnostics
+        .iter()
+        .map(|d| SarifNotification {
+            level: "warning",
+            message: SarifMessage {
+                text: d.message.clone(),
+            },
+            descriptor: SarifNotificationDescriptor { id: d.code },
+        })
+        .collect();
+    if provenance.is_none() && notifications.is_empty() {
+        return None;
+    }
+    Some(SarifInvocation {
+        execution_successful: true,
+        tool_execution_notifications: notifications,
+        start_time_utc: provenance.and_then(|p| p.started_at.clone()),
+        machine: provenance.and_then(|p| p.hostname.clone()),
+    })
+}
+
+fn sarif_run<'a>(
+    findings: &[Finding],
+    baseline_states: Option<&[&'static str]>,
+    registry: Option<&RuleRegistry>,
+    provenance: Option<&'a Provenance>,
+    diagnostics: &[Diagnostic],
+    automation_id: Option<String>,
+    columns: ColumnUnit,
+) -> SarifRun<'a> {
     let rules: Vec<SarifRuleDescriptor> = if let Some(reg) = registry {
         reg.all_rules()
             .iter()
@@ -136,7 +370,8 @@ pub fn format_sarif_with_rules(
 
     let results: Vec<SarifResult> = findings
         .iter()
-        .map(|f| SarifResult {
+        .enumerate()
+        .map(|(i, f)| SarifResult {
             rule_id: f.rule_id.clone(),
             level: severity_to_level(f.severity).to_string(),
             message: SarifMessage {
@@ -145,7 +380,7 @@ pub fn format_sarif_with_rules(
             locations: vec![SarifLocation {
                 physical_location: SarifPhysicalLocation {
                     artifact_location: SarifArtifactLocation {
-                        uri: f.location.file.display().to_string(),
+                        uri: report_path(&f.location.file),
                     },
                     region: SarifRegion {
                         start_line: f.location.line,
@@ -153,23 +388,42 @@ pub fn format_sarif_with_rules(
                     },
                 },
             }],
+            partial_fingerprints: BTreeMap::from([("skillIssue/v1", f.fingerprint())]),
+            baseline_state: baseline_states.and_then(|states| states.get(i).copied()),
+            properties: (!f.tags.is_empty() || f.domain.is_some()).then(|| SarifResultProperties {
+                tags: f.tags.clone(),
+                domain: f.domain.clone(),
+            }),
         })
         .collect();
 
-    let log = SarifLog {
-        schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/sarif-2.1/schema/sarif-schema-2.1.0.json",
-        version: "2.1.0",
-        runs: vec![SarifRun {
-            tool: SarifTool {
-                driver: SarifDriver {
-                    name: "skill-issue",
-                    version: env!("CARGO_PKG_VERSION").to_string(),
-                    rules,
-                },
+    SarifRun {
+        tool: SarifTool {
+            driver: SarifDriver {
+                name: "skill-issue",
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                rules,
             },
-            results,
-        }],
-    };
-
-    serde_json::to_string_pretty(&log).unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
+        },
+        // SARIF has no byte convention; viewers then assume UTF-16.
+        column_kind: match columns {
+            ColumnUnit::Char => Some("unicodeCodePoints"),
+            ColumnUnit::Utf16 => Some("utf16CodeUnits"),
+            ColumnUnit::Byte => None,
+        },
+        invocations: invocation(provenance, diagnostics).into_iter().collect(),
+        version_control_provenance: provenance
+            .and_then(|p| {
+                Some(SarifVersionControl {
+                    repository_uri: p.repository.clone()?,
+                    revision_id: p.commit.clone()?,
+                    branch: p.branch.clone()?,
+                })
+            })
+            .into_iter()
+            .collect(),
+        results,
+        automation_details: automation_id.map(|id| SarifAutomationDetails { id }),
+        properties: provenance.map(|provenance| SarifRunProperties { provenance }),
+    }
 }
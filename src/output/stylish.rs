@@ -0,0 +1,171 @@
+use crate::finding::{report_path, Finding, Severity};
+use crate::scanner::ScannedFile;
+use colored::Colorize;
+use std::collections::BTreeMap;
+
+/// Lines of source shown above and below a finding's line.
+const FRAME_RADIUS: usize = 1;
+
+/// Findings grouped by file, each with a code frame: the lines around it and
+/// a caret under what matched. Findings in files not given (a saved report)
+/// get no frame.
+pub fn format_stylish(findings: &[Finding], files: &[ScannedFile]) -> String {
+    if findings.is_empty() {
+        return "No issues found.".green().to_string();
+    }
+    let mut by_file: BTreeMap<String, Vec<&Finding>> = BTreeMap::new();
+    for f in findings {
+        by_file
+            .entry(report_path(&f.location.file))
+            .or_default()
+            .push(f);
+    }
+
+    let mut out = String::new();
+    for (path, findings) in &by_file {
+        out.push_str(&format!("{}\n", path.underline()));
+        let content = files
+            .iter()
+            .find(|f| report_path(&f.relative_path) == *path)
+            .map(|f| f.content.as_str());
+        for f in findings {
+            let message: String = f
+                .message
+                .chars()
+                .map(|c| if c == '\n' || c == '\r' { ' ' } else { c })
+                .collect();
+            out.push_str(&format!(
+                "  {}  {}  {}  {}\n",
+                format!("{}:{}", f.location.line, f.location.column).dimmed(),
+                severity_label(f.severity),
+                message,
+                f.rule_id.dimmed()
+            ));
+            if let Some(content) = content {
+                out.push_str(&frame(content, f));
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str(&totals(findings));
+    out
+}
+
+fn severity_label(severity: Severity) -> String {
+    let label = format!("{:<8}", severity.to_string());
+    match severity {
+        Severity::Critical => label.red().bold().to_string(),
+        Severity::Error => label.red().to_string(),
+        Severity::Warning => label.yellow().to_string(),
+        Severity::Info => label.cyan().to_string(),
+    }
+}
+
+/// The numbered lines around a finding, its own marked with `>` and
+/// underlined from its column for as long as the matched text runs on that
+/// line.
+fn frame(content: &str, finding: &Finding) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let line = finding.location.line;
+    if line == 0 || line > lines.len() {
+        return String::new();
+    }
+    let first = line.saturating_sub(FRAME_RADIUS).max(1);
+    let last = (line + FRAME_RADIUS).min(lines.len());
+    let width = last.to_string().len();
+    let mut out = String::new();
+    for n in first..=last {
+        let marker = if n == line { ">" } else { " " };
+        out.push_str(&format!("    {marker} {n:>width$} | {}\n", lines[n - 1]));
+        if n != line {
+            continue;
+        }
+        let text = lines[n - 1];
+        let column = finding.location.column.max(1);
+        // Keep tabs so the caret lines up however the terminal shows them.
+        let indent: String = text
+            .chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let rest = text.chars().count().saturating_sub(column - 1);
+        let matched = finding
+            .matched_text
+            .lines()
+            .next()
+            .map_or(0, |m| m.chars().count());
+        let carets = "^".repeat(matched.min(rest).max(1));
+        out.push_str(&format!("      {:width$} | {indent}{}\n", "", carets.red()));
+    }
+    out
+}
+
+/// `3 problems (1 error, 2 warnings)`, naming only the severities present.
+fn totals(findings: &[Finding]) -> String {
+    let count = |s: Severity| findings.iter().filter(|f| f.severity == s).count();
+    let parts: Vec<String> = [
+        (Severity::Critical, "critical", "critical"),
+        (Severity::Error, "error", "errors"),
+        (Severity::Warning, "warning", "warnings"),
+        (Severity::Info, "info", "info"),
+    ]
+    .into_iter()
+    .filter_map(|(severity, one, many)| match count(severity) {
+        0 => None,
+        1 => Some(format!("1 {one}")),
+        n => Some(format!("{n} {many}")),
+    })
+    .collect();
+    let noun = if findings.len() == 1 {
+        "problem"
+    } else {
+        "problems"
+    };
+    let line = format!("{} {noun} ({})", findings.len(), parts.join(", "));
+    if findings.iter().any(|f| f.severity >= Severity::Error) {
+        line.red().bold().to_string()
+    } else {
+        line.yellow().bold().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::scanner::FileType;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_frames_each_finding_under_its_file() {
+        let file = ScannedFile {
+            path: PathBuf::from("skill/SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: "# Steps\nRun `curl https://x.example` now.\nDone.\n".to_string(),
+        };
+        let mut finding = Finding {
+            rule_name: "Curl Command".into(),
+            message: "Curl command detected".into(),
+            matched_text: "curl https://x.example".into(),
+            ..Finding::test("SL-NET-002", Severity::Warning, "SKILL.md", 2)
+        };
+        finding.location.column = 6;
+        let ansi = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let out = format_stylish(&[finding], &[file]);
+        assert_eq!(
+            ansi.replace_all(&out, ""),
+            [
+                "SKILL.md",
+                "  2:6  warning   Curl command detected  SL-NET-002",
+                "      1 | # Steps",
+                "    > 2 | Run `curl https://x.example` now.",
+                "        |      ^^^^^^^^^^^^^^^^^^^^^^",
+                "      3 | Done.",
+                "",
+                "1 problem (1 warning)",
+            ]
+            .join("\n")
+        );
+    }
+}
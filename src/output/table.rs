@@ -1,4 +1,6 @@
-use crate::finding::{Finding, Severity};
+use super::SkillFindings;
+use crate::finding::{report_path, Finding, Severity};
+use crate::scoring::{self, Grade, TrustScore};
 use colored::Colorize;
 use comfy_table::{
     modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Cell, Color as TableColor,
@@ -6,8 +8,10 @@ use comfy_table::{
 };
 
 pub fn format_table(findings: &[Finding]) -> String {
+    let trust = scoring::score(findings);
+
     if findings.is_empty() {
-        return format!("{}", "No issues found.".green());
+        return format!("{}\n{}", "No issues found.".green(), format_grade(&trust));
     }
 
     let mut table = Table::new();
@@ -19,6 +23,9 @@ pub fn format_table(findings: &[Finding]) -> String {
 
     for finding in findings {
         let severity_cell = match finding.severity {
+            Severity::Critical => Cell::new("CRITICAL")
+                .fg(TableColor::Red)
+                .add_attribute(comfy_table::Attribute::Bold),
             Severity::Error => Cell::new("ERROR").fg(TableColor::Red),
             Severity::Warning => Cell::new("WARN").fg(TableColor::Yellow),
             Severity::Info => Cell::new("INFO").fg(TableColor::Cyan),
@@ -27,7 +34,7 @@ pub fn format_table(findings: &[Finding]) -> String {
         table.add_row(vec![
             severity_cell,
             Cell::new(&finding.rule_id),
-            Cell::new(finding.location.file.display().to_string()),
+            Cell::new(report_path(&finding.location.file)),
             Cell::new(format!(
                 "{}:{}",
                 finding.location.line, finding.location.column
@@ -36,6 +43,10 @@ pub fn format_table(findings: &[Finding]) -> String {
         ]);
     }
 
+    let critical_count = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Critical)
+        .count();
     let error_count = findings
         .iter()
         .filter(|f| f.severity == Severity::Error)
@@ -49,15 +60,20 @@ pub fn format_table(findings: &[Finding]) -> String {
         .filter(|f| f.severity == Severity::Info)
         .count();
 
+    let critical = if critical_count > 0 {
+        format!("{critical_count} critical, ")
+    } else {
+        String::new()
+    };
     let summary = format!(
-        "\nFound {} issue(s): {} error(s), {} warning(s), {} info(s)",
+        "\nFound {} issue(s): {critical}{} error(s), {} warning(s), {} info(s)",
         findings.len(),
         error_count,
         warn_count,
         info_count
     );
 
-    let colored_summary = if error_count > 0 {
+    let colored_summary = if critical_count + error_count > 0 {
         summary.red().bold().to_string()
     } else if warn_count > 0 {
         summary.yellow().bold().to_string()
@@ -65,5 +81,90 @@ pub fn format_table(findings: &[Finding]) -> String {
         summary.cyan().to_string()
     };
 
-    format!("{table}\n{colored_summary}")
+    format!(
+        "{table}\n{colored_summary}\n{}\n{}",
+        format_grade(&trust),
+        format_categories(&trust)
+    )
+}
+
+/// `--batch` report: each skill's findings under a heading, then a pass/fail
+/// line per skill. A skill fails with a finding at or above `error_on`.
+pub fn format_batch_table(skills: &[SkillFindings], error_on: Severity) -> String {
+    let mut out = String::new();
+    for skill in skills {
+        out.push_str(&format!(
+            "{}\n{}\n\n",
+            report_path(&skill.path).bold(),
+            format_table(&skill.findings)
+        ));
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Skill", "Result", "Grade", "Findings"]);
+    let mut passed = 0;
+    for skill in skills {
+        let result = if skill.passed(error_on) {
+            passed += 1;
+            Cell::new("PASS").fg(TableColor::Green)
+        } else {
+            Cell::new("FAIL").fg(TableColor::Red)
+        };
+        table.add_row(vec![
+            Cell::new(report_path(&skill.path)),
+            result,
+            Cell::new(scoring::score(&skill.findings).grade.to_string()),
+            Cell::new(skill.findings.len()),
+        ]);
+    }
+    let summary = format!("{passed} of {} skill(s) passed", skills.len());
+    let summary = if passed == skills.len() {
+        summary.green().bold()
+    } else {
+        summary.red().bold()
+    };
+    out.push_str(&format!("{table}\n{summary}"));
+    out
+}
+
+fn format_grade(trust: &TrustScore) -> String {
+    let grade = trust.grade.to_string();
+    let grade = match trust.grade {
+        Grade::A | Grade::B => grade.green().bold(),
+        Grade::C => grade.yellow().bold(),
+        Grade::D | Grade::F => grade.red().bold(),
+    };
+    format!("Trust grade: {grade} (risk score {})", trust.risk_score)
+}
+
+fn format_categories(trust: &TrustScore) -> String {
+    let width = trust
+        .categories
+        .keys()
+        .map(|c| c.to_string().len())
+        .max()
+        .unwrap_or(0);
+    trust
+        .categories
+        .iter()
+        .map(|(category, counts)| {
+            let critical = if counts.critical > 0 {
+                format!("{} critical, ", counts.critical)
+            } else {
+                String::new()
+            };
+            format!(
+                "  {:<width$}  {critical}{} error(s), {} warning(s), {} info(s)",
+                category.to_string(),
+                counts.errors,
+                counts.warnings,
+                counts.info
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
@@ -0,0 +1,81 @@
+use crate::finding::{Finding, Severity};
+use std::path::Path;
+
+/// One finding per line: `path:line:col: severity RULE-ID message`.
+/// Paths are joined onto the scanned path so they resolve from the
+/// directory the scan was started in. VS Code has no critical level, so
+/// critical findings are reported as errors.
+///
+/// Lines match this problem matcher for `tasks.json`:
+///
+/// ```json
+/// "problemMatcher": {
+///   "owner": "skill-issue",
+///   "fileLocation": ["relative", "${workspaceFolder}"],
+///   "pattern": {
+///     "regexp": "^(.+?):(\\d+):(\\d+): (error|warning|info) (\\S+) (.*)$",
+///     "file": 1, "line": 2, "column": 3, "severity": 4, "code": 5, "message": 6
+///   }
+/// }
+/// ```
+pub fn format_vscode(findings: &[Finding], skill_path: &Path) -> String {
+    findings
+        .iter()
+        .map(|f| {
+            let message: String = f
+                .message
+                .chars()
+                .map(|c| if c == '\n' || c == '\r' { ' ' } else { c })
+                .collect();
+            let severity = match f.severity {
+                Severity::Critical => Severity::Error,
+                s => s,
+            };
+            format!(
+                "{}:{}:{}: {} {} {}",
+                skill_path.join(&f.location.file).display(),
+                f.location.line,
+                f.location.column,
+                severity,
+                f.rule_id,
+                message
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Severity;
+
+    const PROBLEM_MATCHER_REGEX: &str = r"^(.+?):(\d+):(\d+): (error|warning|info) (\S+) (.*)$";
+
+    #[test]
+    fn test_format_matches_problem_matcher() {
+        let mut finding = Finding {
+            rule_name: "Prompt Injection Pattern".into(),
+            message: "Prompt injection\nacross lines".into(),
+            matched_text: "ignore".into(),
+            ..Finding::test("SL-INJ-001", Severity::Error, "SKILL.md", 3)
+        };
+        finding.location.column = 7;
+        let findings = vec![finding];
+
+        let out = format_vscode(&findings, Path::new("my-skill"));
+        let re = regex::Regex::new(PROBLEM_MATCHER_REGEX).unwrap();
+        let caps = re
+            .captures(&out)
+            .expect("line should match the problem matcher");
+        assert_eq!(
+            &caps[1],
+            Path::new("my-skill").join("SKILL.md").to_str().unwrap()
+        );
+        assert_eq!(&caps[2], "3");
+        assert_eq!(&caps[3], "7");
+        assert_eq!(&caps[4], "error");
+        assert_eq!(&caps[5], "SL-INJ-001");
+        assert_eq!(&caps[6], "Prompt injection across lines");
+    }
+}
@@ -0,0 +1,470 @@
+use crate::baseline;
+use crate::config::{Config, ConfigFile, ScanMode};
+use crate::coverage;
+use crate::diagnostics::{self, Code, Diagnostic};
+use crate::duplicates;
+use crate::engine::Engine;
+use crate::finding::{report_path, Finding};
+use crate::freshness::{self, SkillAge};
+use crate::links;
+use crate::plugin;
+use crate::provenance::{Provenance, Revision};
+use crate::remote::{self, FailedSkill, RepoSignals};
+use crate::reputation;
+use crate::rerun::Rerun;
+use crate::rules::combining_rule::CombiningFloodRule;
+use crate::rules::schema_rule::FrontmatterSchemaRule;
+use crate::rules::RuleRegistry;
+use crate::scanner::{self, ScannedFile, SkipReason, SkippedFile};
+use crate::secrets;
+use crate::signature;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Result of running the full scan pipeline against one target.
+pub struct ScanOutcome {
+    pub files: Vec<ScannedFile>,
+    pub findings: Vec<Finding>,
+    pub display_path: PathBuf,
+    pub provenance: Provenance,
+    /// Why the target had nothing worth scanning, if so. A clean result for
+    /// such a target would be misleading.
+    pub empty: Option<String>,
+    /// `--fail-fast` skipped files after the first blocking finding.
+    pub stopped_early: bool,
+    /// Findings hidden by `--baseline`.
+    pub baselined: usize,
+    /// Warnings from this scan, in the order they arose; any entry means the
+    /// scan was degraded.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Files gathered for a scan target, before any rules run.
+#[derive(Clone)]
+pub struct Collected {
+    pub files: Vec<ScannedFile>,
+    pub skipped: Vec<SkippedFile>,
+    pub display_path: PathBuf,
+    pub revision: Option<Revision>,
+    /// When each remote skill last changed; filled only for an age policy.
+    pub skill_ages: Vec<SkillAge>,
+    /// Publisher signals; filled only for `--reputation` remote scans.
+    pub signals: Option<RepoSignals>,
+    /// Remote skills that could not be fetched.
+    pub failed: Vec<FailedSkill>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Read and parse a `.skill-issue.toml` file, warning (not failing) on errors.
+pub fn load_config_file(path: &Path) -> Option<ConfigFile> {
+    read_config_file(path).ok().flatten()
+}
+
+/// Like `load_config_file`, but returns the warning for a scan's report.
+pub fn read_config_file(path: &Path) -> Result<Option<ConfigFile>, Diagnostic> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    match std::fs::read_to_string(path) {
+        Ok(contents) => match toml::from_str::<ConfigFile>(&contents) {
+            Ok(cf) => Ok(Some(cf)),
+            Err(e) => Err(diagnostics::warn(
+                Code::ConfigInvalid,
+                format!("failed to parse config file: {e}"),
+            )),
+        },
+        Err(e) => Err(diagnostics::warn(
+            Code::ConfigUnreadable,
+            format!("failed to read config file: {e}"),
+        )),
+    }
+}
+
+/// Collect the files to analyze — either from a remote spec or a local directory.
+pub fn collect_files(config: &Config) -> Result<Collected, String> {
+    if let Some(ref spec) = config.remote {
+        if config.offline {
+            return Err("--remote needs network access; drop --offline".to_string());
+        }
+        if config.verbose {
+            eprintln!("Scanning remote: {spec}");
+        }
+
+        let skill = remote::fetch_remote_skill(
+            spec,
+            config.github_token.as_deref(),
+            &config.skill_markers,
+            config.verbose,
+        )
+        .map_err(|e| e.to_string())?;
+        let skill_ages = if config.min_age.is_some() || config.max_age.is_some() {
+            skill_ages(spec, config, &skill)?
+        } else {
+            Vec::new()
+        };
+        let signals = if config.reputation {
+            Some(
+                remote::repo_signals(spec, &skill, config.github_token.as_deref(), config.verbose)
+                    .map_err(|e| e.to_string())?,
+            )
+        } else {
+            None
+        };
+        Ok(Collected {
+            files: skill.files,
+            skipped: skill.skipped,
+            display_path: PathBuf::from(spec),
+            revision: Some(Revision {
+                repository: skill.repository,
+                branch: skill.branch,
+                commit: skill.commit,
+            }),
+            skill_ages,
+            signals,
+            failed: skill.failed,
+            diagnostics: skill.diagnostics,
+        })
+    } else {
+        if config.verbose {
+            eprintln!("Scanning: {}", config.path.display());
+        }
+        let mut diagnostics = Vec::new();
+        if config.min_age.is_some() || config.max_age.is_some() {
+            diagnostics.push(diagnostics::warn(
+                Code::OptionIgnored,
+                "--min-age and --max-age apply only to --remote scans",
+            ));
+        }
+        if config.reputation {
+            diagnostics.push(diagnostics::warn(
+                Code::OptionIgnored,
+                "--reputation applies only to --remote scans",
+            ));
+        }
+
+        let scan = match config.mode {
+            ScanMode::Skill => scanner::scan_directory(&config.path)?,
+            ScanMode::AgentFiles => scanner::scan_agent_files(&config.path)?,
+        };
+        Ok(Collected {
+            files: scan.files,
+            skipped: scan.skipped,
+            display_path: config.path.clone(),
+            revision: None,
+            skill_ages: Vec::new(),
+            signals: None,
+            failed: Vec::new(),
+            diagnostics,
+        })
+    }
+}
+
+/// Last-change dates for each skill in a remote scan, reported on the
+/// skill's marker file when it has one.
+fn skill_ages(
+    spec: &str,
+    config: &Config,
+    skill: &remote::RemoteSkill,
+) -> Result<Vec<SkillAge>, String> {
+    let dates = remote::last_changed(spec, skill, config.github_token.as_deref(), config.verbose)
+        .map_err(|e| e.to_string())?;
+    dates
+        .into_iter()
+        .map(|(root, date)| {
+            let committed_at = chrono::DateTime::parse_from_rfc3339(&date)
+                .map_err(|e| format!("invalid commit date {date:?}: {e}"))?
+                .to_utc();
+            let location = skill
+                .files
+                .iter()
+                .map(|f| &f.relative_path)
+                .find(|p| {
+                    p.parent() == Some(root.as_path())
+                        && scanner::is_skill_marker(p, &config.skill_markers)
+                })
+                .cloned()
+                .unwrap_or(root);
+            Ok(SkillAge {
+                location,
+                committed_at,
+            })
+        })
+        .collect()
+}
+
+/// Collect files, load the default rules, and run the engine.
+pub fn run(config: &Config) -> Result<ScanOutcome, String> {
+    let started_at = chrono::Utc::now();
+    let timer = Instant::now();
+    let collected = collect_files(config)?;
+    analyze(config, collected, started_at, timer)
+}
+
+/// Run the checks and rules over files already collected. `started_at` and
+/// `timer` date the scan from before collection.
+pub fn analyze(
+    config: &Config,
+    collected: Collected,
+    started_at: chrono::DateTime<chrono::Utc>,
+    timer: Instant,
+) -> Result<ScanOutcome, String> {
+    let Collected {
+        mut files,
+        mut skipped,
+        display_path,
+        revision,
+        skill_ages,
+        signals,
+        failed,
+        diagnostics: collected_diagnostics,
+    } = collected;
+    let mut diagnostics = config.diagnostics.clone();
+    diagnostics.extend(collected_diagnostics);
+
+    if config.verbose {
+        eprintln!("Found {} files to analyze", files.len());
+    }
+
+    let mut registry = build_registry(config)?;
+    diagnostics.extend(registry.diagnostics().iter().cloned());
+    let rerun = config.rerun_from.as_deref().map(Rerun::load).transpose()?;
+    if let Some(ref rerun) = rerun {
+        rerun.restrict_rules(&mut registry);
+    }
+    if config.verbose {
+        let below = registry
+            .all_rules()
+            .iter()
+            .filter(|r| !config.meets_min_rule_severity(r.id(), r.default_severity()))
+            .count();
+        eprintln!(
+            "Loaded {} rules ({below} below --min-rule-severity will not run)",
+            registry.all_rules().len()
+        );
+    }
+
+    let mut target_findings = Vec::new();
+    if config.mode == ScanMode::Skill
+        && (config.require_signature || !config.trusted_keys.is_empty())
+    {
+        target_findings.extend(signature::check(config, &files)?);
+    }
+    // Signature files are base64 blobs by design; keep them away from the rules.
+    files.retain(|f| !signature::is_signature_file(&f.relative_path));
+    if config.mode == ScanMode::Skill {
+        target_findings.extend(duplicates::check(&files, &config.skill_markers));
+        target_findings.extend(plugin::check(&files, &config.skill_markers));
+    }
+
+    target_findings.extend(freshness::check(
+        &skill_ages,
+        started_at,
+        config.min_age,
+        config.max_age,
+    ));
+    if let Some(ref signals) = signals {
+        // Repository-wide: reported on the outermost skill's marker file.
+        let location = scanner::skill_roots(&files, &config.skill_markers)
+            .last()
+            .and_then(|root| {
+                files.iter().map(|f| &f.relative_path).find(|p| {
+                    p.parent() == Some(root.as_path())
+                        && scanner::is_skill_marker(p, &config.skill_markers)
+                })
+            })
+            .cloned()
+            .unwrap_or_default();
+        target_findings.extend(reputation::check(signals, &location, started_at));
+    }
+    if config.check_links {
+        target_findings.extend(links::check(&files, config.verbose, &mut diagnostics));
+    }
+
+    let baseline = match config.baseline {
+        Some(ref path) if !config.update_baseline => baseline::load(path)?,
+        _ => HashSet::new(),
+    };
+    // Only now: the target-level checks above need the whole tree.
+    if let Some(ref rerun) = rerun {
+        rerun.restrict_files(&mut files);
+        skipped.retain(|s| rerun.files.contains(&s.relative_path));
+        if config.verbose {
+            eprintln!(
+                "Re-checking {} file(s) for {} rule(s)",
+                files.len(),
+                registry.all_rules().len()
+            );
+        }
+    }
+    let engine = Engine::new(config, &registry).with_baseline(baseline);
+    // A rerun may rightly have nothing left to check.
+    let empty = match rerun {
+        Some(_) => None,
+        None => empty_target(config, &files, &engine),
+    };
+    for s in &skipped {
+        if matches!(
+            s.reason,
+            SkipReason::TooLarge(_) | SkipReason::Unreadable(_)
+        ) {
+            let path = report_path(&s.relative_path);
+            diagnostics.push(Diagnostic::new(
+                Code::FileSkipped,
+                format!("{path} was not scanned ({})", s.reason),
+            ));
+        }
+    }
+    for f in &failed {
+        diagnostics.push(Diagnostic::new(
+            Code::SkillFetchFailed,
+            format!("{} could not be fetched: {}", report_path(&f.root), f.error),
+        ));
+    }
+    skipped.extend(coverage::unchecked(&files, |f| engine.has_active_rules(f)));
+    target_findings.extend(coverage::skipped_findings(&skipped));
+    target_findings.extend(coverage::failed_skill_findings(&failed));
+    let mut findings = engine.run(&files, target_findings);
+    if let Some(ref rerun) = rerun {
+        findings.retain(|f| rerun.covers(f));
+    }
+    if let (true, Some(path)) = (config.update_baseline, &config.baseline) {
+        // Before redaction, which would change the fingerprints.
+        let count = baseline::write(path, &findings)?;
+        if config.verbose {
+            eprintln!("Wrote {count} baseline entries to {}", path.display());
+        }
+    }
+    if config.verify_secrets {
+        secrets::verify(&mut findings, config.verbose);
+        findings.sort_by_key(|f| f.sort_key());
+    }
+    if config.redact {
+        findings.iter_mut().for_each(Finding::redact);
+    }
+    if config.deterministic {
+        // Findings at the same place otherwise keep rule and file-walk order.
+        findings.sort_by(|a, b| {
+            (a.sort_key(), &a.rule_id, &a.message, &a.matched_text).cmp(&(
+                b.sort_key(),
+                &b.rule_id,
+                &b.message,
+                &b.matched_text,
+            ))
+        });
+    }
+
+    let provenance = Provenance::new(
+        report_path(&display_path),
+        revision,
+        &registry,
+        started_at,
+        timer.elapsed(),
+    );
+    Ok(ScanOutcome {
+        files,
+        findings,
+        display_path,
+        provenance,
+        empty,
+        stopped_early: engine.stopped_early(),
+        baselined: engine.baselined(),
+        diagnostics,
+    })
+}
+
+/// Combine the outcomes of scanning several local paths into one report
+/// rooted at the working directory. Files and findings keep the path they
+/// were given under as a prefix, so each finding names its own skill.
+pub fn merge(targets: &[PathBuf], outcomes: Vec<ScanOutcome>) -> ScanOutcome {
+    let mut merged = ScanOutcome {
+        files: Vec::new(),
+        findings: Vec::new(),
+        display_path: PathBuf::from("."),
+        provenance: outcomes[0].provenance.clone(),
+        empty: None,
+        stopped_early: false,
+        baselined: 0,
+        diagnostics: Vec::new(),
+    };
+    merged.provenance.target = targets
+        .iter()
+        .map(|t| report_path(t))
+        .collect::<Vec<_>>()
+        .join(", ");
+    merged.provenance.duration_ms = outcomes
+        .iter()
+        .map(|o| o.provenance.duration_ms)
+        .sum::<Option<u64>>();
+    for (target, outcome) in targets.iter().zip(outcomes) {
+        // `./skills/a` and `skills/a` name the same prefix.
+        let prefix: PathBuf = target
+            .components()
+            .filter(|c| !matches!(c, std::path::Component::CurDir))
+            .collect();
+        merged.files.extend(outcome.files.into_iter().map(|mut f| {
+            f.relative_path = prefix.join(&f.relative_path);
+            f
+        }));
+        merged
+            .findings
+            .extend(outcome.findings.into_iter().map(|mut f| {
+                f.location.file = prefix.join(&f.location.file);
+                f
+            }));
+        merged.stopped_early |= outcome.stopped_early;
+        merged.baselined += outcome.baselined;
+        // Targets that share a config file or flags share their warnings.
+        for diagnostic in outcome.diagnostics {
+            if !merged.diagnostics.contains(&diagnostic) {
+                merged.diagnostics.push(diagnostic);
+            }
+        }
+    }
+    merged.findings.sort_by_key(|f| f.sort_key());
+    merged
+}
+
+/// The built-in rules configured for `config`, plus any from `--rules-dir`,
+/// with rule parameters applied.
+pub fn build_registry(config: &Config) -> Result<RuleRegistry, String> {
+    let mut registry = RuleRegistry::new();
+    registry.load_defaults();
+    registry.replace(Box::new(CombiningFloodRule::new(
+        config.max_combining_marks,
+    )));
+    registry.replace(Box::new(FrontmatterSchemaRule::new(config.skill_spec)));
+    if let Some(dir) = &config.rules_dir {
+        let loaded = registry.load_dir(dir)?;
+        if config.verbose {
+            eprintln!("Loaded {loaded} custom rule(s) from {}", dir.display());
+        }
+    }
+    registry.apply_params(&config.rule_params())?;
+    Ok(registry)
+}
+
+/// Files that make a directory a plugin, whose hooks are scanned, in addition
+/// to the skill markers.
+const PLUGIN_MARKERS: &[&str] = &["plugin.json", "hooks.json"];
+
+fn empty_target(config: &Config, files: &[ScannedFile], engine: &Engine) -> Option<String> {
+    if !files.iter().any(|f| engine.has_active_rules(f)) {
+        return Some(if files.is_empty() {
+            "no files found".to_string()
+        } else {
+            "no file is checked by any enabled rule".to_string()
+        });
+    }
+    let has_skill = files.iter().any(|f| {
+        scanner::is_skill_marker(&f.relative_path, &config.skill_markers)
+            || f.relative_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| PLUGIN_MARKERS.contains(&n))
+    });
+    if config.remote.is_none() && config.mode == ScanMode::Skill && !has_skill {
+        return Some(format!("no {} found", config.skill_markers.join(" or ")));
+    }
+    None
+}
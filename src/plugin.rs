@@ -0,0 +1,387 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::RuleDoc;
+use crate::scanner::{self, FileType, ScannedFile};
+use crate::signature::manifest_path;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+const MISSING_RULE_ID: &str = "SL-HOOK-003";
+const MISSING_RULE_NAME: &str = "Plugin Component Missing";
+const UNDECLARED_RULE_ID: &str = "SL-HOOK-004";
+const UNDECLARED_RULE_NAME: &str = "Undeclared Plugin Executable";
+
+pub const RULE_DOCS: &[RuleDoc] = &[
+    RuleDoc {
+        id: MISSING_RULE_ID,
+        name: MISSING_RULE_NAME,
+        severity: Severity::Warning,
+        applies_to: &[FileType::Json],
+        description: "A plugin's `plugin.json`, hook or MCP configuration points at a file that is not in the plugin. What runs there cannot be reviewed, and a missing hook script is a slot for something downloaded later.",
+        remediation: "Ship the referenced file with the plugin, or remove the reference.",
+    },
+    RuleDoc {
+        id: UNDECLARED_RULE_ID,
+        name: UNDECLARED_RULE_NAME,
+        severity: Severity::Warning,
+        applies_to: &[FileType::Script],
+        description: "A script in a plugin, or an extensionless file with a `#!` line or the executable bit, that no manifest, hook, MCP server, command or skill refers to. Nothing legitimate runs it, so it is either dead code or meant to be run some other way, such as by instructions fetched at runtime.",
+        remediation: "Reference the script from the component that runs it, or remove it from the plugin.",
+    },
+];
+
+/// Manifest keys whose string values are paths to components.
+const COMPONENT_KEYS: &[&str] = &["commands", "agents", "hooks", "mcpServers"];
+/// Hook and MCP configuration a plugin uses without declaring it.
+const DEFAULT_CONFIGS: &[&str] = &["hooks/hooks.json", ".mcp.json"];
+
+/// A file named relative to the plugin, as hook and MCP commands do.
+static PLUGIN_ROOT_PATH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\$\{?CLAUDE_PLUGIN_ROOT\}?/([^\s"'`;|&<>()]+)"#).unwrap());
+
+/// Cross-check each plugin in the target against its `plugin.json`: every
+/// component, hook script and MCP server it names must exist, and every
+/// script in it must be named by something.
+pub fn check(files: &[ScannedFile], markers: &[String]) -> Vec<Finding> {
+    let roots = scanner::skill_roots(files, markers);
+    let mut findings = Vec::new();
+    for manifest in files.iter().filter(|f| is_manifest(&f.relative_path)) {
+        let Ok(json) = serde_json::from_str::<Value>(&manifest.content) else {
+            continue;
+        };
+        let root = plugin_root(&manifest.relative_path);
+        let plugin: BTreeMap<String, &ScannedFile> = files
+            .iter()
+            .filter_map(|f| {
+                let relative = f.relative_path.strip_prefix(&root).ok()?;
+                Some((manifest_path(relative), f))
+            })
+            .collect();
+        let exists = |path: &str| {
+            plugin.contains_key(path) || plugin.keys().any(|k| k.starts_with(&format!("{path}/")))
+        };
+
+        let mut referenced = BTreeSet::new();
+        let mut configs = vec![manifest];
+        configs.extend(
+            DEFAULT_CONFIGS
+                .iter()
+                .filter_map(|p| plugin.get(*p).copied()),
+        );
+        for key in COMPONENT_KEYS {
+            for declared in component_paths(&json[key]) {
+                let path = normalize(declared);
+                if !exists(&path) {
+                    findings.push(missing(
+                        manifest,
+                        declared,
+                        format!(
+                            "plugin.json declares {key} at {declared}, which is not in the plugin"
+                        ),
+                    ));
+                } else if matches!(*key, "hooks" | "mcpServers") {
+                    configs.extend(plugin.get(&path).copied());
+                }
+                referenced.insert(path);
+            }
+        }
+        configs.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        configs.dedup_by(|a, b| a.relative_path == b.relative_path);
+
+        for config in configs {
+            for reference in plugin_root_paths(&config.content) {
+                if !exists(reference) {
+                    findings.push(missing(
+                        config,
+                        reference,
+                        format!(
+                            "Runs ${{CLAUDE_PLUGIN_ROOT}}/{reference}, which is not in the plugin"
+                        ),
+                    ));
+                }
+                referenced.insert(reference.to_string());
+            }
+        }
+        // Slash commands and agents run plugin scripts the same way.
+        for file in plugin
+            .values()
+            .filter(|f| f.file_type == FileType::Markdown)
+        {
+            referenced.extend(plugin_root_paths(&file.content).map(str::to_string));
+        }
+
+        findings.extend(undeclared(&plugin, &referenced, &roots));
+    }
+    findings
+}
+
+fn is_manifest(path: &Path) -> bool {
+    path.file_name().is_some_and(|n| n == "plugin.json")
+}
+
+/// The directory a manifest describes: the parent of `.claude-plugin/`, or
+/// the manifest's own directory for a bare `plugin.json`.
+fn plugin_root(manifest: &Path) -> PathBuf {
+    let dir = manifest.parent().unwrap_or(Path::new(""));
+    if dir.file_name().is_some_and(|n| n == ".claude-plugin") {
+        dir.parent().unwrap_or(Path::new("")).to_path_buf()
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+/// Paths in a component value: a string or an array of them. Inline hook or
+/// server definitions (objects) name no path.
+fn component_paths(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(s) => vec![s.as_str()],
+        Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn normalize(path: &str) -> String {
+    let path = path
+        .trim_start_matches("${CLAUDE_PLUGIN_ROOT}/")
+        .trim_start_matches("./");
+    path.trim_end_matches('/').to_string()
+}
+
+fn plugin_root_paths(content: &str) -> impl Iterator<Item = &str> {
+    PLUGIN_ROOT_PATH
+        .captures_iter(content)
+        .filter_map(|c| c.get(1))
+        .map(|m| m.as_str().trim_end_matches(['.', ',']))
+}
+
+/// Scripts nothing refers to. A script counts as referenced when a hook or
+/// MCP server runs it, when a referenced script next to it names it (a
+/// `require('./util')` or `./stage2.sh`), or when any other file in the
+/// plugin mentions its path, relative to the plugin or to its skill.
+fn undeclared(
+    plugin: &BTreeMap<String, &ScannedFile>,
+    referenced: &BTreeSet<String>,
+    roots: &[PathBuf],
+) -> Vec<Finding> {
+    let runnable: BTreeMap<&str, &ScannedFile> = plugin
+        .iter()
+        .filter(|(_, f)| is_runnable(f))
+        .map(|(path, f)| (path.as_str(), *f))
+        .collect();
+    let texts: Vec<&str> = plugin
+        .iter()
+        .filter(|(path, _)| !runnable.contains_key(path.as_str()))
+        .map(|(_, f)| f.content.as_str())
+        .collect();
+
+    let mentioned = |path: &str, file: &ScannedFile| {
+        let mut names = vec![path.to_string()];
+        if let Some(skill) = scanner::skill_root_for(&file.relative_path, roots) {
+            if let Ok(within) = file.relative_path.strip_prefix(skill) {
+                names.push(manifest_path(within));
+            }
+        }
+        texts
+            .iter()
+            .any(|t| names.iter().any(|n| t.contains(n.as_str())))
+    };
+
+    let mut reached: BTreeSet<&str> = runnable
+        .iter()
+        .filter(|(path, file)| referenced.contains(**path) || mentioned(path, file))
+        .map(|(path, _)| *path)
+        .collect();
+    loop {
+        let named: Vec<&str> = runnable
+            .iter()
+            .filter(|(path, file)| {
+                !reached.contains(*path)
+                    && reached.iter().any(|r| {
+                        parent(r) == parent(path) && names_sibling(&runnable[r].content, file)
+                    })
+            })
+            .map(|(path, _)| *path)
+            .collect();
+        if named.is_empty() {
+            break;
+        }
+        reached.extend(named);
+    }
+
+    let mut findings = Vec::new();
+    for (path, file) in runnable {
+        if reached.contains(path) {
+            continue;
+        }
+        findings.push(Finding {
+            rule_id: UNDECLARED_RULE_ID.to_string(),
+            rule_name: UNDECLARED_RULE_NAME.to_string(),
+            severity: Severity::Warning,
+            message: format!(
+                "Script {path} is not referenced by the plugin manifest, its hooks, MCP servers, commands or skills"
+            ),
+            location: Location {
+                file: file.relative_path.clone(),
+                line: 1,
+                column: 1,
+            },
+            matched_text: String::new(),
+            confidence: Confidence::Medium,
+            tags: Vec::new(),
+            domain: None,
+        });
+    }
+    findings
+}
+
+/// A file the plugin could run: a script by extension, or a file of no
+/// known type with a `#!` line or the executable bit. Task runner files
+/// (Makefile, justfile) are not run by the plugin.
+fn is_runnable(file: &ScannedFile) -> bool {
+    match file.file_type {
+        FileType::Script => file.relative_path.extension().is_some(),
+        FileType::Unknown => file.content.starts_with("#!") || is_executable(&file.path),
+        _ => false,
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_: &Path) -> bool {
+    false
+}
+
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// Whether a script's content names a file next to it, by file name or as a
+/// `./stem` module path.
+fn names_sibling(content: &str, sibling: &ScannedFile) -> bool {
+    let path = &sibling.relative_path;
+    let name = path.file_name().map(|n| n.to_string_lossy());
+    let stem = path
+        .file_stem()
+        .map(|s| format!("./{}", s.to_string_lossy()));
+    name.is_some_and(|n| content.contains(n.as_ref())) || stem.is_some_and(|s| content.contains(&s))
+}
+
+fn missing(file: &ScannedFile, reference: &str, message: String) -> Finding {
+    let (line, column) = match file.content.find(reference) {
+        Some(offset) => {
+            let before = &file.content[..offset];
+            let line_start = before.rfind('\n').map_or(0, |p| p + 1);
+            (before.matches('\n').count() + 1, offset - line_start + 1)
+        }
+        None => (1, 1),
+    };
+    Finding {
+        rule_id: MISSING_RULE_ID.to_string(),
+        rule_name: MISSING_RULE_NAME.to_string(),
+        severity: Severity::Warning,
+        message,
+        location: Location {
+            file: file.relative_path.clone(),
+            line,
+            column,
+        },
+        matched_text: reference.to_string(),
+        confidence: Confidence::High,
+        tags: Vec::new(),
+        domain: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, content: &str) -> ScannedFile {
+        ScannedFile {
+            path: PathBuf::from(path),
+            relative_path: PathBuf::from(path),
+            file_type: FileType::from_path(Path::new(path)),
+            content: content.to_string(),
+        }
+    }
+
+    fn ids(findings: &[Finding]) -> Vec<(&str, String)> {
+        findings
+            .iter()
+            .map(|f| (f.rule_id.as_str(), manifest_path(&f.location.file)))
+            .collect()
+    }
+
+    #[test]
+    fn test_reports_missing_components_and_hook_scripts() {
+        let files = [
+            file(
+                "fmt/.claude-plugin/plugin.json",
+                r#"{"name": "fmt", "commands": ["./commands/", "./extra/deploy.md"], "hooks": "./hooks/hooks.json"}"#,
+            ),
+            file("fmt/commands/format.md", "# Format\n"),
+            file(
+                "fmt/hooks/hooks.json",
+                r#"{"hooks": {"PostToolUse": [{"hooks": [
+                    {"type": "command", "command": "${CLAUDE_PLUGIN_ROOT}/scripts/format.sh"},
+                    {"type": "command", "command": "bash ${CLAUDE_PLUGIN_ROOT}/scripts/gone.sh"}
+                ]}]}}"#,
+            ),
+            file("fmt/scripts/format.sh", "prettier --write .\n"),
+        ];
+        let findings = check(&files, &["SKILL.md".to_string()]);
+        assert_eq!(
+            ids(&findings),
+            [
+                (
+                    MISSING_RULE_ID,
+                    "fmt/.claude-plugin/plugin.json".to_string()
+                ),
+                (MISSING_RULE_ID, "fmt/hooks/hooks.json".to_string()),
+            ]
+        );
+        assert_eq!(findings[0].matched_text, "./extra/deploy.md");
+        assert_eq!(findings[1].matched_text, "scripts/gone.sh");
+        assert_eq!(findings[1].location.line, 3);
+    }
+
+    #[test]
+    fn test_reports_scripts_nothing_refers_to() {
+        let files = [
+            file(
+                ".claude-plugin/plugin.json",
+                r#"{"name": "p", "mcpServers": {"db": {"command": "node", "args": ["${CLAUDE_PLUGIN_ROOT}/server/index.js"]}}}"#,
+            ),
+            file("server/index.js", "require('./util')\n"),
+            file("server/util.js", "module.exports = {}\n"),
+            file(
+                "skills/pdf/SKILL.md",
+                "Run `scripts/extract.py` on the file.\n",
+            ),
+            file("skills/pdf/scripts/extract.py", "print(1)\n"),
+            file("commands/sync.md", "!`${CLAUDE_PLUGIN_ROOT}/bin/sync.sh`\n"),
+            file("bin/sync.sh", "git pull\n"),
+            file("tools/beacon.sh", "curl https://example.com\n"),
+            file("bin/payload", "#!/bin/sh\ncurl https://example.com\n"),
+            file("bin/README", "Helpers.\n"),
+            file("bin/stage2.sh", "curl https://example.com\n"),
+            file("Makefile", "all:\n\ttrue\n"),
+        ];
+        let findings = check(&files, &["SKILL.md".to_string()]);
+        assert_eq!(
+            ids(&findings),
+            [
+                (UNDECLARED_RULE_ID, "bin/payload".to_string()),
+                (UNDECLARED_RULE_ID, "bin/stage2.sh".to_string()),
+                (UNDECLARED_RULE_ID, "tools/beacon.sh".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::rules::{self, RuleRegistry};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Where and how a report was produced, so it can be audited or reproduced
+/// later.
+#[derive(Debug, Clone, Serialize)]
+pub struct Provenance {
+    pub tool: &'static str,
+    pub tool_version: &'static str,
+    pub ruleset_version: String,
+    pub pattern_versions: BTreeMap<String, String>,
+    /// Digests of the `--rules-dir` pattern files, which `ruleset_version`
+    /// also covers.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_pattern_versions: BTreeMap<String, String>,
+    pub target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+}
+
+/// The revision a remote target was read at.
+#[derive(Debug, Clone)]
+pub struct Revision {
+    pub repository: String,
+    pub branch: String,
+    pub commit: String,
+}
+
+impl Provenance {
+    pub fn new(
+        target: String,
+        revision: Option<Revision>,
+        registry: &RuleRegistry,
+        started_at: chrono::DateTime<chrono::Utc>,
+        duration: Duration,
+    ) -> Self {
+        let (repository, branch, commit) = match revision {
+            Some(r) => (Some(r.repository), Some(r.branch), Some(r.commit)),
+            None => (None, None, None),
+        };
+        Provenance {
+            tool: "skill-issue",
+            tool_version: env!("CARGO_PKG_VERSION"),
+            ruleset_version: registry.ruleset_version(),
+            pattern_versions: rules::pattern_versions(),
+            custom_pattern_versions: registry.custom_pattern_versions().clone(),
+            target,
+            repository,
+            branch,
+            commit,
+            started_at: Some(started_at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+            duration_ms: Some(duration.as_millis() as u64),
+            hostname: hostname(),
+        }
+    }
+
+    /// The same provenance without the details of this particular run (start
+    /// time, duration, host), which differ between otherwise identical scans.
+    pub fn reproducible(&self) -> Provenance {
+        Provenance {
+            started_at: None,
+            duration_ms: None,
+            hostname: None,
+            ..self.clone()
+        }
+    }
+}
+
+fn hostname() -> Option<String> {
+    let from_file = ["/proc/sys/kernel/hostname", "/etc/hostname"]
+        .iter()
+        .find_map(|p| std::fs::read_to_string(p).ok());
+    from_file
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_provenance_omits_revision() {
+        let p = Provenance::new(
+            "./skill".to_string(),
+            None,
+            &RuleRegistry::new(),
+            chrono::Utc::now(),
+            Duration::from_millis(42),
+        );
+        let json = serde_json::to_value(&p).unwrap();
+        assert_eq!(json["duration_ms"], 42);
+        assert!(json.get("commit").is_none());
+        assert!(json["pattern_versions"]["injection"].is_string());
+        assert!(json.get("custom_pattern_versions").is_none());
+        assert!(p.ruleset_version.starts_with(p.tool_version));
+    }
+
+    #[test]
+    fn test_remote_provenance_records_revision() {
+        let p = Provenance::new(
+            "owner/repo".to_string(),
+            Some(Revision {
+                repository: "https://github.com/owner/repo".to_string(),
+                branch: "main".to_string(),
+                commit: "0123abcd".to_string(),
+            }),
+            &RuleRegistry::new(),
+            chrono::Utc::now(),
+            Duration::ZERO,
+        );
+        let json = serde_json::to_value(&p).unwrap();
+        assert_eq!(json["branch"], "main");
+        assert_eq!(json["commit"], "0123abcd");
+
+        let json = serde_json::to_value(p.reproducible()).unwrap();
+        assert!(json.get("started_at").is_none());
+        assert!(json.get("duration_ms").is_none());
+        assert!(json.get("hostname").is_none());
+        assert_eq!(json["commit"], "0123abcd");
+    }
+}
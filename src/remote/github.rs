@@ -1,7 +1,13 @@
-use crate::remote::{RemoteError, RemoteTarget};
-use crate::scanner::{FileType, ScannedFile};
+use crate::diagnostics::{self, Code, Diagnostic};
+use crate::finding::report_path;
+use crate::remote::http;
+use crate::remote::{
+    FailedSkill, ForkSignals, QueueItem, RemoteError, RemoteSkill, RemoteSkillDir, RemoteTarget,
+    RepoSignals,
+};
+use crate::scanner::{self, FileType, ScannedFile, SkippedFile};
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const USER_AGENT: &str = concat!("skill-issue/", env!("CARGO_PKG_VERSION"));
 
@@ -32,23 +38,29 @@ struct DiscoveredSkill {
 pub fn fetch_skill_files(
     target: &RemoteTarget,
     token: Option<&str>,
+    markers: &[String],
     verbose: bool,
-) -> Result<Vec<ScannedFile>, RemoteError> {
+) -> Result<RemoteSkill, RemoteError> {
     // Determine the branch — use specified or default
     let branch = match &target.branch {
         Some(b) => b.clone(),
         None => detect_default_branch(target, token, verbose)?,
     };
 
+    // Pin the branch to a commit so every file comes from the same revision
+    let commit = resolve_commit(target, &branch, token, verbose)?;
+
     if verbose {
-        eprintln!("Using branch: {branch}");
+        eprintln!("Using branch: {branch} ({commit})");
     }
 
     // Fetch recursive tree
-    let tree = fetch_tree(target, &branch, token, verbose)?;
+    let tree = fetch_tree(target, &commit, token, verbose)?;
 
-    // Discover skills
-    let skills = discover_skills(&tree, target)?;
+    // Discover skills, from index files and SKILL.md markers
+    let mut diagnostics = Vec::new();
+    let indexed = index_skills(target, &commit, &tree, token, verbose, &mut diagnostics)?;
+    let skills = discover_skills(&tree, target, markers, indexed)?;
 
     if verbose {
         eprintln!("Found {} skill(s)", skills.len());
@@ -58,45 +70,165 @@ pub fn fetch_skill_files(
     }
 
     // Collect all file entries belonging to the discovered skills
-    let mut files = Vec::new();
-    for skill in &skills {
-        let skill_entries: Vec<&TreeEntry> = tree
-            .iter()
-            .filter(|e| e.entry_type == "blob" && e.path.starts_with(&skill.prefix))
-            .collect();
+    // With several skills, paths stay relative to the repository root so
+    // files (and findings) from different skills remain distinguishable.
+    let keep_prefix = skills.len() > 1;
+    let mut fetched = std::collections::HashSet::new();
+    let work: Vec<(&DiscoveredSkill, Vec<&TreeEntry>)> = skills
+        .iter()
+        .map(|skill| {
+            let entries = tree
+                .iter()
+                .filter(|e| e.entry_type == "blob" && e.path.starts_with(&skill.prefix))
+                .filter(|e| fetched.insert(e.path.clone()))
+                .collect();
+            (skill, entries)
+        })
+        .collect();
 
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+    let mut fetched_skills = Vec::new();
+    let mut failed = Vec::new();
+    let mut first_error = None;
+    let results = fetch_concurrently(&work, |(skill, entries)| {
         if verbose {
             eprintln!(
                 "Fetching {} files for skill '{}'",
-                skill_entries.len(),
+                entries.len(),
                 skill.name
             );
         }
-
-        for entry in skill_entries {
-            let content = fetch_file_content(target, &branch, &entry.path, token)?;
-
-            // Relative path within the skill directory
-            let relative = entry
-                .path
-                .strip_prefix(&skill.prefix)
-                .unwrap_or(&entry.path);
-            let relative_path = PathBuf::from(relative);
-
-            files.push(ScannedFile {
-                path: PathBuf::from(&entry.path),
-                relative_path: relative_path.clone(),
-                file_type: FileType::from_path(&relative_path),
-                content,
-            });
+        fetch_skill(target, &commit, skill, entries, keep_prefix, token)
+    });
+    for ((skill, _), result) in work.iter().zip(results) {
+        let root = PathBuf::from(if keep_prefix {
+            skill.prefix.trim_end_matches('/')
+        } else {
+            ""
+        });
+        match result {
+            Ok((skill_files, skill_skipped)) => {
+                files.extend(skill_files);
+                skipped.extend(skill_skipped);
+                fetched_skills.push(RemoteSkillDir {
+                    prefix: skill.prefix.clone(),
+                    root,
+                });
+            }
+            Err(e) => {
+                if verbose {
+                    eprintln!("Failed to fetch skill '{}': {e}", skill.name);
+                }
+                failed.push(FailedSkill {
+                    root,
+                    error: e.to_string(),
+                });
+                first_error.get_or_insert(e);
+            }
         }
     }
 
+    // Nothing to scan: report why rather than a finding per skill.
+    if fetched_skills.is_empty() {
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+    }
     if files.is_empty() {
         return Err(RemoteError::NoSkillsFound);
     }
 
-    Ok(files)
+    scanner::add_package_script_views(&mut files);
+
+    Ok(RemoteSkill {
+        files,
+        skipped,
+        repository: format!("https://github.com/{}/{}", target.owner, target.repo),
+        branch,
+        commit,
+        skills: fetched_skills,
+        failed,
+        diagnostics,
+    })
+}
+
+/// Skills fetched at once. Each one is a stream of small requests, so a few
+/// in flight hide most of the latency without tripping secondary limits.
+const FETCH_WORKERS: usize = 4;
+
+/// `fetch` for every item on up to `FETCH_WORKERS` threads, with results in
+/// item order.
+fn fetch_concurrently<I: Sync, T: Send>(items: &[I], fetch: impl Fn(&I) -> T + Sync) -> Vec<T> {
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let mut results: Vec<(usize, T)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..FETCH_WORKERS.min(items.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            return done;
+                        };
+                        done.push((i, fetch(item)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Files of one skill, and those that are not text. The first failed request
+/// fails the skill.
+fn fetch_skill(
+    target: &RemoteTarget,
+    commit: &str,
+    skill: &DiscoveredSkill,
+    entries: &[&TreeEntry],
+    keep_prefix: bool,
+    token: Option<&str>,
+) -> Result<(Vec<ScannedFile>, Vec<SkippedFile>), RemoteError> {
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in entries {
+        let bytes = fetch_file_content(target, commit, &entry.path, token)?;
+
+        // Relative path within the skill directory
+        let relative = if keep_prefix {
+            &entry.path
+        } else {
+            entry
+                .path
+                .strip_prefix(&skill.prefix)
+                .unwrap_or(&entry.path)
+        };
+        let relative_path = PathBuf::from(relative);
+        let content = match scanner::decode_text(bytes) {
+            Ok(content) => content,
+            Err(reason) => {
+                skipped.push(SkippedFile {
+                    relative_path,
+                    reason,
+                });
+                continue;
+            }
+        };
+
+        files.push(ScannedFile {
+            path: PathBuf::from(&entry.path),
+            relative_path: relative_path.clone(),
+            file_type: FileType::from_path(&relative_path),
+            content,
+        });
+    }
+    Ok((files, skipped))
 }
 
 /// Detect the default branch of a repo via the GitHub API.
@@ -126,16 +258,292 @@ fn detect_default_branch(
         .ok_or_else(|| RemoteError::HttpError("could not determine default branch".to_string()))
 }
 
-/// Fetch the recursive tree for a branch.
-fn fetch_tree(
+/// Resolve a branch (or tag, or SHA) to the commit SHA it currently points at.
+fn resolve_commit(
     target: &RemoteTarget,
     branch: &str,
     token: Option<&str>,
     verbose: bool,
+) -> Result<String, RemoteError> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/commits/{}",
+        target.owner,
+        target.repo,
+        api_ref(branch)
+    );
+
+    if verbose {
+        eprintln!("Resolving commit: {url}");
+    }
+
+    // GitHub answers 404 or 422 for a ref it does not know.
+    let mut resp = match make_request(&url, token) {
+        Err(RemoteError::RepoNotFound(_)) => return Err(ref_not_found(target, branch, token)),
+        Err(RemoteError::HttpError(e)) if e.contains("422") => {
+            return Err(ref_not_found(target, branch, token))
+        }
+        other => other?,
+    };
+    let body: serde_json::Value = resp
+        .body_mut()
+        .read_json()
+        .map_err(|e| RemoteError::HttpError(e.to_string()))?;
+
+    body["sha"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| RemoteError::HttpError(format!("could not resolve commit for {branch}")))
+}
+
+/// The commits API takes `heads/NAME` and `tags/NAME` for explicit refs.
+fn api_ref(branch: &str) -> &str {
+    branch.strip_prefix("refs/").unwrap_or(branch)
+}
+
+/// Explain a missing ref by listing the repository's branches and tags. If
+/// they cannot be listed either, the repository itself is the problem.
+fn ref_not_found(target: &RemoteTarget, branch: &str, token: Option<&str>) -> RemoteError {
+    let list = |kind: &str| -> Result<Vec<String>, RemoteError> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/{kind}?per_page=100",
+            target.owner, target.repo
+        );
+        let body: Vec<serde_json::Value> = make_request(&url, token)?
+            .body_mut()
+            .read_json()
+            .map_err(|e| RemoteError::HttpError(e.to_string()))?;
+        Ok(body
+            .iter()
+            .filter_map(|r| r["name"].as_str().map(str::to_string))
+            .collect())
+    };
+    let (branches, tags) = match list("branches").and_then(|b| Ok((b, list("tags")?))) {
+        Ok(refs) => refs,
+        Err(_) => return RemoteError::RepoNotFound(target.display()),
+    };
+    let wanted = branch
+        .strip_prefix("refs/heads/")
+        .or_else(|| branch.strip_prefix("refs/tags/"))
+        .unwrap_or(branch);
+    let available: Vec<String> = branches.into_iter().chain(tags).collect();
+    RemoteError::RefNotFound {
+        name: branch.to_string(),
+        suggestion: closest(wanted, &available).map(str::to_string),
+        available,
+    }
+}
+
+/// The candidate nearest to `wanted` by edit distance, if it is near enough to
+/// be a plausible typo.
+fn closest<'a>(wanted: &str, candidates: &'a [String]) -> Option<&'a str> {
+    let limit = (wanted.chars().count() / 3).max(2);
+    candidates
+        .iter()
+        .map(|c| (edit_distance(&wanted.to_lowercase(), &c.to_lowercase()), c))
+        .filter(|(d, _)| *d <= limit)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c.as_str())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitute = prev + usize::from(ca != cb);
+            prev = row[j + 1];
+            row[j + 1] = substitute.min(prev + 1).min(row[j] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Repository, owner and (for forks) divergence signals. The fork comparison
+/// runs against the scanned commit, so it matches what was scanned.
+pub fn repo_signals(
+    target: &RemoteTarget,
+    commit: &str,
+    token: Option<&str>,
+    verbose: bool,
+) -> Result<RepoSignals, RemoteError> {
+    let get = |url: String| -> Result<serde_json::Value, RemoteError> {
+        if verbose {
+            eprintln!("Fetching reputation signals: {url}");
+        }
+        make_request(&url, token)?
+            .body_mut()
+            .read_json()
+            .map_err(|e| RemoteError::HttpError(e.to_string()))
+    };
+
+    let repo = get(format!(
+        "https://api.github.com/repos/{}/{}",
+        target.owner, target.repo
+    ))?;
+    let owner = repo["owner"]["login"]
+        .as_str()
+        .unwrap_or(&target.owner)
+        .to_string();
+    let owner_created_at = get(format!("https://api.github.com/users/{owner}"))
+        .ok()
+        .and_then(|user| user["created_at"].as_str().map(str::to_string));
+
+    let parent = &repo["parent"];
+    let fork = match parent["full_name"].as_str() {
+        Some(parent_name) if repo["fork"].as_bool() == Some(true) => {
+            let compare = get(format!(
+                "https://api.github.com/repos/{parent_name}/compare/{}...{commit}",
+                parent["default_branch"].as_str().unwrap_or("main")
+            ))?;
+            Some(ForkSignals {
+                parent: parent_name.to_string(),
+                parent_stars: parent["stargazers_count"].as_u64().unwrap_or(0),
+                ahead_by: compare["ahead_by"].as_u64().unwrap_or(0),
+                behind_by: compare["behind_by"].as_u64().unwrap_or(0),
+            })
+        }
+        _ => None,
+    };
+
+    Ok(RepoSignals {
+        repository: format!("{}/{}", target.owner, target.repo),
+        stars: repo["stargazers_count"].as_u64().unwrap_or(0),
+        created_at: repo["created_at"].as_str().unwrap_or_default().to_string(),
+        owner,
+        owner_created_at,
+        fork,
+    })
+}
+
+/// Pages of 100 fetched at most when listing an account's repositories.
+const MAX_REPO_PAGES: usize = 10;
+
+/// Repositories of `owner` (an organization or user) pushed at or after
+/// `since`, then open pull requests against them updated since then.
+/// Timestamps compare as strings: GitHub returns `YYYY-MM-DDTHH:MM:SSZ`.
+pub fn updated_since(
+    owner: &str,
+    since: &str,
+    include_prs: bool,
+    token: Option<&str>,
+    verbose: bool,
+) -> Result<Vec<QueueItem>, RemoteError> {
+    let get = |url: &str| -> Result<serde_json::Value, RemoteError> {
+        if verbose {
+            eprintln!("Fetching: {url}");
+        }
+        make_request(url, token)?
+            .body_mut()
+            .read_json()
+            .map_err(|e| RemoteError::HttpError(e.to_string()))
+    };
+
+    let mut items = Vec::new();
+    let mut account = "orgs";
+    'pages: for page in 1..=MAX_REPO_PAGES {
+        let url = format!(
+            "https://api.github.com/{account}/{owner}/repos?sort=pushed&direction=desc&per_page=100&page={page}"
+        );
+        let repos = match get(&url) {
+            // Not an organization: list the user's repositories instead.
+            Err(RemoteError::RepoNotFound(_)) if account == "orgs" => {
+                account = "users";
+                get(&url.replace("/orgs/", "/users/"))?
+            }
+            other => other?,
+        };
+        let Some(repos) = repos.as_array().filter(|r| !r.is_empty()) else {
+            break;
+        };
+        for repo in repos {
+            let pushed_at = repo["pushed_at"].as_str().unwrap_or_default();
+            if pushed_at < since {
+                break 'pages; // sorted newest first
+            }
+            let Some(name) = repo["full_name"].as_str() else {
+                continue;
+            };
+            if repo["archived"].as_bool() == Some(true) {
+                continue;
+            }
+            items.push(QueueItem {
+                spec: name.to_string(),
+                label: name.to_string(),
+                updated_at: pushed_at.to_string(),
+            });
+        }
+    }
+
+    if include_prs {
+        let qualifier = if account == "orgs" { "org" } else { "user" };
+        let url = format!(
+            "https://api.github.com/search/issues?q={qualifier}:{owner}+is:pr+is:open+updated:%3E%3D{since}&per_page=100"
+        );
+        let results = get(&url)?;
+        for issue in results["items"].as_array().into_iter().flatten() {
+            let Some(pr_url) = issue["pull_request"]["url"].as_str() else {
+                continue;
+            };
+            let pr = get(pr_url)?;
+            let (Some(head_repo), Some(sha), Some(base)) = (
+                pr["head"]["repo"]["full_name"].as_str(),
+                pr["head"]["sha"].as_str(),
+                pr["base"]["repo"]["full_name"].as_str(),
+            ) else {
+                continue; // head repository was deleted
+            };
+            items.push(QueueItem {
+                spec: format!("{head_repo}:{sha}"),
+                label: format!("{base}#{}", pr["number"]),
+                updated_at: issue["updated_at"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+    }
+    Ok(items)
+}
+
+/// Committer date of the latest commit touching `prefix`, up to `commit`.
+pub fn last_commit_date(
+    target: &RemoteTarget,
+    commit: &str,
+    prefix: &str,
+    token: Option<&str>,
+    verbose: bool,
+) -> Result<String, RemoteError> {
+    let mut url = format!(
+        "https://api.github.com/repos/{}/{}/commits?sha={commit}&per_page=1",
+        target.owner, target.repo
+    );
+    if !prefix.is_empty() {
+        url.push_str(&format!("&path={}", prefix.trim_end_matches('/')));
+    }
+
+    if verbose {
+        eprintln!("Fetching last commit: {url}");
+    }
+
+    let body: serde_json::Value = make_request(&url, token)?
+        .body_mut()
+        .read_json()
+        .map_err(|e| RemoteError::HttpError(e.to_string()))?;
+    body[0]["commit"]["committer"]["date"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| RemoteError::HttpError(format!("no commit history for {prefix}")))
+}
+
+/// Fetch the recursive tree for a commit.
+fn fetch_tree(
+    target: &RemoteTarget,
+    commit: &str,
+    token: Option<&str>,
+    verbose: bool,
 ) -> Result<Vec<TreeEntry>, RemoteError> {
     let url = format!(
         "https://api.github.com/repos/{}/{}/git/trees/{}?recursive=1",
-        target.owner, target.repo, branch
+        target.owner, target.repo, commit
     );
 
     if verbose {
@@ -155,10 +563,167 @@ fn fetch_tree(
     Ok(tree_resp.tree)
 }
 
-/// Discover skills by finding SKILL.md files in the tree.
+/// One reported file at `commit`: its path is relative to the repository
+/// root, or to the skill's directory when the scan found a single skill.
+/// Only the tree and that file are fetched.
+pub fn fetch_reported_file(
+    target: &RemoteTarget,
+    commit: &str,
+    file: &Path,
+    token: Option<&str>,
+    markers: &[String],
+    verbose: bool,
+) -> Result<String, RemoteError> {
+    let tree = fetch_tree(target, commit, token, verbose)?;
+    let path = reported_path(&tree, target, markers, &report_path(file))
+        .ok_or_else(|| RemoteError::FileNotFound(report_path(file)))?;
+    let bytes = fetch_file_content(target, commit, &path, token)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// The repository path of a reported `file`: under the skill's directory
+/// first when the tree has a single skill, else as given.
+fn reported_path(
+    tree: &[TreeEntry],
+    target: &RemoteTarget,
+    markers: &[String],
+    file: &str,
+) -> Option<String> {
+    let prefixes: Vec<String> = discover_skills(tree, target, markers, Vec::new())
+        .map(|skills| skills.into_iter().map(|s| s.prefix).collect())
+        .unwrap_or_default();
+    let mut candidates = vec![file.to_string()];
+    let prefixed = prefixes.iter().map(|p| format!("{p}{file}"));
+    if prefixes.len() == 1 {
+        candidates.splice(0..0, prefixed);
+    } else {
+        candidates.extend(prefixed);
+    }
+    candidates
+        .into_iter()
+        .find(|p| tree.iter().any(|e| e.entry_type == "blob" && e.path == *p))
+}
+
+/// Index files that list a repository's skills, checked at these paths.
+const INDEX_FILES: &[&str] = &[
+    "skills.json",
+    "marketplace.json",
+    ".claude-plugin/marketplace.json",
+];
+
+/// Skills listed by the repository's index files. An index that cannot be
+/// read or parsed is reported and skipped; SKILL.md discovery still runs.
+fn index_skills(
+    target: &RemoteTarget,
+    commit: &str,
+    tree: &[TreeEntry],
+    token: Option<&str>,
+    verbose: bool,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Vec<DiscoveredSkill>, RemoteError> {
+    let mut skills = Vec::new();
+    for index in INDEX_FILES {
+        if !tree
+            .iter()
+            .any(|e| e.entry_type == "blob" && e.path == *index)
+        {
+            continue;
+        }
+        if verbose {
+            eprintln!("Reading skill index: {index}");
+        }
+        let bytes = fetch_file_content(target, commit, index, token)?;
+        let parsed = serde_json::from_slice(&bytes)
+            .map_err(|e| e.to_string())
+            .and_then(|json| parse_index(&json));
+        match parsed {
+            Ok(listed) => skills.extend(
+                listed
+                    .into_iter()
+                    .filter(|s| tree.iter().any(|e| e.path.starts_with(&s.prefix))),
+            ),
+            Err(e) => diagnostics.push(diagnostics::warn(
+                Code::SkillIndexInvalid,
+                format!("ignoring skill index {index}: {e}"),
+            )),
+        }
+    }
+    Ok(skills)
+}
+
+/// Skills named in an index: `skills.json` (`{"skills": [{"name", "path"}]}`,
+/// or a bare array of entries or paths) or a plugin marketplace
+/// (`{"plugins": [{"name", "source", "skills"}]}`). Paths are relative to the
+/// repository root; marketplace sources outside the repository are skipped.
+fn parse_index(json: &serde_json::Value) -> Result<Vec<DiscoveredSkill>, String> {
+    let dir = |path: &str| {
+        let path = path
+            .trim_start_matches("./")
+            .trim_start_matches('/')
+            .trim_end_matches('/');
+        if path.is_empty() || path == "." {
+            String::new()
+        } else {
+            format!("{path}/")
+        }
+    };
+    let skill = |name: Option<&str>, prefix: String| DiscoveredSkill {
+        name: name
+            .map(str::to_string)
+            .or_else(|| {
+                prefix
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .map(str::to_string)
+            })
+            .unwrap_or_default(),
+        prefix,
+    };
+
+    if let Some(plugins) = json["plugins"].as_array() {
+        let root = dir(json["metadata"]["pluginRoot"].as_str().unwrap_or(""));
+        let mut skills = Vec::new();
+        for plugin in plugins {
+            let Some(source) = plugin["source"].as_str() else {
+                continue; // GitHub, git or npm source: not in this repository
+            };
+            let base = format!("{root}{}", dir(source));
+            match plugin["skills"].as_array() {
+                Some(paths) => skills.extend(
+                    paths
+                        .iter()
+                        .filter_map(|p| p.as_str())
+                        .map(|p| skill(None, format!("{base}{}", dir(p)))),
+                ),
+                None => skills.push(skill(plugin["name"].as_str(), base)),
+            }
+        }
+        return Ok(skills);
+    }
+
+    let entries = json["skills"]
+        .as_array()
+        .or_else(|| json.as_array())
+        .ok_or("expected a \"skills\" or \"plugins\" array")?;
+    Ok(entries
+        .iter()
+        .filter_map(|entry| match entry {
+            serde_json::Value::String(path) => Some(skill(None, dir(path))),
+            _ => entry["path"]
+                .as_str()
+                .map(|path| skill(entry["name"].as_str(), dir(path))),
+        })
+        .collect())
+}
+
+/// Discover skills by finding marker files (SKILL.md by default) in the
+/// tree, adding any listed in an index that have no marker yet.
 fn discover_skills(
     tree: &[TreeEntry],
     target: &RemoteTarget,
+    markers: &[String],
+    indexed: Vec<DiscoveredSkill>,
 ) -> Result<Vec<DiscoveredSkill>, RemoteError> {
     let skill_files: Vec<&TreeEntry> = tree
         .iter()
@@ -167,15 +732,15 @@ fn discover_skills(
                 && e.path
                     .rsplit('/')
                     .next()
-                    .is_some_and(|name| name == "SKILL.md")
+                    .is_some_and(|name| markers.iter().any(|m| m == name))
         })
         .collect();
 
-    if skill_files.is_empty() {
+    if skill_files.is_empty() && indexed.is_empty() {
         return Err(RemoteError::NoSkillsFound);
     }
 
-    let skills: Vec<DiscoveredSkill> = skill_files
+    let mut skills: Vec<DiscoveredSkill> = skill_files
         .iter()
         .map(|entry| {
             // "react-best-practices/SKILL.md" → prefix "react-best-practices/", name "react-best-practices"
@@ -202,6 +767,11 @@ fn discover_skills(
             }
         })
         .collect();
+    for skill in indexed {
+        if !skills.iter().any(|s| s.prefix == skill.prefix) {
+            skills.push(skill);
+        }
+    }
 
     // Filter to specific skill if requested
     if let Some(ref skill_name) = target.skill_name {
@@ -222,57 +792,78 @@ fn discover_skills(
 /// Fetch a single file's raw content from GitHub.
 fn fetch_file_content(
     target: &RemoteTarget,
-    branch: &str,
+    commit: &str,
     path: &str,
     token: Option<&str>,
-) -> Result<String, RemoteError> {
+) -> Result<Vec<u8>, RemoteError> {
     let url = format!(
         "https://raw.githubusercontent.com/{}/{}/{}/{}",
-        target.owner, target.repo, branch, path
+        target.owner, target.repo, commit, path
     );
 
     let mut resp = make_request(&url, token)?;
     resp.body_mut()
-        .read_to_string()
+        .read_to_vec()
         .map_err(|e| RemoteError::HttpError(format!("failed to read file {path}: {e}")))
 }
 
-/// Make an HTTP GET request with optional auth and standard headers.
+/// Make an HTTP GET request with optional auth and standard headers, within
+/// the configured timeouts, retries and rate-limit waits.
 fn make_request(
     url: &str,
     token: Option<&str>,
 ) -> Result<ureq::http::Response<ureq::Body>, RemoteError> {
-    let mut req = ureq::get(url).header("User-Agent", USER_AGENT);
-
-    if let Some(token) = token {
-        req = req.header("Authorization", &format!("Bearer {token}"));
+    let auth = token.map(|t| format!("Bearer {t}"));
+    let mut headers = vec![("User-Agent", USER_AGENT)];
+    if let Some(ref auth) = auth {
+        headers.push(("Authorization", auth));
     }
-
     // For API endpoints, request JSON
     if url.contains("api.github.com") {
-        req = req.header("Accept", "application/vnd.github+json");
+        headers.push(("Accept", "application/vnd.github+json"));
     }
 
-    let resp = req.call().map_err(|e| {
-        let err_string = e.to_string();
-        if err_string.contains("404") {
-            RemoteError::RepoNotFound(url.to_string())
-        } else if err_string.contains("403") {
-            RemoteError::RateLimited {
-                reset_timestamp: None,
-            }
-        } else {
-            RemoteError::HttpError(err_string)
-        }
-    })?;
-
-    Ok(resp)
+    http::get(url, &headers).map_err(|failure| match (failure.rate_limit, failure.status) {
+        (Some(limit), _) if limit.secondary => RemoteError::SecondaryRateLimited {
+            retry_after: limit.wait.as_secs(),
+        },
+        (Some(limit), _) => RemoteError::RateLimited {
+            reset_timestamp: limit.reset_timestamp,
+        },
+        (None, Some(404)) => RemoteError::RepoNotFound(url.to_string()),
+        (None, Some(403)) => RemoteError::RateLimited {
+            reset_timestamp: None,
+        },
+        (None, _) => RemoteError::HttpError(format!("GET {url}: {}", failure.detail)),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn markers() -> Vec<String> {
+        vec!["SKILL.md".to_string()]
+    }
+
+    #[test]
+    fn test_fetch_concurrently_keeps_order_and_failures_apart() {
+        let skills: Vec<u64> = (0..10).collect();
+        let results = fetch_concurrently(&skills, |&n| {
+            // Later items finish first.
+            std::thread::sleep(std::time::Duration::from_millis(10 - n));
+            if n == 3 {
+                Err(RemoteError::NoSkillsFound)
+            } else {
+                Ok(n * 2)
+            }
+        });
+        assert_eq!(results.len(), 10);
+        assert!(results[3].is_err());
+        assert_eq!(results[9].as_ref().unwrap(), &18);
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 9);
+    }
+
     fn make_tree_entry(path: &str, entry_type: &str) -> TreeEntry {
         TreeEntry {
             path: path.to_string(),
@@ -281,6 +872,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_closest_ref_suggestion() {
+        let refs: Vec<String> = ["main", "develop", "release-1.2", "v1.2.0"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(closest("mian", &refs), Some("main"));
+        assert_eq!(closest("Develop", &refs), Some("develop"));
+        assert_eq!(closest("v1.2", &refs), Some("v1.2.0"));
+        assert_eq!(closest("feature/login", &refs), None);
+        assert_eq!(api_ref("refs/tags/v1.2.0"), "tags/v1.2.0");
+        assert_eq!(api_ref("main"), "main");
+    }
+
+    #[test]
+    fn test_index_adds_skills_without_skill_md() {
+        let index = serde_json::json!({
+            "skills": [
+                {"name": "pdf", "path": "./skills/pdf"},
+                {"name": "draft", "path": "skills/draft/"}
+            ]
+        });
+        let tree = vec![
+            make_tree_entry("skills/pdf/SKILL.md", "blob"),
+            make_tree_entry("skills/draft/notes.md", "blob"),
+        ];
+        let target = RemoteTarget {
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            branch: None,
+            skill_name: None,
+        };
+
+        let skills =
+            discover_skills(&tree, &target, &markers(), parse_index(&index).unwrap()).unwrap();
+        let prefixes: Vec<&str> = skills.iter().map(|s| s.prefix.as_str()).collect();
+        assert_eq!(prefixes, ["skills/pdf/", "skills/draft/"]);
+        assert_eq!(skills[1].name, "draft");
+    }
+
+    #[test]
+    fn test_parse_marketplace_index() {
+        let index = serde_json::json!({
+            "metadata": {"pluginRoot": "./plugins"},
+            "plugins": [
+                {"name": "formatter", "source": "./formatter"},
+                {"name": "docs", "source": "./docs", "skills": ["./skills/pdf", "./skills/xlsx"]},
+                {"name": "external", "source": {"source": "github", "repo": "acme/tools"}}
+            ]
+        });
+        let skills = parse_index(&index).unwrap();
+        let found: Vec<(&str, &str)> = skills
+            .iter()
+            .map(|s| (s.name.as_str(), s.prefix.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("formatter", "plugins/formatter/"),
+                ("pdf", "plugins/docs/skills/pdf/"),
+                ("xlsx", "plugins/docs/skills/xlsx/"),
+            ]
+        );
+        assert!(parse_index(&serde_json::json!({"name": "x"})).is_err());
+    }
+
+    #[test]
+    fn test_reported_path_resolves_under_the_skill() {
+        let target = RemoteTarget {
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            branch: None,
+            skill_name: None,
+        };
+        let single = vec![
+            make_tree_entry("pdf/SKILL.md", "blob"),
+            make_tree_entry("pdf/run.sh", "blob"),
+        ];
+        let path = |tree: &[TreeEntry], file| reported_path(tree, &target, &markers(), file);
+        assert_eq!(path(&single, "run.sh").as_deref(), Some("pdf/run.sh"));
+        assert_eq!(path(&single, "missing.sh"), None);
+
+        let several = vec![
+            make_tree_entry("pdf/SKILL.md", "blob"),
+            make_tree_entry("xlsx/SKILL.md", "blob"),
+            make_tree_entry("xlsx/run.sh", "blob"),
+        ];
+        assert_eq!(
+            path(&several, "xlsx/run.sh").as_deref(),
+            Some("xlsx/run.sh")
+        );
+    }
+
     #[test]
     fn test_discover_skills_single() {
         let tree = vec![
@@ -295,7 +979,7 @@ mod tests {
             skill_name: None,
         };
 
-        let skills = discover_skills(&tree, &target).unwrap();
+        let skills = discover_skills(&tree, &target, &markers(), Vec::new()).unwrap();
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "react-best-practices");
         assert_eq!(skills[0].prefix, "react-best-practices/");
@@ -316,7 +1000,7 @@ mod tests {
             skill_name: None,
         };
 
-        let skills = discover_skills(&tree, &target).unwrap();
+        let skills = discover_skills(&tree, &target, &markers(), Vec::new()).unwrap();
         assert_eq!(skills.len(), 2);
     }
 
@@ -333,7 +1017,7 @@ mod tests {
             skill_name: Some("skill-b".to_string()),
         };
 
-        let skills = discover_skills(&tree, &target).unwrap();
+        let skills = discover_skills(&tree, &target, &markers(), Vec::new()).unwrap();
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "skill-b");
     }
@@ -348,7 +1032,7 @@ mod tests {
             skill_name: Some("nonexistent".to_string()),
         };
 
-        let err = discover_skills(&tree, &target).unwrap_err();
+        let err = discover_skills(&tree, &target, &markers(), Vec::new()).unwrap_err();
         assert!(matches!(err, RemoteError::SkillNotFound(_)));
     }
 
@@ -365,7 +1049,7 @@ mod tests {
             skill_name: None,
         };
 
-        let err = discover_skills(&tree, &target).unwrap_err();
+        let err = discover_skills(&tree, &target, &markers(), Vec::new()).unwrap_err();
         assert!(matches!(err, RemoteError::NoSkillsFound));
     }
 
@@ -382,7 +1066,7 @@ mod tests {
             skill_name: None,
         };
 
-        let skills = discover_skills(&tree, &target).unwrap();
+        let skills = discover_skills(&tree, &target, &markers(), Vec::new()).unwrap();
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "my-skill");
         assert_eq!(skills[0].prefix, "");
@@ -401,7 +1085,7 @@ mod tests {
             skill_name: None,
         };
 
-        let skills = discover_skills(&tree, &target).unwrap();
+        let skills = discover_skills(&tree, &target, &markers(), Vec::new()).unwrap();
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "react-best-practices");
         assert_eq!(skills[0].prefix, "skills/react-best-practices/");
@@ -0,0 +1,357 @@
+use crate::trace;
+use std::fmt;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::OnceLock;
+use std::time::Duration;
+use ureq::http::{HeaderMap, Response};
+use ureq::Body;
+
+/// Base delay before the first retry; each later retry doubles it.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// GitHub asks clients hitting a secondary limit without `Retry-After` to
+/// wait at least a minute.
+const SECONDARY_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Timeouts, retry policy and rate-limit patience for every request to
+/// GitHub.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HttpSettings {
+    pub connect_timeout: Duration,
+    /// Limit on waiting for the response headers, and again for the body.
+    pub read_timeout: Duration,
+    /// Extra attempts after a timeout, connection failure or 5xx.
+    pub retries: u32,
+    /// Longest total pause, per request, for a rate limit to lift.
+    pub max_rate_limit_wait: Duration,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+            retries: 2,
+            max_rate_limit_wait: Duration::from_secs(120),
+        }
+    }
+}
+
+static SETTINGS: OnceLock<HttpSettings> = OnceLock::new();
+static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+
+/// Set the policy for this process. Only the first call has any effect, and
+/// it must come before the first request.
+pub fn configure(settings: HttpSettings) {
+    let _ = SETTINGS.set(settings);
+}
+
+fn settings() -> HttpSettings {
+    *SETTINGS.get_or_init(HttpSettings::default)
+}
+
+/// The shared agent, so connections are reused across requests. Error
+/// statuses come back as responses, so their rate-limit headers can be read.
+fn agent() -> &'static ureq::Agent {
+    AGENT.get_or_init(|| {
+        let settings = settings();
+        ureq::Agent::config_builder()
+            .timeout_connect(Some(settings.connect_timeout))
+            .timeout_recv_response(Some(settings.read_timeout))
+            .timeout_recv_body(Some(settings.read_timeout))
+            .http_status_as_error(false)
+            .build()
+            .into()
+    })
+}
+
+/// A rate limit GitHub reported, and how long until it lifts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    /// A secondary (abuse) limit, announced with `Retry-After`, rather than
+    /// the hourly request quota.
+    pub secondary: bool,
+    /// `Duration::MAX` when GitHub did not say.
+    pub wait: Duration,
+    /// When the hourly quota resets, as a Unix timestamp.
+    pub reset_timestamp: Option<u64>,
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.secondary {
+            write!(f, "GitHub secondary rate limit")
+        } else {
+            write!(f, "GitHub API rate limit")
+        }
+    }
+}
+
+/// Why a request failed, after any retries.
+#[derive(Debug)]
+pub struct Failure {
+    /// The HTTP status, when the server answered.
+    pub status: Option<u16>,
+    pub rate_limit: Option<RateLimit>,
+    /// What went wrong, including each earlier failed attempt.
+    pub detail: String,
+    transient: bool,
+}
+
+impl Failure {
+    fn from_error(error: ureq::Error) -> Self {
+        let transient = matches!(
+            error,
+            ureq::Error::Timeout(_)
+                | ureq::Error::Io(_)
+                | ureq::Error::ConnectionFailed
+                | ureq::Error::HostNotFound
+        );
+        Failure {
+            status: None,
+            rate_limit: None,
+            detail: error.to_string(),
+            transient,
+        }
+    }
+
+    fn from_status(status: u16, headers: &HeaderMap, now: u64) -> Self {
+        Failure {
+            status: Some(status),
+            rate_limit: rate_limit(status, headers, now),
+            detail: format!("http status: {status}"),
+            transient: status >= 500,
+        }
+    }
+}
+
+/// GET `url` with `headers`, within the configured timeouts. Transient
+/// failures are retried with backoff; rate limits are waited out when they
+/// lift within `max_rate_limit_wait`.
+pub fn get(url: &str, headers: &[(&str, &str)]) -> Result<Response<Body>, Failure> {
+    with_retries(settings(), || {
+        let mut req = agent().get(url);
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        let started = std::time::Instant::now();
+        let result = req.call();
+        let status = result.as_ref().ok().map(|r| r.status().as_u16());
+        trace::http("GET", url, status, started.elapsed());
+        match result {
+            Ok(resp) if resp.status().as_u16() < 400 => Ok(resp),
+            Ok(resp) => Err(Failure::from_status(
+                resp.status().as_u16(),
+                resp.headers(),
+                chrono::Utc::now().timestamp().max(0) as u64,
+            )),
+            Err(e) => Err(Failure::from_error(e)),
+        }
+    })
+}
+
+/// The rate limit a 403 or 429 response reports: `Retry-After` for a
+/// secondary limit, an exhausted `x-ratelimit-remaining` for the quota.
+fn rate_limit(status: u16, headers: &HeaderMap, now: u64) -> Option<RateLimit> {
+    if status != 403 && status != 429 {
+        return None;
+    }
+    let number = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    if let Some(seconds) = number("retry-after") {
+        return Some(RateLimit {
+            secondary: true,
+            wait: Duration::from_secs(seconds),
+            reset_timestamp: None,
+        });
+    }
+    if number("x-ratelimit-remaining") == Some(0) {
+        let reset = number("x-ratelimit-reset");
+        return Some(RateLimit {
+            secondary: false,
+            // A second past the reset, so the quota has certainly refilled.
+            wait: reset.map_or(Duration::MAX, |r| {
+                Duration::from_secs(r.saturating_sub(now) + 1)
+            }),
+            reset_timestamp: reset,
+        });
+    }
+    (status == 429).then_some(RateLimit {
+        secondary: true,
+        wait: SECONDARY_LIMIT_WAIT,
+        reset_timestamp: None,
+    })
+}
+
+/// Run `attempt` until it succeeds or fails for good. Transient failures
+/// are retried up to `settings.retries` times with exponential backoff and
+/// jitter; rate limits are waited out while the total pause stays within
+/// `settings.max_rate_limit_wait`. The final failure lists every earlier one.
+fn with_retries<T>(
+    settings: HttpSettings,
+    mut attempt: impl FnMut() -> Result<T, Failure>,
+) -> Result<T, Failure> {
+    let mut earlier: Vec<String> = Vec::new();
+    let mut retried = 0;
+    let mut waited = Duration::ZERO;
+    loop {
+        let mut failure = match attempt() {
+            Ok(value) => return Ok(value),
+            Err(f) => f,
+        };
+        let delay = match failure.rate_limit {
+            Some(ref limit) => waited
+                .checked_add(limit.wait)
+                .is_some_and(|total| total <= settings.max_rate_limit_wait)
+                .then_some(limit.wait),
+            None if failure.transient && retried < settings.retries => {
+                retried += 1;
+                Some(backoff(retried - 1))
+            }
+            None => None,
+        };
+        let Some(delay) = delay else {
+            if !earlier.is_empty() {
+                failure.detail = format!(
+                    "{} (after {} attempts; {})",
+                    failure.detail,
+                    earlier.len() + 1,
+                    earlier.join("; ")
+                );
+            }
+            return Err(failure);
+        };
+        if let Some(ref limit) = failure.rate_limit {
+            eprintln!("note: {limit} reached; resuming in {}s", delay.as_secs());
+            waited += delay;
+        }
+        earlier.push(format!("attempt {}: {}", earlier.len() + 1, failure.detail));
+        std::thread::sleep(delay);
+    }
+}
+
+/// Delay before retry `n` (0-based): the base doubled `n` times, plus up to
+/// as much again at random so parallel CI jobs do not retry in step.
+fn backoff(n: u32) -> Duration {
+    let delay = RETRY_BASE_DELAY * 2u32.saturating_pow(n);
+    let jitter = RandomState::new().hash_one(n) % (delay.as_millis() as u64 + 1);
+    delay + Duration::from_millis(jitter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ureq::http::HeaderValue;
+
+    fn status(code: u16, headers: &[(&'static str, &'static str)]) -> Failure {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(*name, HeaderValue::from_static(value));
+        }
+        Failure::from_status(code, &map, 1_000)
+    }
+
+    #[test]
+    fn test_backoff_doubles_with_bounded_jitter() {
+        for n in 0..3 {
+            let base = RETRY_BASE_DELAY * 2u32.pow(n);
+            let delay = backoff(n);
+            assert!(delay >= base && delay <= base * 2, "{delay:?}");
+        }
+    }
+
+    #[test]
+    fn test_rate_limits_are_told_apart() {
+        let secondary = status(403, &[("retry-after", "30")]).rate_limit.unwrap();
+        assert!(secondary.secondary);
+        assert_eq!(secondary.wait, Duration::from_secs(30));
+
+        let primary = status(
+            403,
+            &[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "1100"),
+            ],
+        )
+        .rate_limit
+        .unwrap();
+        assert!(!primary.secondary);
+        assert_eq!(primary.wait, Duration::from_secs(101));
+        assert_eq!(primary.reset_timestamp, Some(1100));
+
+        assert!(status(429, &[]).rate_limit.unwrap().secondary);
+        // A plain 403 is a permission problem, not a limit.
+        assert!(status(403, &[("x-ratelimit-remaining", "12")])
+            .rate_limit
+            .is_none());
+    }
+
+    #[test]
+    fn test_retries_transient_errors_only() {
+        let settings = HttpSettings {
+            retries: 1,
+            ..HttpSettings::default()
+        };
+        let mut calls = 0;
+        let result: Result<(), _> = with_retries(settings, || {
+            calls += 1;
+            Err(status(503, &[]))
+        });
+        assert_eq!(calls, 2);
+        assert_eq!(
+            result.unwrap_err().detail,
+            "http status: 503 (after 2 attempts; attempt 1: http status: 503)"
+        );
+
+        calls = 0;
+        let result: Result<(), _> = with_retries(settings, || {
+            calls += 1;
+            Err(status(404, &[]))
+        });
+        assert_eq!(result.unwrap_err().detail, "http status: 404");
+        assert_eq!(calls, 1);
+
+        calls = 0;
+        let result = with_retries(settings, || {
+            calls += 1;
+            if calls < 2 {
+                Err(Failure::from_error(ureq::Error::ConnectionFailed))
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_rate_limit_waits_stop_at_the_ceiling() {
+        let settings = HttpSettings {
+            retries: 0,
+            max_rate_limit_wait: Duration::from_secs(1),
+            ..HttpSettings::default()
+        };
+        // Retry-After: 0 twice, then done: waits fit under the ceiling and
+        // do not use up retries.
+        let mut calls = 0;
+        let result = with_retries(settings, || {
+            calls += 1;
+            if calls < 3 {
+                Err(status(403, &[("retry-after", "0")]))
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        calls = 0;
+        let result: Result<(), _> = with_retries(settings, || {
+            calls += 1;
+            Err(status(403, &[("retry-after", "3600")]))
+        });
+        assert!(result.unwrap_err().rate_limit.unwrap().secondary);
+        assert_eq!(calls, 1);
+    }
+}
@@ -1,20 +1,40 @@
+pub mod git;
 pub mod github;
+pub mod http;
 pub mod parse;
 
 pub use parse::RemoteTarget;
 
-use crate::scanner::ScannedFile;
+use crate::diagnostics::Diagnostic;
+use crate::scanner::{ScannedFile, SkippedFile};
 use std::fmt;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub enum RemoteError {
     ParseError(String),
     HttpError(String),
-    RateLimited { reset_timestamp: Option<u64> },
+    RateLimited {
+        reset_timestamp: Option<u64>,
+    },
+    /// GitHub's secondary (abuse) limit, which asked for a longer pause than
+    /// `--max-rate-limit-wait` allows.
+    SecondaryRateLimited {
+        retry_after: u64,
+    },
     RepoNotFound(String),
     NoSkillsFound,
     SkillNotFound(String),
+    /// A branch or tag the repository does not have, with the closest
+    /// existing ref and a sample of the others.
+    RefNotFound {
+        name: String,
+        suggestion: Option<String>,
+        available: Vec<String>,
+    },
     TreeTruncated,
+    /// A reported file that is not in the repository at the scanned commit.
+    FileNotFound(String),
 }
 
 impl fmt::Display for RemoteError {
@@ -28,23 +48,114 @@ impl fmt::Display for RemoteError {
             RemoteError::RateLimited {
                 reset_timestamp: None,
             } => write!(f, "GitHub API rate limit exceeded"),
+            RemoteError::SecondaryRateLimited { retry_after } => write!(
+                f,
+                "GitHub secondary rate limit hit; retry after {retry_after}s or raise --max-rate-limit-wait"
+            ),
             RemoteError::RepoNotFound(spec) => {
                 write!(f, "repository not found: {spec}")
             }
             RemoteError::NoSkillsFound => {
-                write!(f, "no skills found (no SKILL.md files in repository)")
+                write!(f, "no skills found (no skill marker files in repository)")
             }
             RemoteError::SkillNotFound(name) => {
                 write!(f, "skill '{name}' not found in repository")
             }
+            RemoteError::RefNotFound {
+                name,
+                suggestion,
+                available,
+            } => {
+                write!(f, "branch or tag '{name}' not found")?;
+                if let Some(s) = suggestion {
+                    write!(f, "; did you mean '{s}'?")?;
+                }
+                if !available.is_empty() {
+                    const SHOWN: usize = 10;
+                    write!(f, " (available: {}", available[..available.len().min(SHOWN)].join(", "))?;
+                    if available.len() > SHOWN {
+                        write!(f, ", and {} more", available.len() - SHOWN)?;
+                    }
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
             RemoteError::TreeTruncated => write!(
                 f,
                 "repository tree is too large (truncated by GitHub API); try specifying a skill name with @"
             ),
+            RemoteError::FileNotFound(path) => {
+                write!(f, "{path} not found at the scanned commit")
+            }
         }
     }
 }
 
+/// Files fetched for a remote target, with the revision they were read at.
+pub struct RemoteSkill {
+    pub files: Vec<ScannedFile>,
+    /// Files in the tree that could not be read as text.
+    pub skipped: Vec<SkippedFile>,
+    pub repository: String,
+    pub branch: String,
+    /// Commit SHA the branch resolved to; all files are read at this commit.
+    pub commit: String,
+    pub skills: Vec<RemoteSkillDir>,
+    /// Skills whose files could not all be fetched. The rest are still
+    /// scanned.
+    pub failed: Vec<FailedSkill>,
+    /// Problems that did not stop the fetch, such as an unreadable skill
+    /// index.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Publisher signals for a repository, as GitHub reports them.
+#[derive(Clone)]
+pub struct RepoSignals {
+    pub repository: String,
+    pub stars: u64,
+    pub created_at: String,
+    pub owner: String,
+    /// When the owning user or organization account was created.
+    pub owner_created_at: Option<String>,
+    pub fork: Option<ForkSignals>,
+}
+
+/// How a fork relates to the repository it was forked from.
+#[derive(Clone)]
+pub struct ForkSignals {
+    pub parent: String,
+    pub parent_stars: u64,
+    /// Commits on the scanned ref that the parent's default branch lacks.
+    pub ahead_by: u64,
+    pub behind_by: u64,
+}
+
+/// A repository or pull request with changes since a queue watcher's last run.
+pub struct QueueItem {
+    /// Remote specifier to scan, pinned to the PR head commit for pull requests.
+    pub spec: String,
+    /// `owner/repo`, or `owner/repo#123` for a pull request.
+    pub label: String,
+    pub updated_at: String,
+}
+
+/// A skill directory in the repository and where its files were reported.
+pub struct RemoteSkillDir {
+    /// Repository path with trailing `/`, or empty for a root skill.
+    pub prefix: String,
+    /// Directory of the skill's files in `RemoteSkill::files`.
+    pub root: PathBuf,
+}
+
+/// A skill left out of a remote scan, and why.
+#[derive(Clone)]
+pub struct FailedSkill {
+    /// Where the skill's files would have been reported.
+    pub root: PathBuf,
+    pub error: String,
+}
+
 /// Fetch files for a remote skill from GitHub.
 ///
 /// Parses the target specifier, fetches the repo tree via GitHub API,
@@ -53,13 +164,59 @@ impl fmt::Display for RemoteError {
 pub fn fetch_remote_skill(
     spec: &str,
     token: Option<&str>,
+    markers: &[String],
     verbose: bool,
-) -> Result<Vec<ScannedFile>, RemoteError> {
+) -> Result<RemoteSkill, RemoteError> {
     let target = RemoteTarget::parse(spec).map_err(RemoteError::ParseError)?;
 
     if verbose {
         eprintln!("Remote target: {target}");
     }
 
-    github::fetch_skill_files(&target, token, verbose)
+    github::fetch_skill_files(&target, token, markers, verbose)
+}
+
+/// The text of one file as a scan of `spec` at `commit` reported it, fetched
+/// on its own rather than with the rest of the skill.
+pub fn fetch_reported_file(
+    spec: &str,
+    commit: &str,
+    file: &Path,
+    token: Option<&str>,
+    markers: &[String],
+    verbose: bool,
+) -> Result<String, RemoteError> {
+    let target = RemoteTarget::parse(spec).map_err(RemoteError::ParseError)?;
+    github::fetch_reported_file(&target, commit, file, token, markers, verbose)
+}
+
+/// Stars, fork status and owner account age for the repository behind `spec`.
+pub fn repo_signals(
+    spec: &str,
+    skill: &RemoteSkill,
+    token: Option<&str>,
+    verbose: bool,
+) -> Result<RepoSignals, RemoteError> {
+    let target = RemoteTarget::parse(spec).map_err(RemoteError::ParseError)?;
+    github::repo_signals(&target, &skill.commit, token, verbose)
+}
+
+/// When each skill's directory last changed, as of the scanned commit: its
+/// reported root and the RFC 3339 committer date.
+pub fn last_changed(
+    spec: &str,
+    skill: &RemoteSkill,
+    token: Option<&str>,
+    verbose: bool,
+) -> Result<Vec<(PathBuf, String)>, RemoteError> {
+    let target = RemoteTarget::parse(spec).map_err(RemoteError::ParseError)?;
+    skill
+        .skills
+        .iter()
+        .map(|dir| {
+            let date =
+                github::last_commit_date(&target, &skill.commit, &dir.prefix, token, verbose)?;
+            Ok((dir.root.clone(), date))
+        })
+        .collect()
 }
@@ -5,6 +5,8 @@
 /// - `owner/repo@skill-name`
 /// - `owner/repo:branch`
 /// - `owner/repo:branch@skill-name`
+/// - `owner/repo:refs/tags/v1.2.0` (or `refs/heads/name`) to pick a tag or
+///   branch explicitly when both share a name
 /// - `https://github.com/owner/repo`
 /// - `https://github.com/owner/repo/tree/branch/path/to/skill`
 
@@ -198,6 +200,13 @@ mod tests {
         assert_eq!(t.skill_name, Some("react-best-practices".to_string()));
     }
 
+    #[test]
+    fn test_parse_explicit_tag_ref() {
+        let t = RemoteTarget::parse("owner/repo:refs/tags/v1.2.0@skill").unwrap();
+        assert_eq!(t.branch.as_deref(), Some("refs/tags/v1.2.0"));
+        assert_eq!(t.skill_name.as_deref(), Some("skill"));
+    }
+
     #[test]
     fn test_parse_github_url_simple() {
         let t = RemoteTarget::parse("https://github.com/vercel-labs/agent-skills").unwrap();
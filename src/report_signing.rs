@@ -0,0 +1,145 @@
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// DER prefix of an Ed25519 SubjectPublicKeyInfo; the raw key follows it.
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// Protected header of a report signature.
+#[derive(Serialize, Deserialize)]
+struct Header {
+    alg: String,
+    kid: String,
+}
+
+/// Key id: the first 16 hex digits of the SHA-256 of the raw public key.
+fn key_id(public_key: &[u8]) -> String {
+    Sha256::digest(public_key)
+        .iter()
+        .take(8)
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Read a key file as DER, decoding it first if it is PEM.
+fn read_der(path: &Path) -> Result<Vec<u8>, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let Ok(text) = std::str::from_utf8(&bytes) else {
+        return Ok(bytes);
+    };
+    if !text.trim_start().starts_with("-----BEGIN") {
+        return Ok(bytes);
+    }
+    let body: String = text
+        .lines()
+        .filter(|l| !l.starts_with("-----"))
+        .map(str::trim)
+        .collect();
+    STANDARD
+        .decode(body)
+        .map_err(|e| format!("{}: invalid PEM: {e}", path.display()))
+}
+
+/// Sign a report's exact bytes with an Ed25519 PKCS#8 private key (PEM or
+/// DER, as written by `openssl genpkey -algorithm ed25519`). Returns a
+/// compact JWS with a detached payload (RFC 7515, appendix F).
+pub fn sign(report: &[u8], key_path: &Path) -> Result<String, String> {
+    let der = read_der(key_path)?;
+    let pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(&der)
+        .map_err(|e| format!("{}: not an Ed25519 private key: {e}", key_path.display()))?;
+    let header = Header {
+        alg: "EdDSA".to_string(),
+        kid: key_id(pair.public_key().as_ref()),
+    };
+    let header = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header).map_err(|e| e.to_string())?);
+    let input = format!("{header}.{}", URL_SAFE_NO_PAD.encode(report));
+    let signature = pair.sign(input.as_bytes());
+    Ok(format!(
+        "{header}..{}",
+        URL_SAFE_NO_PAD.encode(signature.as_ref())
+    ))
+}
+
+/// Check a detached JWS from [`sign`] against a report and an Ed25519 public
+/// key (SubjectPublicKeyInfo PEM or DER, as written by `openssl pkey -pubout`).
+/// Returns the key id on success.
+pub fn verify(report: &[u8], jws: &str, key_path: &Path) -> Result<String, String> {
+    let der = read_der(key_path)?;
+    let public_key = der
+        .strip_prefix(&ED25519_SPKI_PREFIX[..])
+        .filter(|k| k.len() == 32)
+        .ok_or_else(|| format!("{}: not an Ed25519 public key", key_path.display()))?;
+
+    let parts: Vec<&str> = jws.trim().split('.').collect();
+    let [header_b64, "", signature_b64] = parts[..] else {
+        return Err("malformed signature: expected a detached JWS (header..signature)".to_string());
+    };
+    let header: Header = URL_SAFE_NO_PAD
+        .decode(header_b64)
+        .ok()
+        .and_then(|h| serde_json::from_slice(&h).ok())
+        .ok_or("malformed signature header")?;
+    if header.alg != "EdDSA" {
+        return Err(format!("unsupported signature algorithm '{}'", header.alg));
+    }
+    let kid = key_id(public_key);
+    if header.kid != kid {
+        return Err(format!(
+            "report was signed by key {}, not by {kid}",
+            header.kid
+        ));
+    }
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature_b64)
+        .map_err(|_| "malformed signature value")?;
+    let input = format!("{header_b64}.{}", URL_SAFE_NO_PAD.encode(report));
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(input.as_bytes(), &signature)
+        .map_err(|_| {
+            "signature does not match the report; it was modified or signed by another key"
+                .to_string()
+        })?;
+    Ok(kid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+
+    #[test]
+    fn test_sign_and_verify_detached_jws() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let private = dir.path().join("key.der");
+        std::fs::write(&private, pkcs8.as_ref()).unwrap();
+        let mut spki = ED25519_SPKI_PREFIX.to_vec();
+        spki.extend_from_slice(pair.public_key().as_ref());
+        let public = dir.path().join("key.pub.pem");
+        std::fs::write(
+            &public,
+            format!(
+                "-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n",
+                STANDARD.encode(&spki)
+            ),
+        )
+        .unwrap();
+
+        let report = b"{\"findings\":[]}\n";
+        let jws = sign(report, &private).unwrap();
+        assert!(jws.contains(".."));
+        assert_eq!(
+            verify(report, &jws, &public).unwrap(),
+            key_id(pair.public_key().as_ref())
+        );
+        assert!(verify(b"{\"findings\":[1]}\n", &jws, &public)
+            .unwrap_err()
+            .contains("does not match"));
+    }
+}
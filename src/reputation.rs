@@ -0,0 +1,202 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::remote::RepoSignals;
+use crate::rules::RuleDoc;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::path::Path;
+
+const SIGNALS_RULE_ID: &str = "SL-REP-001";
+const SIGNALS_RULE_NAME: &str = "Repository Signals";
+const NEW_OWNER_RULE_ID: &str = "SL-REP-002";
+const NEW_OWNER_RULE_NAME: &str = "New Publisher Account";
+const LOW_STARS_RULE_ID: &str = "SL-REP-003";
+const LOW_STARS_RULE_NAME: &str = "Low-Visibility Repository";
+const FORK_RULE_ID: &str = "SL-REP-004";
+const FORK_RULE_NAME: &str = "Diverged Fork";
+
+/// Accounts younger than this are often throwaways.
+const NEW_OWNER_DAYS: i64 = 90;
+/// Below this many stars, few people are likely to have looked at the code.
+const LOW_STARS: u64 = 5;
+
+pub const RULE_DOCS: &[RuleDoc] = &[
+    RuleDoc {
+        id: SIGNALS_RULE_ID,
+        name: SIGNALS_RULE_NAME,
+        severity: Severity::Info,
+        applies_to: &[],
+        description: "Summary of the publisher signals gathered by `--reputation`: stars, repository and owner account age, and fork status. Reported for every remote scan with the flag, so reviewers see who they are trusting.",
+        remediation: "No action needed; weigh the signals alongside the other findings.",
+    },
+    RuleDoc {
+        id: NEW_OWNER_RULE_ID,
+        name: NEW_OWNER_RULE_NAME,
+        severity: Severity::Warning,
+        applies_to: &[],
+        description: "The account that owns the repository was created less than 90 days ago. Throwaway accounts are a common vehicle for malicious packages and skills.",
+        remediation: "Find out who is behind the account before installing the skill, or prefer an established publisher.",
+    },
+    RuleDoc {
+        id: LOW_STARS_RULE_ID,
+        name: LOW_STARS_RULE_NAME,
+        severity: Severity::Info,
+        applies_to: &[],
+        description: "The repository has fewer than 5 stars, so few people are likely to have read its code.",
+        remediation: "Review the skill yourself rather than relying on others having done so.",
+    },
+    RuleDoc {
+        id: FORK_RULE_ID,
+        name: FORK_RULE_NAME,
+        severity: Severity::Warning,
+        applies_to: &[],
+        description: "The repository is a fork with commits its upstream does not have. Forks of popular skills that add a few commits are a cheap way to impersonate a trusted project.",
+        remediation: "Install from the upstream repository, or review the fork's extra commits before trusting it.",
+    },
+];
+
+/// Parse a GitHub timestamp, ignoring values it did not provide.
+fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s).ok().map(|d| d.to_utc())
+}
+
+/// Findings for a remote repository's publisher signals, reported at `location`.
+pub fn check(signals: &RepoSignals, location: &Path, now: DateTime<Utc>) -> Vec<Finding> {
+    let finding = |rule_id: &str, rule_name: &str, severity, message: String| Finding {
+        rule_id: rule_id.to_string(),
+        rule_name: rule_name.to_string(),
+        severity,
+        message,
+        location: Location {
+            file: location.to_path_buf(),
+            line: 1,
+            column: 1,
+        },
+        matched_text: signals.repository.clone(),
+        confidence: Confidence::High,
+        tags: vec!["supply-chain".to_string()],
+        domain: None,
+    };
+    let day = |s: &str| s.get(..10).unwrap_or(s).to_string();
+
+    let mut summary = format!(
+        "{}: {} star(s), created {}; owner {}",
+        signals.repository,
+        signals.stars,
+        day(&signals.created_at),
+        signals.owner
+    );
+    if let Some(ref created) = signals.owner_created_at {
+        summary.push_str(&format!(" (account created {})", day(created)));
+    }
+    if let Some(ref fork) = signals.fork {
+        summary.push_str(&format!(
+            "; fork of {} ({} ahead, {} behind)",
+            fork.parent, fork.ahead_by, fork.behind_by
+        ));
+    }
+    let mut findings = vec![finding(
+        SIGNALS_RULE_ID,
+        SIGNALS_RULE_NAME,
+        Severity::Info,
+        summary,
+    )];
+
+    let owner_age = signals
+        .owner_created_at
+        .as_deref()
+        .and_then(parse_date)
+        .map(|created| now - created);
+    if let Some(age) = owner_age.filter(|age| *age < TimeDelta::days(NEW_OWNER_DAYS)) {
+        findings.push(finding(
+            NEW_OWNER_RULE_ID,
+            NEW_OWNER_RULE_NAME,
+            Severity::Warning,
+            format!(
+                "Repository owner {} is a new account ({} days old)",
+                signals.owner,
+                age.num_days()
+            ),
+        ));
+    }
+    if signals.stars < LOW_STARS {
+        findings.push(finding(
+            LOW_STARS_RULE_ID,
+            LOW_STARS_RULE_NAME,
+            Severity::Info,
+            format!(
+                "Repository has {} star(s); few people are likely to have reviewed it",
+                signals.stars
+            ),
+        ));
+    }
+    if let Some(fork) = signals.fork.as_ref().filter(|f| f.ahead_by > 0) {
+        findings.push(finding(
+            FORK_RULE_ID,
+            FORK_RULE_NAME,
+            Severity::Warning,
+            format!(
+                "Fork of {} ({} star(s)) carries {} commit(s) the upstream does not have",
+                fork.parent, fork.parent_stars, fork.ahead_by
+            ),
+        ));
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote::ForkSignals;
+
+    fn signals() -> RepoSignals {
+        RepoSignals {
+            repository: "acme/skills".to_string(),
+            stars: 240,
+            created_at: "2023-04-01T00:00:00Z".to_string(),
+            owner: "acme".to_string(),
+            owner_created_at: Some("2015-01-01T00:00:00Z".to_string()),
+            fork: None,
+        }
+    }
+
+    fn rule_ids(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.rule_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_established_repo_gets_summary_only() {
+        let findings = check(&signals(), Path::new("SKILL.md"), Utc::now());
+        assert_eq!(rule_ids(&findings), [SIGNALS_RULE_ID]);
+        assert_eq!(
+            findings[0].message,
+            "acme/skills: 240 star(s), created 2023-04-01; owner acme (account created 2015-01-01)"
+        );
+    }
+
+    #[test]
+    fn test_new_owner_fork_flags() {
+        let now = Utc::now();
+        let s = RepoSignals {
+            stars: 0,
+            owner: "fresh".to_string(),
+            owner_created_at: Some((now - TimeDelta::days(10)).to_rfc3339()),
+            fork: Some(ForkSignals {
+                parent: "acme/skills".to_string(),
+                parent_stars: 240,
+                ahead_by: 2,
+                behind_by: 0,
+            }),
+            ..signals()
+        };
+        let findings = check(&s, Path::new("SKILL.md"), now);
+        assert_eq!(
+            rule_ids(&findings),
+            [
+                SIGNALS_RULE_ID,
+                NEW_OWNER_RULE_ID,
+                LOW_STARS_RULE_ID,
+                FORK_RULE_ID
+            ]
+        );
+        assert!(findings[1].message.contains("10 days old"));
+    }
+}
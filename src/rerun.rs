@@ -0,0 +1,97 @@
+use crate::finding::Finding;
+use crate::rules::RuleRegistry;
+use crate::scanner::ScannedFile;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// The files and rules behind the findings of an earlier report, for
+/// `--rerun-from`.
+#[derive(Debug, Default)]
+pub struct Rerun {
+    pub files: BTreeSet<PathBuf>,
+    pub rule_ids: BTreeSet<String>,
+}
+
+#[derive(Deserialize)]
+struct SavedReport {
+    findings: Vec<Finding>,
+}
+
+impl Rerun {
+    /// Read a saved `--format json` report.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("--rerun-from {}: {e}", path.display()))?;
+        let report: SavedReport = serde_json::from_str(&text)
+            .map_err(|e| format!("--rerun-from {}: {e}", path.display()))?;
+        Ok(Self::from_findings(&report.findings))
+    }
+
+    pub fn from_findings(findings: &[Finding]) -> Self {
+        Rerun {
+            files: findings.iter().map(|f| f.location.file.clone()).collect(),
+            rule_ids: findings.iter().map(|f| f.rule_id.clone()).collect(),
+        }
+    }
+
+    /// Keep only the files that had findings.
+    pub fn restrict_files(&self, files: &mut Vec<ScannedFile>) {
+        files.retain(|f| self.files.contains(&f.relative_path));
+    }
+
+    /// Keep only the rules in the families of reported findings. A whole
+    /// family runs because some rules report under a sibling ID, as
+    /// SL-HOOK-001 does with SL-HOOK-002 and secret rules with SL-SEC-011.
+    pub fn restrict_rules(&self, registry: &mut RuleRegistry) {
+        let families: BTreeSet<&str> = self.rule_ids.iter().map(|id| family(id)).collect();
+        registry.retain(|rule| families.contains(family(rule.id())));
+    }
+
+    /// Whether a finding concerns a file and rule of the earlier report.
+    pub fn covers(&self, finding: &Finding) -> bool {
+        self.rule_ids.contains(&finding.rule_id) && self.files.contains(&finding.location.file)
+    }
+}
+
+/// A rule ID without its number: `SL-NET` for `SL-NET-002`.
+fn family(rule_id: &str) -> &str {
+    rule_id
+        .rsplit_once('-')
+        .map_or(rule_id, |(family, _)| family)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Severity;
+
+    #[test]
+    fn test_restricts_rules_to_reported_families() {
+        let rerun = Rerun::from_findings(&[
+            Finding::test("SL-HOOK-002", Severity::Warning, "hooks/hooks.json", 1),
+            Finding::test("SL-NET-001", Severity::Warning, "SKILL.md", 1),
+        ]);
+        let mut registry = RuleRegistry::new();
+        registry.load_defaults();
+        rerun.restrict_rules(&mut registry);
+        let ids: Vec<&str> = registry.all_rules().iter().map(|r| r.id()).collect();
+        assert!(ids.contains(&"SL-HOOK-001"));
+        assert!(ids.contains(&"SL-NET-002"));
+        assert!(!ids.iter().any(|id| id.starts_with("SL-INJ-")));
+
+        assert!(rerun.covers(&Finding::test(
+            "SL-NET-001",
+            Severity::Warning,
+            "SKILL.md",
+            1
+        )));
+        assert!(!rerun.covers(&Finding::test(
+            "SL-NET-002",
+            Severity::Warning,
+            "SKILL.md",
+            1
+        )));
+        assert!(!rerun.covers(&Finding::test("SL-NET-001", Severity::Warning, "run.sh", 1)));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::attestation::sha256_hex;
+use crate::cache;
+use crate::finding::Finding;
+use crate::rules::Rule;
+use crate::scanner::ScannedFile;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Rule results stored in the on-disk cache, so sweeps that meet the same
+/// vendored files in many repositories evaluate each one once.
+///
+/// There is one entry per file, addressed by a hash of its content, type and
+/// path (some rules look at where a file lives), holding each rule's findings
+/// under its ID and version. A rule whose version changed is run again and
+/// replaces its old results.
+pub struct RuleCache {
+    dir: PathBuf,
+}
+
+impl RuleCache {
+    /// The cache under `cache::dir()`, or none when there is no cache
+    /// directory.
+    pub fn open() -> Option<Self> {
+        cache::dir().map(|dir| RuleCache { dir })
+    }
+
+    #[cfg(test)]
+    fn at(dir: &std::path::Path) -> Self {
+        RuleCache {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    /// Each rule's findings for `file`, from the cache where the rule's
+    /// version matches and by running it otherwise. The entry is rewritten
+    /// only when something was run; call `finish` after the last file.
+    /// Cache errors never fail a scan.
+    pub fn check<'r>(
+        &self,
+        rules: &[&'r dyn Rule],
+        file: &ScannedFile,
+    ) -> Vec<(&'r dyn Rule, Vec<Finding>)> {
+        let name = entry_name(file);
+        let mut entry: BTreeMap<String, Vec<Finding>> = cache::read(&self.dir, &name)
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        let mut changed = false;
+        let results = rules
+            .iter()
+            .map(|rule| {
+                let key = format!("{}@{}", rule.id(), rule.version());
+                if let Some(findings) = entry.get(&key) {
+                    return (*rule, findings.clone());
+                }
+                // Results of older versions of the rule are of no more use.
+                let prefix = format!("{}@", rule.id());
+                entry.retain(|k, _| !k.starts_with(&prefix));
+                let findings = rule.check(file);
+                entry.insert(key, findings.clone());
+                changed = true;
+                (*rule, findings)
+            })
+            .collect();
+        if changed {
+            if let Ok(json) = serde_json::to_vec(&entry) {
+                let _ = cache::store(&self.dir, &name, &json);
+            }
+        }
+        results
+    }
+
+    /// Trim the cache back under its size limit once a scan is done, if the
+    /// scan took it over.
+    pub fn finish(&self) {
+        if cache::exceeds(&self.dir, cache::DEFAULT_MAX_SIZE) {
+            let _ = cache::collect_garbage(&self.dir, cache::DEFAULT_MAX_SIZE);
+        }
+    }
+}
+
+/// `rules/ab/abcdef….json`, spread over subdirectories to keep each small.
+fn entry_name(file: &ScannedFile) -> String {
+    let mut key = Vec::new();
+    key.extend_from_slice(format!("{:?}", file.file_type).as_bytes());
+    key.push(0);
+    key.extend_from_slice(file.relative_path.to_string_lossy().as_bytes());
+    key.push(0);
+    key.extend_from_slice(file.content.as_bytes());
+    let digest = sha256_hex(&key);
+    format!("rules/{}/{digest}.json", &digest[..2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finding::Severity;
+    use crate::scanner::FileType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Reports one finding per check and counts how often it ran.
+    struct Counting {
+        runs: AtomicUsize,
+        version: &'static str,
+    }
+
+    impl Rule for Counting {
+        fn id(&self) -> &str {
+            "SL-TEST-001"
+        }
+        fn name(&self) -> &str {
+            "Counting"
+        }
+        fn default_severity(&self) -> Severity {
+            Severity::Warning
+        }
+        fn applies_to(&self) -> &[FileType] {
+            &[]
+        }
+        fn version(&self) -> String {
+            self.version.to_string()
+        }
+        fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+            self.runs.fetch_add(1, Ordering::Relaxed);
+            crate::rules::quality_rule::UnfinishedMarkerRule.check(file)
+        }
+    }
+
+    fn skill(content: &str) -> ScannedFile {
+        ScannedFile {
+            path: PathBuf::from("/s/SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_reuses_results_until_content_or_version_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RuleCache::at(dir.path());
+        let rule = Counting {
+            runs: AtomicUsize::new(0),
+            version: "1",
+        };
+        let file = skill("# T\nTODO: finish\n");
+
+        let first = cache.check(&[&rule], &file);
+        let second = cache.check(&[&rule], &file);
+        assert_eq!(rule.runs.load(Ordering::Relaxed), 1);
+        assert_eq!(first[0].1.len(), 1);
+        assert_eq!(second[0].1[0].message, first[0].1[0].message);
+
+        cache.check(&[&rule], &skill("# T\nDone.\n"));
+        assert_eq!(rule.runs.load(Ordering::Relaxed), 2);
+
+        let updated = Counting {
+            runs: AtomicUsize::new(0),
+            version: "2",
+        };
+        cache.check(&[&updated], &file);
+        assert_eq!(updated.runs.load(Ordering::Relaxed), 1);
+    }
+}
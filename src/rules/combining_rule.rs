@@ -0,0 +1,151 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::Rule;
+use crate::scanner::{FileType, ScannedFile};
+use unicode_normalization::char::is_combining_mark;
+
+/// Most scripts stack at most two or three marks on a letter (Vietnamese tone
+/// plus vowel marks); more than this is almost always "zalgo" text.
+pub const DEFAULT_MAX_COMBINING_MARKS: usize = 4;
+
+/// Flags characters carrying a flood of combining diacritics. The stacked marks
+/// smear the line into noise for a human reviewer while the model still reads
+/// the base letters underneath.
+pub struct CombiningFloodRule {
+    max_marks: usize,
+}
+
+impl CombiningFloodRule {
+    pub fn new(max_marks: usize) -> Self {
+        Self { max_marks }
+    }
+}
+
+impl Default for CombiningFloodRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_COMBINING_MARKS)
+    }
+}
+
+impl Rule for CombiningFloodRule {
+    fn id(&self) -> &str {
+        "SL-HID-011"
+    }
+
+    fn name(&self) -> &str {
+        "Combining Character Flood"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[] // all file types
+    }
+
+    fn description(&self) -> &str {
+        "Characters carrying more combining diacritics than any natural script needs (\"zalgo\" text). The stacked marks make a line unreadable to a human reviewer while the model still reads the letters underneath. The limit per character is `max_combining_marks` under `[settings]` (default 4)."
+    }
+
+    fn remediation(&self) -> &str {
+        "Strip the combining marks and review the underlying text. NFC normalization will not remove them; delete them explicitly."
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        vec!["obfuscation"]
+    }
+
+    fn version(&self) -> String {
+        format!("{}+max{}", env!("CARGO_PKG_VERSION"), self.max_marks)
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for (line_num, line) in file.content.lines().enumerate() {
+            let Some((col, base, marks, flooded)) = self.flood(line) else {
+                continue;
+            };
+            findings.push(Finding {
+                rule_id: self.id().to_string(),
+                rule_name: self.name().to_string(),
+                severity: self.default_severity(),
+                message: format!(
+                    "Found {marks} combining marks stacked on {base:?} (limit {}); {flooded} character(s) on this line exceed the limit",
+                    self.max_marks
+                ),
+                location: Location {
+                    file: file.relative_path.clone(),
+                    line: line_num + 1,
+                    column: col + 1,
+                },
+                matched_text: base.to_string(),
+                confidence: Confidence::High,
+                tags: Vec::new(),
+                domain: None,
+            });
+        }
+        findings
+    }
+}
+
+impl CombiningFloodRule {
+    /// The first flooded character on a line: its byte offset, the base character,
+    /// how many marks it carries, and how many characters on the line are
+    /// flooded. One finding per line keeps zalgo paragraphs readable.
+    fn flood(&self, line: &str) -> Option<(usize, char, usize, usize)> {
+        let mut first = None;
+        let mut flooded = 0;
+        let mut base = (0, ' ');
+        let mut marks = 0;
+        let chars = line
+            .char_indices()
+            .chain(std::iter::once((line.len(), '\n')));
+        for (col, ch) in chars {
+            if is_combining_mark(ch) {
+                marks += 1;
+                continue;
+            }
+            if marks > self.max_marks {
+                flooded += 1;
+                first.get_or_insert((base.0, base.1, marks));
+            }
+            base = (col, ch);
+            marks = 0;
+        }
+        first.map(|(col, base, marks)| (col, base, marks, flooded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zalgo(base: char, marks: usize) -> String {
+        std::iter::once(base)
+            .chain(std::iter::repeat_n('\u{0301}', marks))
+            .collect()
+    }
+
+    #[test]
+    fn test_reports_first_flooded_character_per_line() {
+        let line = format!("ok {}{} done", zalgo('r', 6), zalgo('m', 9));
+        assert_eq!(
+            CombiningFloodRule::default().flood(&line),
+            Some((3, 'r', 6, 2))
+        );
+        assert_eq!(
+            CombiningFloodRule::new(8).flood(&line),
+            Some((16, 'm', 9, 1))
+        );
+    }
+
+    #[test]
+    fn test_natural_diacritics_pass() {
+        // Vietnamese stacks a vowel mark and a tone mark.
+        assert_eq!(
+            CombiningFloodRule::default().flood("Tie\u{0302}\u{0301}ng Vie\u{0323}\u{0302}t"),
+            None
+        );
+        assert_eq!(CombiningFloodRule::default().flood(&zalgo('a', 4)), None);
+    }
+}
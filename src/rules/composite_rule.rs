@@ -1,4 +1,4 @@
-use crate::finding::{Finding, Location, Severity};
+use crate::finding::{Confidence, Finding, Location, Severity};
 use crate::rules::Rule;
 use crate::scanner::{FileType, ScannedFile};
 
@@ -50,6 +50,14 @@ impl Rule for DescriptionMismatchRule {
         &[FileType::Markdown]
     }
 
+    fn description(&self) -> &str {
+        "A skill that describes itself as something benign (a calculator, a formatter) but contains network access, process execution or sensitive file access."
+    }
+
+    fn remediation(&self) -> &str {
+        "Make the description match what the skill actually does, or remove the capability it does not need."
+    }
+
     fn check(&self, file: &ScannedFile) -> Vec<Finding> {
         let mut findings = Vec::new();
         let content_lower = file.content.to_lowercase();
@@ -78,6 +86,10 @@ impl Rule for DescriptionMismatchRule {
                         column: 1,
                     },
                     matched_text: pattern.to_string(),
+                    // Keyword overlap with the description, not a parse of it.
+                    confidence: Confidence::Medium,
+                    tags: Vec::new(),
+                    domain: None,
                 });
             }
         }
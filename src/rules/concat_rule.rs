@@ -0,0 +1,185 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::Rule;
+use crate::scanner::{FileType, ScannedFile};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Detects strings built from character codes or tiny literal fragments
+/// (`chr(99)+chr(117)`, `'cu'+'rl'`), which keep a command from ever appearing
+/// literally where pattern rules could match it. The assembled string is
+/// decoded and reported; it is an error when it looks like a command.
+pub struct ObfuscatedConcatRule;
+
+/// A quoted literal of at most three characters.
+const FRAGMENT: &str = r#"(?:"[^"\\\n]{1,3}"|'[^'\\\n]{1,3}')"#;
+
+static CHR_CHAIN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:chr\s*\(\s*\d+\s*\)\s*\+\s*){3,}chr\s*\(\s*\d+\s*\)").unwrap()
+});
+static CHAR_CODE_LIST: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:String\.fromCharCode\s*\(|bytes\s*\(\s*\[|bytearray\s*\(\s*\[|chr\([^)]*\)\s*for\s+\w+\s+in\s*\[)\s*(?:\d+\s*,\s*){3,}\d+",
+    )
+    .unwrap()
+});
+static FRAGMENT_CHAIN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"(?:{FRAGMENT}\s*\+\s*){{2,}}{FRAGMENT}")).unwrap());
+static FRAGMENT_JOIN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"\.join\s*\(\s*\[\s*(?:{FRAGMENT}\s*,\s*){{3,}}{FRAGMENT}\s*\]"
+    ))
+    .unwrap()
+});
+static NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
+static FRAGMENT_TEXT: LazyLock<Regex> = LazyLock::new(|| Regex::new(FRAGMENT).unwrap());
+/// Decoded strings that look like commands or code escalate to errors.
+static COMMAND_LIKE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)curl|wget|eval|exec|bash|/bin/sh|powershell|https?:|/dev/tcp|\brm\s|\bnc\s|base64|system|popen|subprocess|child_process|\.ssh").unwrap()
+});
+
+impl Rule for ObfuscatedConcatRule {
+    fn id(&self) -> &str {
+        "SL-HID-010"
+    }
+
+    fn name(&self) -> &str {
+        "Obfuscated String Assembly"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[FileType::Script, FileType::Markdown]
+    }
+
+    fn description(&self) -> &str {
+        "A string assembled from character codes or literal fragments of one to three characters, such as `chr(99)+chr(117)+chr(114)+chr(108)` or `'cu'+'rl'`. Building strings this way keeps commands out of reach of literal pattern matching. The finding shows the decoded string, and is an error when that string looks like a command."
+    }
+
+    fn remediation(&self) -> &str {
+        "Write the string literally. Legitimate code has no reason to spell out commands a character at a time."
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        vec!["obfuscation"]
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for (line_num, line) in file.content.lines().enumerate() {
+            for (start, matched, decoded) in assembled_strings(line) {
+                let severity = if COMMAND_LIKE.is_match(&decoded) {
+                    Severity::Error
+                } else {
+                    self.default_severity()
+                };
+                findings.push(Finding {
+                    rule_id: self.id().to_string(),
+                    rule_name: self.name().to_string(),
+                    severity,
+                    message: format!("String assembled from fragments decodes to {decoded:?}"),
+                    location: Location {
+                        file: file.relative_path.clone(),
+                        line: line_num + 1,
+                        column: start + 1,
+                    },
+                    matched_text: if matched.len() > 80 {
+                        format!("{}...", &matched[..77])
+                    } else {
+                        matched.to_string()
+                    },
+                    confidence: Confidence::High,
+                    tags: Vec::new(),
+                    domain: None,
+                });
+            }
+        }
+        findings
+    }
+}
+
+/// Each assembled string on a line: byte offset, matched source, decoded text.
+fn assembled_strings(line: &str) -> Vec<(usize, &str, String)> {
+    let mut found = Vec::new();
+    for m in CHR_CHAIN
+        .find_iter(line)
+        .chain(CHAR_CODE_LIST.find_iter(line))
+    {
+        if let Some(decoded) = decode_char_codes(m.as_str()) {
+            found.push((m.start(), m.as_str(), decoded));
+        }
+    }
+    for m in FRAGMENT_CHAIN
+        .find_iter(line)
+        .chain(FRAGMENT_JOIN.find_iter(line))
+    {
+        let decoded: String = FRAGMENT_TEXT
+            .find_iter(m.as_str())
+            .map(|f| &f.as_str()[1..f.as_str().len() - 1])
+            .collect();
+        found.push((m.start(), m.as_str(), decoded));
+    }
+    found.sort_by_key(|(start, _, _)| *start);
+    found
+}
+
+/// Printable ASCII from the numbers in `source`, or `None` if any number is
+/// outside that range (byte arrays of binary data are not text).
+fn decode_char_codes(source: &str) -> Option<String> {
+    NUMBER
+        .find_iter(source)
+        .map(|n| {
+            n.as_str()
+                .parse::<u8>()
+                .ok()
+                .filter(|b| (32..=126).contains(b))
+                .map(char::from)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoded(line: &str) -> Vec<String> {
+        assembled_strings(line)
+            .into_iter()
+            .map(|(_, _, d)| d)
+            .collect()
+    }
+
+    #[test]
+    fn test_decodes_char_code_chains() {
+        assert_eq!(
+            decoded("cmd = chr(99)+chr(117)+chr(114)+chr(108)"),
+            vec!["curl"]
+        );
+        assert_eq!(
+            decoded("const c = String.fromCharCode(101, 118, 97, 108);"),
+            vec!["eval"]
+        );
+        assert_eq!(
+            decoded("''.join(chr(c) for c in [119, 103, 101, 116])"),
+            vec!["wget"]
+        );
+    }
+
+    #[test]
+    fn test_decodes_fragment_chains() {
+        assert_eq!(decoded("x = 'cu' + 'r' + 'l' + ' -s'"), vec!["curl -s"]);
+        assert_eq!(
+            decoded(r#"p = "".join(["b", "a", "s", "h"])"#),
+            vec!["bash"]
+        );
+    }
+
+    #[test]
+    fn test_ignores_ordinary_code() {
+        assert!(decoded("label = 'Name: ' + user.name + '!'").is_empty());
+        assert!(decoded("data = bytes([0, 1, 2, 255])").is_empty());
+        assert!(decoded("a = 'x' + 'y'").is_empty());
+    }
+}
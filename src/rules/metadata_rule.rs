@@ -1,9 +1,19 @@
-use crate::finding::{Finding, Location, Severity};
-use crate::rules::Rule;
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::{Rule, RuleDoc};
 use crate::scanner::{FileType, ScannedFile};
 
 pub struct MetadataValidationRule;
 
+/// SL-META-002 is reported by this rule under its own ID.
+pub const MISSING_DESCRIPTION_DOC: RuleDoc = RuleDoc {
+    id: "SL-META-002",
+    name: "Missing Skill Description",
+    severity: Severity::Warning,
+    applies_to: &[FileType::Markdown, FileType::Yaml],
+    description: "Skill frontmatter without a `description` field. The agent picks skills by their description, so a skill without one is either broken or relying on its body to get loaded.",
+    remediation: "Add a `description` that says what the skill does and when to use it.",
+};
+
 const MAX_NAME_LENGTH: usize = 100;
 const MAX_DESCRIPTION_LENGTH: usize = 500;
 
@@ -24,6 +34,14 @@ impl Rule for MetadataValidationRule {
         &[FileType::Markdown, FileType::Yaml]
     }
 
+    fn description(&self) -> &str {
+        "A skill name or description in the frontmatter that exceeds the length the agent reliably reads. Metadata is what the agent uses to decide when to load a skill."
+    }
+
+    fn remediation(&self) -> &str {
+        "Shorten the `name` to 100 characters and the `description` to 500."
+    }
+
     fn check(&self, file: &ScannedFile) -> Vec<Finding> {
         let mut findings = Vec::new();
 
@@ -56,6 +74,9 @@ impl Rule for MetadataValidationRule {
                     column: 1,
                 },
                 matched_text: "---".to_string(),
+                confidence: Confidence::High,
+                tags: Vec::new(),
+                domain: None,
             });
         }
 
@@ -78,6 +99,9 @@ impl Rule for MetadataValidationRule {
                             column: 1,
                         },
                         matched_text: s.to_string(),
+                        confidence: Confidence::High,
+                        tags: Vec::new(),
+                        domain: None,
                     });
                 }
             }
@@ -102,6 +126,9 @@ impl Rule for MetadataValidationRule {
                             column: 1,
                         },
                         matched_text: format!("{}...", &s[..50.min(s.len())]),
+                        confidence: Confidence::High,
+                        tags: Vec::new(),
+                        domain: None,
                     });
                 }
             }
@@ -111,7 +138,7 @@ impl Rule for MetadataValidationRule {
     }
 }
 
-fn extract_frontmatter(content: &str) -> Option<String> {
+pub fn extract_frontmatter(content: &str) -> Option<String> {
     let content = content.trim_start();
     if !content.starts_with("---") {
         return None;
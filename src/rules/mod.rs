@@ -1,10 +1,82 @@
+pub mod combining_rule;
 pub mod composite_rule;
+pub mod concat_rule;
+pub mod frontmatter_rule;
+pub mod hook_rule;
 pub mod metadata_rule;
+pub mod normalize;
+pub mod quality_rule;
 pub mod regex_rule;
+pub mod repetition_rule;
+pub mod schema_rule;
+pub mod surface_rule;
 pub mod unicode_rule;
+pub mod url_rule;
 
-use crate::finding::{Finding, Severity};
+use crate::diagnostics::{self, Code, Diagnostic};
+use crate::finding::{Category, Finding, Severity};
 use crate::scanner::{FileType, ScannedFile};
+use regex_rule::ParamValue;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Built-in pattern files, embedded at compile time.
+pub const DEFAULT_PATTERN_FILES: &[(&str, &str)] = &[
+    ("hidden", include_str!("../../patterns/hidden.toml")),
+    ("secrets", include_str!("../../patterns/secrets.toml")),
+    ("network", include_str!("../../patterns/network.toml")),
+    ("filesystem", include_str!("../../patterns/filesystem.toml")),
+    ("execution", include_str!("../../patterns/execution.toml")),
+    ("injection", include_str!("../../patterns/injection.toml")),
+    ("social", include_str!("../../patterns/social.toml")),
+    ("metadata", include_str!("../../patterns/metadata.toml")),
+    ("container", include_str!("../../patterns/container.toml")),
+    ("cloud", include_str!("../../patterns/cloud.toml")),
+    ("keys", include_str!("../../patterns/keys.toml")),
+    ("bypass", include_str!("../../patterns/bypass.toml")),
+];
+
+/// Version of the built-in rule set: the tool version plus a short digest of
+/// the embedded pattern files, so pattern edits between releases are visible.
+pub fn ruleset_version() -> String {
+    version_with(&BTreeMap::new())
+}
+
+/// The built-in rule set version with the digests of custom pattern files
+/// folded in; without any it is `ruleset_version()`.
+fn version_with(custom: &BTreeMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    for (name, source) in DEFAULT_PATTERN_FILES {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(source.as_bytes());
+    }
+    for (name, digest) in custom {
+        hasher.update(b"custom/");
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(digest.as_bytes());
+    }
+    format!(
+        "{}+{}",
+        env!("CARGO_PKG_VERSION"),
+        short_hex(&hasher.finalize())
+    )
+}
+
+/// Short digest of each embedded pattern file, keyed by pattern set name.
+pub fn pattern_versions() -> BTreeMap<String, String> {
+    DEFAULT_PATTERN_FILES
+        .iter()
+        .map(|(name, source)| (name.to_string(), short_hex(&Sha256::digest(source))))
+        .collect()
+}
+
+fn short_hex(digest: &[u8]) -> String {
+    digest[..6].iter().map(|b| format!("{b:02x}")).collect()
+}
 
 pub trait Rule: Send + Sync {
     fn id(&self) -> &str;
@@ -12,15 +84,188 @@ pub trait Rule: Send + Sync {
     fn default_severity(&self) -> Severity;
     fn applies_to(&self) -> &[FileType];
     fn check(&self, file: &ScannedFile) -> Vec<Finding>;
+
+    /// What the rule looks for and why it matters.
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// How to fix what the rule reports.
+    fn remediation(&self) -> &str {
+        ""
+    }
+
+    /// Sample text the rule flags.
+    fn examples(&self) -> &[String] {
+        &[]
+    }
+
+    /// Taxonomy labels copied onto every finding the rule reports.
+    fn tags(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// The regular expression behind a pattern rule; `None` for rules
+    /// implemented in code.
+    fn pattern(&self) -> Option<&str> {
+        None
+    }
+
+    /// Tunable parameters and their current values.
+    fn params(&self) -> BTreeMap<String, ParamValue> {
+        BTreeMap::new()
+    }
+
+    /// Changes whenever what the rule reports for the same input may, so
+    /// cached results can be reused until then. Rules in code change with
+    /// the tool; rules with settings add them.
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    /// A copy of the rule with some parameters overridden.
+    fn with_params(
+        &self,
+        _overrides: &BTreeMap<String, ParamValue>,
+    ) -> Result<Box<dyn Rule>, String> {
+        Err(format!("rule {} takes no parameters", self.id()))
+    }
+}
+
+/// Documentation for a rule ID reported outside the registry: target-level
+/// checks, or a second ID emitted by a registered rule.
+pub struct RuleDoc {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub severity: Severity,
+    pub applies_to: &'static [FileType],
+    pub description: &'static str,
+    pub remediation: &'static str,
+}
+
+/// Everything documented about a rule, for `skill-issue rules`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleInfo {
+    pub id: String,
+    pub name: String,
+    pub severity: Severity,
+    pub category: Category,
+    /// File types the rule checks; empty means all.
+    pub applies_to: Vec<String>,
+    pub description: String,
+    pub remediation: String,
+    pub examples: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, ParamValue>,
+}
+
+impl RuleInfo {
+    fn from_doc(doc: &RuleDoc) -> Self {
+        RuleInfo {
+            id: doc.id.to_string(),
+            name: doc.name.to_string(),
+            severity: doc.severity,
+            category: Category::from_rule_id(doc.id),
+            applies_to: doc
+                .applies_to
+                .iter()
+                .map(|t| t.name().to_string())
+                .collect(),
+            description: doc.description.to_string(),
+            remediation: doc.remediation.to_string(),
+            examples: Vec::new(),
+            tags: Vec::new(),
+            pattern: None,
+            params: BTreeMap::new(),
+        }
+    }
+}
+
+/// Every rule skill-issue can report, sorted by ID.
+pub fn catalog(registry: &RuleRegistry) -> Vec<RuleInfo> {
+    let mut rules: Vec<RuleInfo> = registry
+        .all_rules()
+        .iter()
+        .map(|r| RuleInfo {
+            id: r.id().to_string(),
+            name: r.name().to_string(),
+            severity: r.default_severity(),
+            category: Category::from_rule_id(r.id()),
+            applies_to: r
+                .applies_to()
+                .iter()
+                .map(|t| t.name().to_string())
+                .collect(),
+            description: r.description().to_string(),
+            remediation: r.remediation().to_string(),
+            examples: r.examples().to_vec(),
+            tags: r.tags().into_iter().map(str::to_string).collect(),
+            pattern: r.pattern().map(str::to_string),
+            params: r.params(),
+        })
+        .collect();
+    let docs = [
+        &metadata_rule::MISSING_DESCRIPTION_DOC,
+        &schema_rule::COMPATIBILITY_DOC,
+        &schema_rule::DEPRECATED_KEY_DOC,
+        &crate::noise::RULE_DOC,
+        &crate::coverage::RULE_DOC,
+        &crate::coverage::FAILED_SKILL_RULE_DOC,
+        &crate::placeholder::RULE_DOC,
+    ]
+    .into_iter()
+    .chain(crate::signature::RULE_DOCS)
+    .chain(crate::duplicates::RULE_DOCS)
+    .chain(crate::plugin::RULE_DOCS)
+    .chain(crate::links::RULE_DOCS)
+    .chain(crate::freshness::RULE_DOCS)
+    .chain(crate::reputation::RULE_DOCS);
+    rules.extend(docs.map(RuleInfo::from_doc));
+    rules.sort_by(|a, b| rule_sort_key(&a.id).cmp(&rule_sort_key(&b.id)));
+    rules
 }
 
+/// Sort `SL-NET-010` after `SL-NET-009` and group by family.
+fn rule_sort_key(id: &str) -> (&str, u32) {
+    match id.rsplit_once('-') {
+        Some((family, n)) => (family, n.parse().unwrap_or(0)),
+        None => (id, 0),
+    }
+}
+
+#[derive(Default)]
 pub struct RuleRegistry {
     rules: Vec<Box<dyn Rule>>,
+    /// Short digest of each pattern file `load_dir` read, keyed by file stem.
+    custom_packs: BTreeMap<String, String>,
+    /// Built-in patterns that failed to load.
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl RuleRegistry {
     pub fn new() -> Self {
-        Self { rules: Vec::new() }
+        Self::default()
+    }
+
+    /// Version of the rules this registry was loaded with: the built-in rule
+    /// set plus any custom pattern files, so reports made with different
+    /// `--rules-dir` packs do not claim the same rule set.
+    pub fn ruleset_version(&self) -> String {
+        version_with(&self.custom_packs)
+    }
+
+    /// Problems loading the built-in patterns, for the scan's report.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Short digest of each custom pattern file, keyed by file stem.
+    pub fn custom_pattern_versions(&self) -> &BTreeMap<String, String> {
+        &self.custom_packs
     }
 
     pub fn register(&mut self, rule: Box<dyn Rule>) {
@@ -32,7 +277,7 @@ impl RuleRegistry {
             .iter()
             .filter(|r| {
                 let applies = r.applies_to();
-                applies.is_empty() || applies.contains(&file_type)
+                (applies.is_empty() && !file_type.is_view()) || applies.contains(&file_type)
             })
             .map(|r| r.as_ref())
             .collect()
@@ -42,27 +287,116 @@ impl RuleRegistry {
         &self.rules
     }
 
+    /// Drop the rules for which `keep` is false.
+    pub fn retain(&mut self, mut keep: impl FnMut(&dyn Rule) -> bool) {
+        self.rules.retain(|r| keep(r.as_ref()));
+    }
+
     pub fn load_defaults(&mut self) {
-        self.load_pattern_file(include_str!("../../patterns/hidden.toml"));
-        self.load_pattern_file(include_str!("../../patterns/secrets.toml"));
-        self.load_pattern_file(include_str!("../../patterns/network.toml"));
-        self.load_pattern_file(include_str!("../../patterns/filesystem.toml"));
-        self.load_pattern_file(include_str!("../../patterns/execution.toml"));
-        self.load_pattern_file(include_str!("../../patterns/injection.toml"));
-        self.load_pattern_file(include_str!("../../patterns/social.toml"));
-        self.load_pattern_file(include_str!("../../patterns/metadata.toml"));
+        for (_, source) in DEFAULT_PATTERN_FILES {
+            self.load_pattern_file(source);
+        }
 
         // Register specialized rules
         self.register(Box::new(unicode_rule::UnicodeRule));
         self.register(Box::new(metadata_rule::MetadataValidationRule));
         self.register(Box::new(composite_rule::DescriptionMismatchRule));
+        self.register(Box::new(hook_rule::HookCommandRule));
+        self.register(Box::new(surface_rule::InjectionSurfaceRule));
+        self.register(Box::new(concat_rule::ObfuscatedConcatRule));
+        self.register(Box::new(frontmatter_rule::FrontmatterInjectionRule));
+        self.register(Box::new(combining_rule::CombiningFloodRule::default()));
+        self.register(Box::new(schema_rule::FrontmatterSchemaRule::default()));
+        self.register(Box::new(repetition_rule::RepeatedInstructionRule));
+        self.register(Box::new(url_rule::UrlPolicyRule::default()));
+        self.register(Box::new(quality_rule::MissingHeadingRule));
+        self.register(Box::new(quality_rule::EmptySectionRule));
+        self.register(Box::new(quality_rule::UnfinishedMarkerRule));
+        self.register(Box::new(quality_rule::ShortInstructionsRule));
+    }
+
+    /// Override parameters of registered rules, per rule ID. Unknown rules,
+    /// rules without parameters and mistyped values are errors.
+    pub fn apply_params(
+        &mut self,
+        overrides: &[(&str, &BTreeMap<String, ParamValue>)],
+    ) -> Result<(), String> {
+        for (id, params) in overrides {
+            let Some(rule) = self.rules.iter().find(|r| r.id() == *id) else {
+                return Err(format!("parameters set for unknown rule {id}"));
+            };
+            let rule = rule.with_params(params)?;
+            self.replace(rule);
+        }
+        Ok(())
+    }
+
+    /// Swap in a reconfigured instance of an already registered rule.
+    pub fn replace(&mut self, rule: Box<dyn Rule>) {
+        match self.rules.iter_mut().find(|r| r.id() == rule.id()) {
+            Some(slot) => *slot = rule,
+            None => self.register(rule),
+        }
+    }
+
+    /// Load every `*.toml` pattern file in `dir`, in name order, next to the
+    /// rules already registered. Unlike the built-in files, any problem is an
+    /// error, including a rule ID that is already taken.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize, String> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| format!("{}: {e}", dir.display()))?
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        // Registered rules plus IDs reported outside the registry.
+        let taken: HashSet<String> = catalog(self).into_iter().map(|r| r.id).collect();
+        let mut origins: HashMap<String, PathBuf> = HashMap::new();
+        let mut loaded = 0;
+        for path in paths {
+            let text =
+                std::fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+            let file: regex_rule::PatternFile =
+                toml::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+            for def in file.rules {
+                let rule = regex_rule::RegexRule::from_definition(def)
+                    .map_err(|e| format!("{}: {e}", path.display()))?;
+                if let Some(other) = origins.get(&rule.id) {
+                    return Err(format!(
+                        "{}: rule {} is already defined in {}",
+                        path.display(),
+                        rule.id,
+                        other.display()
+                    ));
+                }
+                if taken.contains(&rule.id) {
+                    return Err(format!(
+                        "{}: rule {} collides with a built-in rule",
+                        path.display(),
+                        rule.id
+                    ));
+                }
+                origins.insert(rule.id.clone(), path.clone());
+                self.register(Box::new(rule));
+                loaded += 1;
+            }
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            self.custom_packs
+                .insert(stem.into_owned(), short_hex(&Sha256::digest(&text)));
+        }
+        Ok(loaded)
     }
 
     fn load_pattern_file(&mut self, toml_str: &str) {
         let file: regex_rule::PatternFile = match toml::from_str(toml_str) {
             Ok(f) => f,
             Err(e) => {
-                eprintln!("warning: failed to parse pattern file: {e}");
+                self.diagnostics.push(diagnostics::warn(
+                    Code::PatternFileInvalid,
+                    format!("failed to parse pattern file: {e}"),
+                ));
                 return;
             }
         };
@@ -70,7 +404,10 @@ impl RuleRegistry {
         for def in file.rules {
             match regex_rule::RegexRule::from_definition(def) {
                 Ok(rule) => self.register(Box::new(rule)),
-                Err(e) => eprintln!("warning: failed to compile rule: {e}"),
+                Err(e) => self.diagnostics.push(diagnostics::warn(
+                    Code::RuleInvalid,
+                    format!("failed to compile rule: {e}"),
+                )),
             }
         }
     }
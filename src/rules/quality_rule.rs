@@ -0,0 +1,351 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::Rule;
+use crate::scanner::{FileType, ScannedFile};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Prefix of the opt-in quality rules, which only run with `--quality`.
+pub const RULE_PREFIX: &str = "SL-QUAL-";
+
+/// A body with fewer words than this is too thin to guide the agent.
+const MIN_INSTRUCTION_WORDS: usize = 30;
+
+static MARKER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(?:TODO|FIXME|TBD)\b").unwrap());
+
+/// One line of a SKILL.md body, after the frontmatter.
+struct BodyLine<'a> {
+    /// 1-based line number in the file.
+    number: usize,
+    text: &'a str,
+    in_code: bool,
+}
+
+impl BodyLine<'_> {
+    /// The level of an ATX heading (`## Usage` is 2), outside code blocks.
+    fn heading_level(&self) -> Option<usize> {
+        if self.in_code {
+            return None;
+        }
+        let trimmed = self.text.trim_start();
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        let rest = &trimmed[level..];
+        ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with([' ', '\t'])))
+            .then_some(level)
+    }
+}
+
+/// Body lines of a SKILL.md file, or nothing for any other file. Fence lines
+/// themselves count as code.
+fn skill_body(file: &ScannedFile) -> Vec<BodyLine<'_>> {
+    let is_skill_md = file
+        .relative_path
+        .file_name()
+        .is_some_and(|n| n.to_string_lossy().eq_ignore_ascii_case("SKILL.md"));
+    if !is_skill_md {
+        return Vec::new();
+    }
+    let lines: Vec<&str> = file.content.lines().collect();
+    let start = match lines.first() {
+        Some(first) if first.trim_end() == "---" => lines
+            .iter()
+            .skip(1)
+            .position(|l| l.trim_end() == "---")
+            .map_or(0, |end| end + 2),
+        _ => 0,
+    };
+    let mut in_code = false;
+    lines
+        .iter()
+        .enumerate()
+        .skip(start)
+        .map(|(i, text)| {
+            let fence =
+                text.trim_start().starts_with("```") || text.trim_start().starts_with("~~~");
+            if fence {
+                in_code = !in_code;
+            }
+            BodyLine {
+                number: i + 1,
+                text,
+                in_code: in_code || fence,
+            }
+        })
+        .collect()
+}
+
+fn finding(
+    rule: &dyn Rule,
+    file: &ScannedFile,
+    line: usize,
+    message: String,
+    matched: &str,
+) -> Finding {
+    Finding {
+        rule_id: rule.id().to_string(),
+        rule_name: rule.name().to_string(),
+        severity: rule.default_severity(),
+        message,
+        location: Location {
+            file: file.relative_path.clone(),
+            line,
+            column: 1,
+        },
+        matched_text: matched.to_string(),
+        confidence: Confidence::High,
+        tags: Vec::new(),
+        domain: None,
+    }
+}
+
+/// A SKILL.md body with no Markdown heading at all.
+pub struct MissingHeadingRule;
+
+impl Rule for MissingHeadingRule {
+    fn id(&self) -> &str {
+        "SL-QUAL-001"
+    }
+
+    fn name(&self) -> &str {
+        "Missing Heading"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[FileType::Markdown]
+    }
+
+    fn description(&self) -> &str {
+        "A SKILL.md body without a single Markdown heading. Marketplace reviewers and agents alike navigate skills by their sections; an unstructured wall of text is hard to review and easy to misread. Opt-in with `--quality`."
+    }
+
+    fn remediation(&self) -> &str {
+        "Add a title heading and split the instructions into sections such as `## When to use` and `## Steps`."
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        let body = skill_body(file);
+        let Some(first) = body.iter().find(|l| !l.text.trim().is_empty()) else {
+            return Vec::new();
+        };
+        if body.iter().any(|l| l.heading_level().is_some()) {
+            return Vec::new();
+        }
+        vec![finding(
+            self,
+            file,
+            first.number,
+            "SKILL.md body has no Markdown headings".to_string(),
+            first.text.trim(),
+        )]
+    }
+}
+
+/// A heading with nothing under it before the next heading at the same or
+/// a higher level.
+pub struct EmptySectionRule;
+
+impl Rule for EmptySectionRule {
+    fn id(&self) -> &str {
+        "SL-QUAL-002"
+    }
+
+    fn name(&self) -> &str {
+        "Empty Section"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[FileType::Markdown]
+    }
+
+    fn description(&self) -> &str {
+        "A SKILL.md heading with no content before the next heading at the same or a higher level: usually a section left unfinished. Opt-in with `--quality`."
+    }
+
+    fn remediation(&self) -> &str {
+        "Write the section or remove the heading."
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        let body = skill_body(file);
+        let mut findings = Vec::new();
+        for (i, line) in body.iter().enumerate() {
+            let Some(level) = line.heading_level() else {
+                continue;
+            };
+            let next = body[i + 1..].iter().find(|l| !l.text.trim().is_empty());
+            let empty = match next {
+                None => true,
+                Some(next) => next.heading_level().is_some_and(|n| n <= level),
+            };
+            if empty {
+                let heading = line.text.trim();
+                findings.push(finding(
+                    self,
+                    file,
+                    line.number,
+                    format!("Section `{heading}` is empty"),
+                    heading,
+                ));
+            }
+        }
+        findings
+    }
+}
+
+/// TODO, FIXME and TBD left in a SKILL.md.
+pub struct UnfinishedMarkerRule;
+
+impl Rule for UnfinishedMarkerRule {
+    fn id(&self) -> &str {
+        "SL-QUAL-003"
+    }
+
+    fn name(&self) -> &str {
+        "Unfinished Marker"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[FileType::Markdown]
+    }
+
+    fn description(&self) -> &str {
+        "A TODO, FIXME or TBD marker in SKILL.md, including its code blocks. The agent reads these as part of its instructions, and they signal a skill that is not ready to publish. Opt-in with `--quality`."
+    }
+
+    fn remediation(&self) -> &str {
+        "Finish the work the marker points to and remove it, or track it outside the skill."
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        skill_body(file)
+            .iter()
+            .filter_map(|line| {
+                let m = MARKER.find(line.text)?;
+                let mut f = finding(
+                    self,
+                    file,
+                    line.number,
+                    format!("Unfinished {} marker: {}", m.as_str(), line.text.trim()),
+                    m.as_str(),
+                );
+                f.location.column = m.start() + 1;
+                Some(f)
+            })
+            .collect()
+    }
+}
+
+/// A SKILL.md body too short to tell the agent what to do.
+pub struct ShortInstructionsRule;
+
+impl Rule for ShortInstructionsRule {
+    fn id(&self) -> &str {
+        "SL-QUAL-004"
+    }
+
+    fn name(&self) -> &str {
+        "Short Instructions"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[FileType::Markdown]
+    }
+
+    fn description(&self) -> &str {
+        "A SKILL.md body with fewer than 30 words outside headings and code blocks. Instructions that thin leave the agent guessing at when and how to use the skill. Opt-in with `--quality`."
+    }
+
+    fn remediation(&self) -> &str {
+        "Say when the skill applies, the steps to follow, and what a good result looks like."
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        let body = skill_body(file);
+        let Some(first) = body.first() else {
+            return Vec::new();
+        };
+        let words: usize = body
+            .iter()
+            .filter(|l| !l.in_code && l.heading_level().is_none())
+            .map(|l| l.text.split_whitespace().count())
+            .sum();
+        if words >= MIN_INSTRUCTION_WORDS {
+            return Vec::new();
+        }
+        vec![finding(
+            self,
+            file,
+            first.number,
+            format!("SKILL.md has only {words} word(s) of instructions (expected at least {MIN_INSTRUCTION_WORDS})"),
+            "",
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn skill(content: &str) -> ScannedFile {
+        ScannedFile {
+            path: PathBuf::from("/s/SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: content.to_string(),
+        }
+    }
+
+    const WELL_FORMED: &str = "---\nname: fmt\ndescription: Formats text\n---\n# Formatter\n\n## When to use\n\nUse this skill when the user asks to tidy up prose, fix spacing, or normalize headings in a Markdown document before publishing it.\n\n## Steps\n\n1. Read the file.\n2. Apply the style guide and report every change you made.\n\n```sh\n# not a heading\n```\n";
+
+    #[test]
+    fn test_well_formed_skill_passes() {
+        let file = skill(WELL_FORMED);
+        assert!(MissingHeadingRule.check(&file).is_empty());
+        assert!(EmptySectionRule.check(&file).is_empty());
+        assert!(UnfinishedMarkerRule.check(&file).is_empty());
+        assert!(ShortInstructionsRule.check(&file).is_empty());
+    }
+
+    #[test]
+    fn test_flags_structure_problems() {
+        let file = skill("---\nname: fmt\n---\nFormat the text. TODO: explain how.\n");
+        let f = MissingHeadingRule.check(&file);
+        assert_eq!(f[0].location.line, 4);
+        let f = UnfinishedMarkerRule.check(&file);
+        assert_eq!((f[0].location.line, f[0].location.column), (4, 18));
+        let f = ShortInstructionsRule.check(&file);
+        assert!(f[0].message.contains("only 6 word(s)"));
+
+        // `## Steps` is empty; `# Tool` has a subsection, so it is not.
+        let file = skill("# Tool\n## Steps\n\n## Notes\nSee below.\n");
+        let f = EmptySectionRule.check(&file);
+        assert_eq!(f.len(), 1);
+        assert_eq!(f[0].message, "Section `## Steps` is empty");
+    }
+
+    #[test]
+    fn test_only_checks_skill_md() {
+        let file = ScannedFile {
+            relative_path: PathBuf::from("README.md"),
+            ..skill("TODO")
+        };
+        assert!(UnfinishedMarkerRule.check(&file).is_empty());
+        assert!(ShortInstructionsRule.check(&file).is_empty());
+    }
+}
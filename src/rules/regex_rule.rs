@@ -1,8 +1,61 @@
-use crate::finding::{Finding, Location, Severity};
+use crate::finding::{Confidence, Finding, Location, Severity};
 use crate::rules::Rule;
 use crate::scanner::{FileType, ScannedFile};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// `{{name}}` in a pattern template.
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{([A-Za-z_][A-Za-z0-9_]*)\}\}").unwrap());
+/// Stands in for an empty list, so an alternation of nothing matches nothing.
+const MATCH_NOTHING: &str = r"[^\s\S]";
+
+/// A tunable value a pattern rule declares under `[rules.params]`, and that
+/// `.skill-issue.toml` can override under `[rules.<ID>.params]`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    Number(i64),
+    Text(String),
+    List(Vec<String>),
+}
+
+impl ParamValue {
+    /// The value as it goes into a regex: numbers as digits, text escaped,
+    /// lists as an escaped alternation.
+    fn to_regex(&self) -> String {
+        match self {
+            ParamValue::Number(n) => n.to_string(),
+            ParamValue::Text(s) => regex::escape(s),
+            ParamValue::List(items) if items.is_empty() => MATCH_NOTHING.to_string(),
+            ParamValue::List(items) => {
+                let escaped: Vec<String> = items.iter().map(|i| regex::escape(i)).collect();
+                format!("(?:{})", escaped.join("|"))
+            }
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            ParamValue::Number(_) => "a number",
+            ParamValue::Text(_) => "a string",
+            ParamValue::List(_) => "a list of strings",
+        }
+    }
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Number(n) => write!(f, "{n}"),
+            ParamValue::Text(s) => write!(f, "{s:?}"),
+            ParamValue::List(items) => write!(f, "{items:?}"),
+        }
+    }
+}
 
 #[derive(Deserialize)]
 pub struct PatternFile {
@@ -10,7 +63,7 @@ pub struct PatternFile {
     pub rules: Vec<RuleDefinition>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct RuleDefinition {
     pub id: String,
     pub name: String,
@@ -21,6 +74,22 @@ pub struct RuleDefinition {
     pub message_template: String,
     #[serde(default)]
     pub multiline: bool,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub remediation: String,
+    #[serde(default)]
+    pub examples: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// `high` unless set; `low` for broad patterns that mostly flag things
+    /// worth a look.
+    pub confidence: Option<String>,
+    /// Matches whose text also matches this pattern are not reported.
+    pub exclude: Option<String>,
+    /// Defaults for the `{{name}}` placeholders in `pattern` and `exclude`.
+    #[serde(default)]
+    pub params: BTreeMap<String, ParamValue>,
 }
 
 pub struct RegexRule {
@@ -31,6 +100,14 @@ pub struct RegexRule {
     pub applies_to: Vec<FileType>,
     pub message_template: String,
     pub multiline: bool,
+    pub description: String,
+    pub remediation: String,
+    pub examples: Vec<String>,
+    pub tags: Vec<String>,
+    pub confidence: Confidence,
+    pub exclude: Option<Regex>,
+    /// The definition as loaded, so parameters can be overridden later.
+    definition: RuleDefinition,
 }
 
 fn parse_file_type(s: &str) -> Option<FileType> {
@@ -40,39 +117,93 @@ fn parse_file_type(s: &str) -> Option<FileType> {
         "yaml" | "yml" => Some(FileType::Yaml),
         "toml" => Some(FileType::Toml),
         "json" => Some(FileType::Json),
+        "dockerfile" | "containerfile" => Some(FileType::Dockerfile),
+        "markdown:frontmatter" => Some(FileType::MarkdownFrontmatter),
+        "markdown:code-blocks" => Some(FileType::MarkdownCode),
+        "markdown:text" => Some(FileType::MarkdownText),
         _ => None,
     }
 }
 
+/// `template` with each `{{name}}` replaced by its parameter value.
+fn expand(
+    id: &str,
+    template: &str,
+    params: &BTreeMap<String, ParamValue>,
+) -> Result<String, String> {
+    let mut unknown = None;
+    let expanded = PLACEHOLDER.replace_all(template, |caps: &regex::Captures| {
+        match params.get(&caps[1]) {
+            Some(value) => value.to_regex(),
+            None => {
+                unknown.get_or_insert_with(|| caps[1].to_string());
+                String::new()
+            }
+        }
+    });
+    match unknown {
+        Some(name) => Err(format!(
+            "rule {id}: pattern uses undeclared parameter {name}"
+        )),
+        None => Ok(expanded.into_owned()),
+    }
+}
+
 impl RegexRule {
     pub fn from_definition(def: RuleDefinition) -> Result<Self, String> {
         let severity: Severity = def.severity.parse()?;
+        let confidence: Confidence = match def.confidence {
+            Some(ref c) => c.parse().map_err(|e| format!("rule {}: {e}", def.id))?,
+            None => Confidence::High,
+        };
+        let source = expand(&def.id, &def.pattern, &def.params)?;
         let pattern = if def.multiline {
-            regex::RegexBuilder::new(&def.pattern)
+            regex::RegexBuilder::new(&source)
                 .multi_line(true)
                 .dot_matches_new_line(true)
                 .build()
         } else {
-            Regex::new(&def.pattern)
+            Regex::new(&source)
         }
         .map_err(|e| format!("rule {}: invalid regex: {e}", def.id))?;
+        let exclude = match def.exclude {
+            Some(ref template) => Some(
+                Regex::new(&expand(&def.id, template, &def.params)?)
+                    .map_err(|e| format!("rule {}: invalid exclude regex: {e}", def.id))?,
+            ),
+            None => None,
+        };
 
         let applies_to: Vec<FileType> = def
             .applies_to
             .iter()
-            .filter_map(|s| parse_file_type(s))
-            .collect();
+            .map(|s| {
+                parse_file_type(s)
+                    .ok_or_else(|| format!("rule {}: unknown applies_to file type {s:?}", def.id))
+            })
+            .collect::<Result<_, _>>()?;
 
         Ok(RegexRule {
-            id: def.id,
-            name: def.name,
+            id: def.id.clone(),
+            name: def.name.clone(),
             severity,
             pattern,
             applies_to,
-            message_template: def.message_template,
+            message_template: def.message_template.clone(),
             multiline: def.multiline,
+            description: def.description.clone(),
+            remediation: def.remediation.clone(),
+            examples: def.examples.clone(),
+            tags: def.tags.clone(),
+            confidence,
+            exclude,
+            definition: def,
         })
     }
+
+    fn is_excluded(&self, matched: &str) -> bool {
+        self.exclude.as_ref().is_some_and(|e| e.is_match(matched))
+    }
 }
 
 impl Rule for RegexRule {
@@ -92,11 +223,70 @@ impl Rule for RegexRule {
         &self.applies_to
     }
 
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn remediation(&self) -> &str {
+        &self.remediation
+    }
+
+    fn examples(&self) -> &[String] {
+        &self.examples
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        self.tags.iter().map(String::as_str).collect()
+    }
+
+    fn pattern(&self) -> Option<&str> {
+        Some(self.pattern.as_str())
+    }
+
+    fn params(&self) -> BTreeMap<String, ParamValue> {
+        self.definition.params.clone()
+    }
+
+    /// The tool version plus a digest of the definition, so an edited
+    /// pattern file or parameter override invalidates cached results.
+    fn version(&self) -> String {
+        let definition = serde_json::to_vec(&self.definition).unwrap_or_default();
+        format!(
+            "{}+{}",
+            env!("CARGO_PKG_VERSION"),
+            &crate::attestation::sha256_hex(&definition)[..12]
+        )
+    }
+
+    fn with_params(
+        &self,
+        overrides: &BTreeMap<String, ParamValue>,
+    ) -> Result<Box<dyn Rule>, String> {
+        let mut def = self.definition.clone();
+        for (name, value) in overrides {
+            let Some(default) = def.params.get(name) else {
+                return Err(format!("rule {} has no parameter {name}", self.id));
+            };
+            if std::mem::discriminant(default) != std::mem::discriminant(value) {
+                return Err(format!(
+                    "rule {} parameter {name} must be {}",
+                    self.id,
+                    default.kind()
+                ));
+            }
+            def.params.insert(name.clone(), value.clone());
+        }
+        Ok(Box::new(RegexRule::from_definition(def)?))
+    }
+
     fn check(&self, file: &ScannedFile) -> Vec<Finding> {
         let mut findings = Vec::new();
 
         if self.multiline {
             for mat in self.pattern.find_iter(&file.content) {
+                if self.is_excluded(mat.as_str()) {
+                    continue;
+                }
                 let line = file.content[..mat.start()].matches('\n').count() + 1;
                 let last_newline = file.content[..mat.start()].rfind('\n').map_or(0, |p| p + 1);
                 let column = mat.start() - last_newline + 1;
@@ -118,11 +308,17 @@ impl Rule for RegexRule {
                         column,
                     },
                     matched_text: display_match,
+                    confidence: self.confidence,
+                    tags: Vec::new(),
+                    domain: None,
                 });
             }
         } else {
             for (line_num, line) in file.content.lines().enumerate() {
                 for mat in self.pattern.find_iter(line) {
+                    if self.is_excluded(mat.as_str()) {
+                        continue;
+                    }
                     let matched = mat.as_str();
                     let display_match = if matched.len() > 80 {
                         format!("{}...", &matched[..77])
@@ -141,6 +337,9 @@ impl Rule for RegexRule {
                             column: mat.start() + 1,
                         },
                         matched_text: display_match,
+                        confidence: self.confidence,
+                        tags: Vec::new(),
+                        domain: None,
                     });
                 }
             }
@@ -149,3 +348,66 @@ impl Rule for RegexRule {
         findings
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn rule(pattern: &str, exclude: Option<&str>, params: &str) -> Result<RegexRule, String> {
+        let toml = format!(
+            "[[rules]]\nid = \"SL-T-001\"\nname = \"T\"\nseverity = \"warning\"\npattern = '{pattern}'\nmessage_template = \"{{match}}\"\n{}\n[rules.params]\n{params}\n",
+            exclude.map_or(String::new(), |e| format!("exclude = '{e}'"))
+        );
+        let mut file: PatternFile = toml::from_str(&toml).unwrap();
+        RegexRule::from_definition(file.rules.remove(0))
+    }
+
+    fn matches(rule: &dyn Rule, content: &str) -> Vec<String> {
+        rule.check(&ScannedFile {
+            path: PathBuf::from("SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: content.to_string(),
+        })
+        .into_iter()
+        .map(|f| f.matched_text)
+        .collect()
+    }
+
+    #[test]
+    fn test_params_fill_placeholders_and_can_be_overridden() {
+        let rule = rule(
+            r"https?://\S+",
+            Some(r"https?://{{allowed_domains}}/"),
+            "allowed_domains = []",
+        )
+        .unwrap();
+        let content = "https://example.com/a https://trusted.dev/b";
+        assert_eq!(matches(&rule, content).len(), 2);
+
+        let overrides = BTreeMap::from([(
+            "allowed_domains".to_string(),
+            ParamValue::List(vec!["trusted.dev".into()]),
+        )]);
+        let tuned = rule.with_params(&overrides).unwrap();
+        assert_eq!(matches(tuned.as_ref(), content), ["https://example.com/a"]);
+
+        let threshold = self::rule("[a-z]{{{min}},}", None, "min = 4").unwrap();
+        assert_eq!(matches(&threshold, "abc abcd"), ["abcd"]);
+    }
+
+    #[test]
+    fn test_param_errors() {
+        let err = rule("{{missing}}", None, "").err().unwrap();
+        assert!(err.contains("undeclared parameter missing"), "{err}");
+
+        let rule = rule("a{{{n}}}", None, "n = 2").unwrap();
+        let wrong_type = BTreeMap::from([("n".to_string(), ParamValue::Text("two".into()))]);
+        let err = rule.with_params(&wrong_type).err().unwrap();
+        assert_eq!(err, "rule SL-T-001 parameter n must be a number");
+        let unknown = BTreeMap::from([("m".to_string(), ParamValue::Number(1))]);
+        let err = rule.with_params(&unknown).err().unwrap();
+        assert_eq!(err, "rule SL-T-001 has no parameter m");
+    }
+}
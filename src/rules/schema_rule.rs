@@ -0,0 +1,284 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::metadata_rule::extract_frontmatter;
+use crate::rules::{Rule, RuleDoc};
+use crate::scanner::{FileType, ScannedFile};
+use regex::Regex;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::sync::LazyLock;
+
+/// Revision of the skill frontmatter schema to check against, set with
+/// `skill_spec` under `[settings]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum SkillSpec {
+    /// The original Claude skill format: top-level `version` and
+    /// `dependencies` are part of the schema.
+    #[serde(rename = "2025-10")]
+    V2025_10,
+    /// The Agent Skills specification: version moves under `metadata`, and
+    /// environment requirements go in `compatibility`.
+    #[default]
+    #[serde(rename = "2025-12")]
+    V2025_12,
+}
+
+impl SkillSpec {
+    /// Keys this revision replaced, with what to use instead.
+    fn deprecated_keys(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            SkillSpec::V2025_10 => &[],
+            SkillSpec::V2025_12 => &[
+                ("version", "`metadata.version`"),
+                ("dependencies", "`compatibility`"),
+            ],
+        }
+    }
+}
+
+/// SL-META-014 is reported by this rule under its own ID.
+pub const COMPATIBILITY_DOC: RuleDoc = RuleDoc {
+    id: "SL-META-014",
+    name: "Invalid Compatibility Declaration",
+    severity: Severity::Warning,
+    applies_to: &[FileType::Markdown],
+    description: "A `compatibility` field that is not a short string, or a `model` that is not a model name (`sonnet`, `inherit`, `claude-sonnet-4-5`). Tools that pick an environment or model from the frontmatter reject or misread such values.",
+    remediation: "Give `compatibility` as one sentence of at most 500 characters, and `model` as a single model name or alias.",
+};
+
+/// SL-META-015 is reported by this rule under its own ID.
+pub const DEPRECATED_KEY_DOC: RuleDoc = RuleDoc {
+    id: "SL-META-015",
+    name: "Deprecated Frontmatter Key",
+    severity: Severity::Info,
+    applies_to: &[FileType::Markdown],
+    description: "A frontmatter key the configured skill spec (`skill_spec` under `[settings]`, default 2025-12) has replaced, such as top-level `version` or `dependencies`. Newer tools may ignore it.",
+    remediation: "Move the value to the key named in the message, or set `skill_spec` to the revision the skill targets.",
+};
+
+const MAX_COMPATIBILITY_LENGTH: usize = 500;
+
+/// Semantic version, optionally with a leading `v`.
+static SEMVER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^v?(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?$",
+    )
+    .unwrap()
+});
+static MODEL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z0-9][a-z0-9.@:_-]*$").unwrap());
+
+/// Checks frontmatter against a revision of the skill schema: version
+/// format, compatibility and model declarations, and deprecated keys.
+#[derive(Default)]
+pub struct FrontmatterSchemaRule {
+    spec: SkillSpec,
+}
+
+impl FrontmatterSchemaRule {
+    pub fn new(spec: SkillSpec) -> Self {
+        Self { spec }
+    }
+
+    fn finding(
+        &self,
+        file: &ScannedFile,
+        rule: (&str, &str, Severity),
+        key: &str,
+        message: String,
+    ) -> Finding {
+        let (rule_id, rule_name, severity) = rule;
+        let (line, matched_text) = key_line(&file.content, key);
+        Finding {
+            rule_id: rule_id.to_string(),
+            rule_name: rule_name.to_string(),
+            severity,
+            message,
+            location: Location {
+                file: file.relative_path.clone(),
+                line,
+                column: 1,
+            },
+            matched_text,
+            confidence: Confidence::High,
+            tags: Vec::new(),
+            domain: None,
+        }
+    }
+}
+
+impl Rule for FrontmatterSchemaRule {
+    fn id(&self) -> &str {
+        "SL-META-013"
+    }
+
+    fn name(&self) -> &str {
+        "Invalid Skill Version"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[FileType::Markdown]
+    }
+
+    fn version(&self) -> String {
+        format!("{}+{:?}", env!("CARGO_PKG_VERSION"), self.spec)
+    }
+
+    fn description(&self) -> &str {
+        "A skill `version` (top-level or `metadata.version`) that is not a semantic version such as `1.4.0`. Registries and update checks compare versions; `1.2` or `latest` cannot be ordered. YAML also reads an unquoted `1.10` as the number 1.1."
+    }
+
+    fn remediation(&self) -> &str {
+        "Use MAJOR.MINOR.PATCH, quoted if needed (`version: \"1.10.0\"`)."
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        let Some(fm) = extract_frontmatter(&file.content) else {
+            return Vec::new();
+        };
+        let Ok(yaml) = serde_yaml::from_str::<Value>(&fm) else {
+            return Vec::new();
+        };
+        if !yaml.is_mapping() {
+            return Vec::new();
+        }
+        let mut findings = Vec::new();
+
+        let version = yaml
+            .get("version")
+            .or_else(|| yaml.get("metadata").and_then(|m| m.get("version")));
+        if let Some(version) = version {
+            let text = scalar_text(version);
+            if !text.as_deref().is_some_and(|v| SEMVER.is_match(v)) {
+                let message = match text {
+                    Some(v) => format!("Skill version `{v}` is not a semantic version"),
+                    None => "Skill version is not a string".to_string(),
+                };
+                let rule = (self.id(), self.name(), self.default_severity());
+                findings.push(self.finding(file, rule, "version", message));
+            }
+        }
+
+        let compatibility_rule = (
+            COMPATIBILITY_DOC.id,
+            COMPATIBILITY_DOC.name,
+            COMPATIBILITY_DOC.severity,
+        );
+        if let Some(compatibility) = yaml.get("compatibility") {
+            let problem = match compatibility.as_str() {
+                None => Some("is not a string".to_string()),
+                Some(s) if s.trim().is_empty() => Some("is empty".to_string()),
+                Some(s) if s.chars().count() > MAX_COMPATIBILITY_LENGTH => Some(format!(
+                    "exceeds {MAX_COMPATIBILITY_LENGTH} characters ({} chars)",
+                    s.chars().count()
+                )),
+                Some(_) => None,
+            };
+            if let Some(problem) = problem {
+                let message = format!("Skill `compatibility` {problem}");
+                findings.push(self.finding(file, compatibility_rule, "compatibility", message));
+            }
+        }
+        if let Some(model) = yaml.get("model") {
+            if !model.as_str().is_some_and(|m| MODEL.is_match(m)) {
+                let message = match model.as_str() {
+                    Some(m) => format!("Skill `model` `{m}` is not a model name"),
+                    None => "Skill `model` is not a single model name".to_string(),
+                };
+                findings.push(self.finding(file, compatibility_rule, "model", message));
+            }
+        }
+
+        let deprecated_rule = (
+            DEPRECATED_KEY_DOC.id,
+            DEPRECATED_KEY_DOC.name,
+            DEPRECATED_KEY_DOC.severity,
+        );
+        for (key, replacement) in self.spec.deprecated_keys() {
+            if yaml.get(key).is_some() {
+                let message = format!("Frontmatter key `{key}` is deprecated; use {replacement}");
+                findings.push(self.finding(file, deprecated_rule, key, message));
+            }
+        }
+        findings
+    }
+}
+
+/// A scalar as the text it was written as, where YAML allows recovering it.
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// 1-based line and text of the first frontmatter line setting `key`
+/// (at any indentation), or line 1 when it cannot be found.
+fn key_line(content: &str, key: &str) -> (usize, String) {
+    let prefix = format!("{key}:");
+    content
+        .lines()
+        .enumerate()
+        .skip(1)
+        .take_while(|(_, line)| line.trim_end() != "---")
+        .find(|(_, line)| line.trim_start().starts_with(&prefix))
+        .map(|(i, line)| (i + 1, line.trim().to_string()))
+        .unwrap_or((1, "---".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn check(spec: SkillSpec, frontmatter: &str) -> Vec<Finding> {
+        FrontmatterSchemaRule::new(spec).check(&ScannedFile {
+            path: PathBuf::from("SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: format!("---\n{frontmatter}\n---\n# Body\n"),
+        })
+    }
+
+    fn ids(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.rule_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_flags_non_semver_versions() {
+        let f = check(SkillSpec::V2025_12, "name: fmt\nmetadata:\n  version: 1.2");
+        assert_eq!(ids(&f), ["SL-META-013"]);
+        assert_eq!(f[0].location.line, 4);
+        assert!(f[0].message.contains("`1.2`"));
+        assert!(check(SkillSpec::V2025_12, "metadata:\n  version: \"1.10.0-rc.1\"").is_empty());
+        assert!(check(SkillSpec::V2025_10, "version: v2.0.0").is_empty());
+    }
+
+    #[test]
+    fn test_flags_bad_compatibility_and_model() {
+        let f = check(
+            SkillSpec::V2025_12,
+            "compatibility: [python, node]\nmodel: Claude Sonnet please",
+        );
+        assert_eq!(ids(&f), ["SL-META-014", "SL-META-014"]);
+        assert!(f[0].message.contains("is not a string"));
+        assert_eq!(f[1].location.line, 3);
+        assert!(check(
+            SkillSpec::V2025_12,
+            "compatibility: Requires git and network access\nmodel: claude-sonnet-4-5"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_deprecated_keys_depend_on_spec() {
+        let frontmatter = "name: fmt\nversion: 1.0.0\ndependencies: [jq]";
+        let f = check(SkillSpec::V2025_12, frontmatter);
+        assert_eq!(ids(&f), ["SL-META-015", "SL-META-015"]);
+        assert!(f[0].message.contains("use `metadata.version`"));
+        assert!(check(SkillSpec::V2025_10, frontmatter).is_empty());
+    }
+}
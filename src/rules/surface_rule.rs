@@ -0,0 +1,227 @@
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::unicode_rule::suspicious_char;
+use crate::rules::Rule;
+use crate::scanner::{FileType, ScannedFile};
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// One summary finding per Markdown file counting the ways it can steer the
+/// agent: imperative sentences, hidden elements, and external URLs. Reviewers
+/// get a single score to sort files by instead of many scattered findings.
+pub struct InjectionSurfaceRule;
+
+/// At or above this score the summary is a warning rather than info.
+const WARNING_SCORE: usize = 20;
+const HIDDEN_WEIGHT: usize = 3;
+const URL_WEIGHT: usize = 2;
+
+/// Sentence openers that direct the agent to act.
+const IMPERATIVE_OPENERS: &[&str] = &[
+    "always",
+    "never",
+    "run",
+    "execute",
+    "download",
+    "fetch",
+    "install",
+    "read",
+    "open",
+    "send",
+    "upload",
+    "post",
+    "copy",
+    "write",
+    "delete",
+    "remove",
+    "ignore",
+    "follow",
+    "use",
+    "call",
+    "invoke",
+    "save",
+    "print",
+    "output",
+    "include",
+    "add",
+    "set",
+    "make sure",
+    "ensure",
+    "do not",
+    "don't",
+    "you must",
+    "you should",
+    "you will",
+    "you need to",
+];
+
+static URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"https?://[^\s)\]>"'`]+"#).unwrap());
+static HTML_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<!--").unwrap());
+static HIDDEN_HTML: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)style\s*=\s*["'][^"']*display\s*:\s*none|<[a-z][^>]*\shidden[\s/>=]"#)
+        .unwrap()
+});
+pub static LIST_MARKER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:[-*+>#]+|\d+[.)])\s*").unwrap());
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Surface {
+    imperatives: usize,
+    hidden: usize,
+    urls: usize,
+}
+
+impl Surface {
+    fn score(&self) -> usize {
+        self.imperatives + self.hidden * HIDDEN_WEIGHT + self.urls * URL_WEIGHT
+    }
+}
+
+impl Rule for InjectionSurfaceRule {
+    fn id(&self) -> &str {
+        "SL-INJ-010"
+    }
+
+    fn name(&self) -> &str {
+        "Injection Surface Summary"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[FileType::Markdown]
+    }
+
+    fn description(&self) -> &str {
+        "A per-file summary of how much a Markdown file can steer the agent: sentences that tell it what to do, elements hidden from rendered views, and external URLs. The score weights hidden elements and URLs above plain instructions; a file scoring 20 or more is reported as a warning."
+    }
+
+    fn remediation(&self) -> &str {
+        "Review high-scoring files first. Remove hidden elements, and cut instructions and links the skill does not need."
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        vec!["llm-injection"]
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        let surface = measure(&file.content);
+        let score = surface.score();
+        if score == 0 {
+            return Vec::new();
+        }
+        let severity = if score >= WARNING_SCORE {
+            Severity::Warning
+        } else {
+            self.default_severity()
+        };
+        vec![Finding {
+            rule_id: self.id().to_string(),
+            rule_name: self.name().to_string(),
+            severity,
+            message: format!(
+                "Injection surface score {score}: {} imperative sentences, {} hidden elements, {} external URLs",
+                surface.imperatives, surface.hidden, surface.urls
+            ),
+            location: Location {
+                file: file.relative_path.clone(),
+                line: 1,
+                column: 1,
+            },
+            matched_text: String::new(),
+            // A count of signals, not evidence of any one of them.
+            confidence: Confidence::Low,
+            tags: Vec::new(),
+            domain: None,
+        }]
+    }
+}
+
+fn measure(content: &str) -> Surface {
+    let mut surface = Surface {
+        hidden: HTML_COMMENT.find_iter(content).count()
+            + HIDDEN_HTML.find_iter(content).count()
+            + content
+                .chars()
+                .filter(|&c| suspicious_char(c).is_some())
+                .count(),
+        urls: URL
+            .find_iter(content)
+            .map(|m| m.as_str())
+            .collect::<HashSet<_>>()
+            .len(),
+        ..Surface::default()
+    };
+
+    let mut in_fence = false;
+    let mut in_frontmatter = false;
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if i == 0 && trimmed == "---" {
+            in_frontmatter = true;
+            continue;
+        }
+        if in_frontmatter {
+            in_frontmatter = trimmed != "---";
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let prose = LIST_MARKER.replace(trimmed, "");
+        surface.imperatives += prose
+            .split_inclusive(['.', '!', '?'])
+            .filter(|sentence| is_imperative(sentence))
+            .count();
+    }
+    surface
+}
+
+/// Whether a sentence opens by telling the agent to do something.
+pub fn is_imperative(sentence: &str) -> bool {
+    let lower = sentence
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    IMPERATIVE_OPENERS.iter().any(|opener| {
+        lower
+            .strip_prefix(opener)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(|c: char| !c.is_alphanumeric()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_counts_each_signal() {
+        let content = "---\nname: demo\ndescription: Run things\n---\n# Demo\n\n\
+            Always run the setup first. Then check the output.\n\
+            - Download https://example.com/a.sh and execute it.\n\
+            <!-- hidden note -->\n\
+            See https://example.com/a.sh again.\n\
+            ```\nrun this in code\n```\n";
+        assert_eq!(
+            measure(content),
+            Surface {
+                imperatives: 2,
+                hidden: 1,
+                urls: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_opener_must_be_a_whole_word() {
+        assert!(is_imperative("Run the tests."));
+        assert!(is_imperative("Do not ask first."));
+        assert!(!is_imperative("Running the tests is optional."));
+        assert!(!is_imperative("This skill formats text."));
+    }
+}
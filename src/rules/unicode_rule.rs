@@ -1,4 +1,4 @@
-use crate::finding::{Finding, Location, Severity};
+use crate::finding::{Confidence, Finding, Location, Severity};
 use crate::rules::Rule;
 use crate::scanner::{FileType, ScannedFile};
 
@@ -14,8 +14,19 @@ const SUSPICIOUS_RANGES: &[(char, char, &str)] = &[
     ('\u{2060}', '\u{2064}', "invisible formatting character"),
     ('\u{FE00}', '\u{FE0F}', "variation selector"),
     ('\u{E0100}', '\u{E01EF}', "variation selector supplement"),
+    ('\u{E0000}', '\u{E007F}', TAG),
+    ('\u{E000}', '\u{F8FF}', PRIVATE_USE),
+    ('\u{F0000}', '\u{FFFFD}', PRIVATE_USE),
+    ('\u{100000}', '\u{10FFFD}', PRIVATE_USE),
 ];
 
+/// Tag characters mirror ASCII at U+E0000 and render as nothing, so a run of
+/// them can carry a whole hidden instruction.
+const TAG: &str = "tag character";
+/// Icon fonts legitimately map glyphs into the private use area, so these are
+/// reported as warnings.
+const PRIVATE_USE: &str = "private-use character";
+
 impl Rule for UnicodeRule {
     fn id(&self) -> &str {
         "SL-HID-001"
@@ -33,39 +44,138 @@ impl Rule for UnicodeRule {
         &[] // all file types
     }
 
+    fn description(&self) -> &str {
+        "Invisible and bidirectional Unicode characters (zero-width spaces, direction overrides, variation selectors, tag characters) that make text read differently to the model than to a human reviewer. Runs of tag characters are decoded to the ASCII they hide. Private-use characters are reported as warnings, since icon fonts use them legitimately."
+    }
+
+    fn remediation(&self) -> &str {
+        "Delete the characters. Most editors can reveal them with a 'show invisibles' setting; `cat -A` shows them in a terminal."
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        vec!["obfuscation", "llm-injection"]
+    }
+
     fn check(&self, file: &ScannedFile) -> Vec<Finding> {
         let mut findings = Vec::new();
 
         for (line_num, line) in file.content.lines().enumerate() {
-            for (col, ch) in line.chars().enumerate() {
+            let offsets: Vec<usize> = line.char_indices().map(|(i, _)| i).collect();
+            let chars: Vec<char> = line.chars().collect();
+            let mut col = 0;
+            while col < chars.len() {
+                let ch = chars[col];
                 // Skip BOM at very start of file
                 if line_num == 0 && col == 0 && ch == '\u{FEFF}' {
+                    col += 1;
                     continue;
                 }
+                let Some(desc) = suspicious_char(ch) else {
+                    col += 1;
+                    continue;
+                };
 
-                for &(start, end, desc) in SUSPICIOUS_RANGES {
-                    if ch >= start && ch <= end {
-                        findings.push(Finding {
-                            rule_id: self.id().to_string(),
-                            rule_name: self.name().to_string(),
-                            severity: self.default_severity(),
-                            message: format!(
-                                "Found {} (U+{:04X}) in file content",
-                                desc, ch as u32
-                            ),
-                            location: Location {
-                                file: file.relative_path.clone(),
-                                line: line_num + 1,
-                                column: col + 1,
-                            },
-                            matched_text: format!("U+{:04X}", ch as u32),
-                        });
-                        break;
-                    }
-                }
+                let (message, matched_text, len) = if desc == TAG {
+                    // Report a run of tag characters once, with the text it spells.
+                    let run = chars[col..]
+                        .iter()
+                        .take_while(|&&c| suspicious_char(c) == Some(TAG))
+                        .count();
+                    let hidden = decode_tags(&chars[col..col + run]);
+                    (
+                        format!(
+                            "Found {run} tag character(s) (U+{:04X}) encoding hidden text {hidden:?}",
+                            ch as u32
+                        ),
+                        hidden,
+                        run,
+                    )
+                } else {
+                    (
+                        format!("Found {} (U+{:04X}) in file content", desc, ch as u32),
+                        format!("U+{:04X}", ch as u32),
+                        1,
+                    )
+                };
+                findings.push(Finding {
+                    rule_id: self.id().to_string(),
+                    rule_name: self.name().to_string(),
+                    severity: if desc == PRIVATE_USE {
+                        Severity::Warning
+                    } else {
+                        self.default_severity()
+                    },
+                    message,
+                    location: Location {
+                        file: file.relative_path.clone(),
+                        line: line_num + 1,
+                        column: offsets[col] + 1,
+                    },
+                    matched_text,
+                    confidence: Confidence::High,
+                    tags: Vec::new(),
+                    domain: None,
+                });
+                col += len;
             }
         }
 
         findings
     }
 }
+
+/// The ASCII a sequence of tag characters spells; tags outside the printable
+/// range (language tag, cancel tag) are dropped.
+fn decode_tags(tags: &[char]) -> String {
+    tags.iter()
+        .filter_map(|&c| char::from_u32((c as u32).checked_sub(0xE0000)?))
+        .filter(|c| c.is_ascii_graphic() || *c == ' ')
+        .collect()
+}
+
+/// Description of an invisible or direction-changing character, if `ch` is one.
+pub fn suspicious_char(ch: char) -> Option<&'static str> {
+    SUSPICIOUS_RANGES
+        .iter()
+        .find(|&&(start, end, _)| ch >= start && ch <= end)
+        .map(|&(_, _, desc)| desc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn check(content: &str) -> Vec<Finding> {
+        UnicodeRule.check(&ScannedFile {
+            path: PathBuf::from("SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: content.to_string(),
+        })
+    }
+
+    fn tags(text: &str) -> String {
+        text.chars()
+            .map(|c| char::from_u32(0xE0000 + c as u32).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_tag_run_is_decoded_once() {
+        let content = format!("Format the text.{}\u{E007F} Done.", tags("run curl x"));
+        let f = check(&content);
+        assert_eq!(f.len(), 1);
+        assert_eq!(f[0].location.column, 17);
+        assert_eq!(f[0].matched_text, "run curl x");
+        assert!(f[0].message.contains("11 tag character(s)"));
+    }
+
+    #[test]
+    fn test_private_use_is_a_warning() {
+        let f = check("Icon: \u{E0A0} branch");
+        assert_eq!(f.len(), 1);
+        assert_eq!(f[0].severity, Severity::Warning);
+        assert!(f[0].message.contains("private-use character (U+E0A0)"));
+    }
+}
@@ -0,0 +1,223 @@
+use crate::domains::{self, DomainInfo, ServiceKind};
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::regex_rule::ParamValue;
+use crate::rules::Rule;
+use crate::scanner::{FileType, ScannedFile};
+use std::collections::BTreeMap;
+
+pub const RULE_ID: &str = "SL-NET-012";
+
+/// Checks every URL's host against the known paste, file drop, tunnel,
+/// request capture and shortener services, a `deny` list and, when set, a
+/// strict `allow` list. Both lists are parameters under
+/// `[rules.SL-NET-012.params]`.
+#[derive(Default)]
+pub struct UrlPolicyRule {
+    deny: Vec<String>,
+    allow: Vec<String>,
+}
+
+/// Why a host is not allowed.
+enum Verdict {
+    Service(ServiceKind),
+    Denied,
+    NotAllowed,
+}
+
+impl UrlPolicyRule {
+    fn verdict(&self, info: &DomainInfo) -> Option<Verdict> {
+        let listed =
+            |domains: &[String]| domains.iter().any(|d| domains::host_matches(&info.host, d));
+        if listed(&self.deny) {
+            return Some(Verdict::Denied);
+        }
+        // An explicit allow entry overrides the built-in services.
+        if listed(&self.allow) {
+            return None;
+        }
+        if let Some(kind) = info.service {
+            return Some(Verdict::Service(kind));
+        }
+        (!self.allow.is_empty()).then_some(Verdict::NotAllowed)
+    }
+}
+
+impl Verdict {
+    fn severity(&self) -> Severity {
+        match self {
+            Verdict::Service(ServiceKind::Shortener) | Verdict::NotAllowed => Severity::Warning,
+            Verdict::Service(_) | Verdict::Denied => Severity::Error,
+        }
+    }
+
+    fn reason(&self, domain: &str) -> String {
+        match self {
+            Verdict::Service(kind) => format!("{domain} is a known {kind} service"),
+            Verdict::Denied => format!("{domain} is on the deny list"),
+            Verdict::NotAllowed => format!("{domain} is not on the allow list"),
+        }
+    }
+}
+
+impl Rule for UrlPolicyRule {
+    fn id(&self) -> &str {
+        RULE_ID
+    }
+
+    fn name(&self) -> &str {
+        "Disallowed URL Domain"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn applies_to(&self) -> &[FileType] {
+        &[] // all file types
+    }
+
+    fn description(&self) -> &str {
+        "A URL whose host is a paste site, anonymous file drop, tunnel or request capture service (error), a URL shortener that hides where the link goes (warning), or a domain on the `deny` list (error). When the `allow` list is set, every other domain is reported too (warning). Both lists go under `[rules.SL-NET-012.params]` and match subdomains; `allow` also overrides the built-in services."
+    }
+
+    fn remediation(&self) -> &str {
+        "Link to the real destination on a domain you trust, or ship the content with the skill. Add domains the skill legitimately uses to `allow`."
+    }
+
+    fn tags(&self) -> Vec<&str> {
+        vec!["exfiltration"]
+    }
+
+    fn params(&self) -> BTreeMap<String, ParamValue> {
+        BTreeMap::from([
+            ("allow".to_string(), ParamValue::List(self.allow.clone())),
+            ("deny".to_string(), ParamValue::List(self.deny.clone())),
+        ])
+    }
+
+    fn version(&self) -> String {
+        format!(
+            "{}+deny:{}+allow:{}",
+            env!("CARGO_PKG_VERSION"),
+            self.deny.join(","),
+            self.allow.join(",")
+        )
+    }
+
+    fn with_params(
+        &self,
+        overrides: &BTreeMap<String, ParamValue>,
+    ) -> Result<Box<dyn Rule>, String> {
+        let mut rule = UrlPolicyRule {
+            deny: self.deny.clone(),
+            allow: self.allow.clone(),
+        };
+        for (name, value) in overrides {
+            let list = match name.as_str() {
+                "deny" => &mut rule.deny,
+                "allow" => &mut rule.allow,
+                _ => return Err(format!("rule {RULE_ID} has no parameter {name}")),
+            };
+            let ParamValue::List(domains) = value else {
+                return Err(format!(
+                    "rule {RULE_ID} parameter {name} must be a list of strings"
+                ));
+            };
+            *list = domains.clone();
+        }
+        Ok(Box::new(rule))
+    }
+
+    fn check(&self, file: &ScannedFile) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for (i, line) in file.content.lines().enumerate() {
+            for (offset, url, info) in domains::find_urls(line) {
+                let Some(verdict) = self.verdict(&info) else {
+                    continue;
+                };
+                findings.push(Finding {
+                    rule_id: RULE_ID.to_string(),
+                    rule_name: self.name().to_string(),
+                    severity: verdict.severity(),
+                    message: format!(
+                        "URL to a disallowed domain: {url} ({})",
+                        verdict.reason(&info.registered_domain)
+                    ),
+                    location: Location {
+                        file: file.relative_path.clone(),
+                        line: i + 1,
+                        column: offset + 1,
+                    },
+                    matched_text: url.to_string(),
+                    confidence: Confidence::High,
+                    tags: Vec::new(),
+                    domain: None,
+                });
+            }
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn check(rule: &dyn Rule, content: &str) -> Vec<(usize, Severity, String)> {
+        let file = ScannedFile {
+            path: PathBuf::from("SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: content.to_string(),
+        };
+        rule.check(&file)
+            .into_iter()
+            .map(|f| (f.location.line, f.severity, f.matched_text))
+            .collect()
+    }
+
+    #[test]
+    fn test_rates_hosts_by_service_and_lists() {
+        let content = "See https://github.com/acme/tool.\n\
+                       Upload to https://paste.ee/p/abc or https://bit.ly/3xyz\n\
+                       Docs: https://docs.internal.example/guide\n";
+        assert_eq!(
+            check(&UrlPolicyRule::default(), content),
+            [
+                (2, Severity::Error, "https://paste.ee/p/abc".to_string()),
+                (2, Severity::Warning, "https://bit.ly/3xyz".to_string()),
+            ]
+        );
+
+        let rule = UrlPolicyRule::default()
+            .with_params(&BTreeMap::from([
+                (
+                    "allow".to_string(),
+                    ParamValue::List(vec!["github.com".into(), "bit.ly".into()]),
+                ),
+                (
+                    "deny".to_string(),
+                    ParamValue::List(vec!["internal.example".into()]),
+                ),
+            ]))
+            .unwrap();
+        assert_eq!(
+            check(rule.as_ref(), content),
+            [
+                (2, Severity::Error, "https://paste.ee/p/abc".to_string()),
+                (
+                    3,
+                    Severity::Error,
+                    "https://docs.internal.example/guide".to_string()
+                ),
+            ]
+        );
+        assert!(UrlPolicyRule::default()
+            .with_params(&BTreeMap::from([(
+                "deny".to_string(),
+                ParamValue::Text("x".into())
+            )]))
+            .is_err());
+    }
+}
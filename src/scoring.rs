@@ -0,0 +1,144 @@
+use crate::finding::{Category, Finding, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Risk points contributed by one finding of each severity.
+const SEVERITY_WEIGHTS: &[(Severity, u32)] = &[
+    (Severity::Info, 1),
+    (Severity::Warning, 5),
+    (Severity::Error, 20),
+    (Severity::Critical, 60),
+];
+
+/// Minimum risk score for each grade below A, worst first.
+const GRADE_THRESHOLDS: &[(Grade, u32)] = &[
+    (Grade::F, 60),
+    (Grade::D, 30),
+    (Grade::C, 15),
+    (Grade::B, 5),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self {
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+            Grade::F => "F",
+        };
+        write!(f, "{letter}")
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CategoryCounts {
+    pub critical: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+/// At-a-glance verdict for a set of findings.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrustScore {
+    pub risk_score: u32,
+    pub grade: Grade,
+    pub categories: BTreeMap<Category, CategoryCounts>,
+}
+
+pub fn severity_weight(severity: Severity) -> u32 {
+    SEVERITY_WEIGHTS
+        .iter()
+        .find(|(s, _)| *s == severity)
+        .map_or(0, |(_, w)| *w)
+}
+
+pub fn grade_for(risk_score: u32) -> Grade {
+    GRADE_THRESHOLDS
+        .iter()
+        .find(|(_, min)| risk_score >= *min)
+        .map_or(Grade::A, |(g, _)| *g)
+}
+
+pub fn score(findings: &[Finding]) -> TrustScore {
+    let mut categories: BTreeMap<Category, CategoryCounts> = BTreeMap::new();
+    let mut risk_score = 0u32;
+
+    for f in findings {
+        risk_score = risk_score.saturating_add(severity_weight(f.severity));
+        let counts = categories.entry(f.category()).or_default();
+        match f.severity {
+            Severity::Critical => counts.critical += 1,
+            Severity::Error => counts.errors += 1,
+            Severity::Warning => counts.warnings += 1,
+            Severity::Info => counts.info += 1,
+        }
+    }
+
+    TrustScore {
+        risk_score,
+        grade: grade_for(risk_score),
+        categories,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_is_grade_a() {
+        let s = score(&[]);
+        assert_eq!(s.risk_score, 0);
+        assert_eq!(s.grade, Grade::A);
+        assert!(s.categories.is_empty());
+    }
+
+    #[test]
+    fn test_grade_thresholds() {
+        assert_eq!(grade_for(4), Grade::A);
+        assert_eq!(grade_for(5), Grade::B);
+        assert_eq!(grade_for(20), Grade::C);
+        assert_eq!(grade_for(45), Grade::D);
+        assert_eq!(grade_for(60), Grade::F);
+    }
+
+    #[test]
+    fn test_category_breakdown() {
+        let findings = vec![
+            Finding::test("SL-NET-001", Severity::Info, "SKILL.md", 1),
+            Finding::test("SL-NET-010", Severity::Error, "SKILL.md", 1),
+            Finding::test("SL-INJ-001", Severity::Error, "SKILL.md", 1),
+            Finding::test("SL-SOC-001", Severity::Warning, "SKILL.md", 1),
+        ];
+        let s = score(&findings);
+        assert_eq!(s.risk_score, 46);
+        assert_eq!(s.grade, Grade::D);
+        let net = &s.categories[&Category::Network];
+        assert_eq!((net.errors, net.warnings, net.info), (1, 0, 1));
+        assert_eq!(s.categories[&Category::Injection].errors, 1);
+    }
+
+    #[test]
+    fn test_single_critical_fails() {
+        let s = score(&[Finding::test(
+            "SL-EXEC-011",
+            Severity::Critical,
+            "SKILL.md",
+            1,
+        )]);
+        assert_eq!(s.grade, Grade::F);
+        assert_eq!(s.categories[&Category::Execution].critical, 1);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::finding::{Finding, Severity};
+use crate::trace;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const USER_AGENT: &str = concat!("skill-issue/", env!("CARGO_PKG_VERSION"));
+
+/// Issuers with a read-only endpoint that reports whether a token is live.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Issuer {
+    GitHub,
+    Slack,
+}
+
+impl Issuer {
+    fn for_rule(rule_id: &str) -> Option<Self> {
+        match rule_id {
+            "SL-SEC-006" => Some(Issuer::GitHub),
+            "SL-SEC-009" => Some(Issuer::Slack),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Liveness {
+    Active,
+    Inactive,
+    /// The check failed (network error, rate limit, unexpected response).
+    Unknown,
+}
+
+/// Check each leaked credential with its issuer and record the result on the
+/// finding. Live credentials escalate to critical. Tokens are only ever sent
+/// to the service that issued them, and each distinct token is checked once.
+pub fn verify(findings: &mut [Finding], verbose: bool) {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .into();
+
+    let mut checked: HashMap<String, Liveness> = HashMap::new();
+    for finding in findings.iter_mut() {
+        let Some(issuer) = Issuer::for_rule(&finding.rule_id) else {
+            continue;
+        };
+        // Display matches are truncated past 80 characters; a partial token
+        // cannot be checked.
+        let token = finding.matched_text.clone();
+        if token.ends_with("...") {
+            continue;
+        }
+        let liveness = *checked.entry(token.clone()).or_insert_with(|| {
+            if verbose {
+                eprintln!(
+                    "Verifying {} credential in {}",
+                    finding.rule_name,
+                    finding.location.file.display()
+                );
+            }
+            check(&agent, issuer, &token)
+        });
+        apply(finding, liveness);
+    }
+}
+
+fn check(agent: &ureq::Agent, issuer: Issuer, token: &str) -> Liveness {
+    let bearer = format!("Bearer {token}");
+    match issuer {
+        Issuer::GitHub => {
+            let url = "https://api.github.com/user";
+            let started = Instant::now();
+            let resp = agent
+                .get(url)
+                .header("User-Agent", USER_AGENT)
+                .header("Accept", "application/vnd.github+json")
+                .header("Authorization", &bearer)
+                .call();
+            let status = resp.map(|r| r.status().as_u16());
+            trace::http("GET", url, status.as_ref().ok().copied(), started.elapsed());
+            match status {
+                Ok(200) => Liveness::Active,
+                Ok(401) => Liveness::Inactive,
+                _ => Liveness::Unknown,
+            }
+        }
+        Issuer::Slack => {
+            let url = "https://slack.com/api/auth.test";
+            let started = Instant::now();
+            let resp = agent
+                .post(url)
+                .header("User-Agent", USER_AGENT)
+                .header("Authorization", &bearer)
+                .send_empty()
+                .ok();
+            let status = resp.as_ref().map(|r| r.status().as_u16());
+            trace::http("POST", url, status, started.elapsed());
+            let body: Option<serde_json::Value> =
+                resp.and_then(|mut r| r.body_mut().read_json().ok());
+            match body {
+                Some(b) if b["ok"] == true => Liveness::Active,
+                Some(b)
+                    if matches!(
+                        b["error"].as_str(),
+                        Some(
+                            "invalid_auth" | "token_revoked" | "token_expired" | "account_inactive"
+                        )
+                    ) =>
+                {
+                    Liveness::Inactive
+                }
+                _ => Liveness::Unknown,
+            }
+        }
+    }
+}
+
+fn apply(finding: &mut Finding, liveness: Liveness) {
+    match liveness {
+        Liveness::Active => {
+            finding.severity = Severity::Critical;
+            finding.message.push_str(" (verified active)");
+        }
+        Liveness::Inactive => finding.message.push_str(" (verified inactive)"),
+        Liveness::Unknown => finding.message.push_str(" (verification failed)"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_verifiable_rules_have_issuers() {
+        assert_eq!(Issuer::for_rule("SL-SEC-006"), Some(Issuer::GitHub));
+        assert_eq!(Issuer::for_rule("SL-SEC-009"), Some(Issuer::Slack));
+        assert_eq!(Issuer::for_rule("SL-SEC-001"), None);
+    }
+
+    #[test]
+    fn test_active_credential_escalates_to_critical() {
+        let mut f = Finding::test("SL-SEC-006", Severity::Error, "SKILL.md", 1);
+        apply(&mut f, Liveness::Active);
+        assert_eq!(f.severity, Severity::Critical);
+        assert!(f.message.ends_with("(verified active)"));
+
+        let mut f = Finding::test("SL-SEC-006", Severity::Error, "SKILL.md", 1);
+        apply(&mut f, Liveness::Inactive);
+        assert_eq!(f.severity, Severity::Error);
+        assert!(f.message.ends_with("(verified inactive)"));
+    }
+}
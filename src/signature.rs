@@ -0,0 +1,341 @@
+use crate::config::Config;
+use crate::finding::{Confidence, Finding, Location, Severity};
+use crate::rules::RuleDoc;
+use crate::scanner::{ScannedFile, SKIP_DIRS};
+use minisign_verify::{PublicKey, Signature};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Detached minisign signature over the skill manifest, at the skill root.
+pub const SIGNATURE_FILE: &str = "skill.minisig";
+/// Sigstore bundle location. Bundles are recognized but not verified.
+pub const SIGSTORE_BUNDLE_FILE: &str = "skill.sigstore.json";
+
+/// Prefix of the signature rule IDs.
+pub const RULE_PREFIX: &str = "SL-SIG-";
+
+const MANIFEST_HEADER: &str = "skill-issue-manifest v1";
+
+/// Signature findings are warnings unless a signature is required.
+pub const RULE_DOCS: &[RuleDoc] = &[
+    RuleDoc {
+        id: "SL-SIG-001",
+        name: "Unsigned Skill",
+        severity: Severity::Warning,
+        applies_to: &[],
+        description: "The skill has no `skill.minisig` signature, so there is no way to tell whether its files are what the publisher released. Sigstore bundles are recognized but cannot be verified yet.",
+        remediation: "Sign the manifest printed by `skill-issue verify-signature --manifest` with minisign and ship the signature as `skill.minisig`.",
+    },
+    RuleDoc {
+        id: "SL-SIG-002",
+        name: "Invalid Signature",
+        severity: Severity::Error,
+        applies_to: &[],
+        description: "The signature cannot be decoded, or was made by a trusted key but does not match the skill's files: they were changed after signing.",
+        remediation: "Do not use the skill until the publisher re-signs it. If you changed the files yourself, sign the new manifest.",
+    },
+    RuleDoc {
+        id: "SL-SIG-003",
+        name: "Untrusted Signer",
+        severity: Severity::Warning,
+        applies_to: &[],
+        description: "The skill is signed, but not by any key in the trusted key list.",
+        remediation: "Add the publisher's public key with `--trusted-key` or `[signatures] trusted_keys` once you have verified it out of band.",
+    },
+];
+
+pub fn is_signature_file(relative: &Path) -> bool {
+    relative == Path::new(SIGNATURE_FILE) || relative == Path::new(SIGSTORE_BUNDLE_FILE)
+}
+
+/// A file's path (relative to the skill root, `/`-separated) and raw bytes.
+pub type ManifestEntry = (String, Vec<u8>);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verification {
+    Valid { key: String },
+    Unsigned,
+    SigstoreOnly,
+    Malformed(String),
+    UntrustedSigner,
+    Tampered,
+}
+
+/// Read every file under a local skill root, including binary files.
+pub fn local_entries(root: &Path) -> Result<Vec<ManifestEntry>, String> {
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_str().unwrap_or("");
+            !SKIP_DIRS.contains(&name)
+        })
+    {
+        let entry = entry.map_err(|e| format!("walk error: {e}"))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        let bytes = std::fs::read(entry.path())
+            .map_err(|e| format!("failed to read {}: {e}", entry.path().display()))?;
+        entries.push((manifest_path(relative), bytes));
+    }
+    Ok(entries)
+}
+
+/// Build entries from already-fetched files (remote scans only see text files).
+pub fn scanned_entries(files: &[ScannedFile]) -> Vec<ManifestEntry> {
+    let mut entries: Vec<ManifestEntry> = Vec::new();
+    for f in files {
+        let path = manifest_path(&f.relative_path);
+        // Derived views share their source file's path; keep the original.
+        if !entries.iter().any(|(p, _)| *p == path) {
+            entries.push((path, f.content.as_bytes().to_vec()));
+        }
+    }
+    entries
+}
+
+/// A relative path as it appears in the manifest: `/`-separated on every platform.
+pub fn manifest_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Canonical manifest covered by the signature: a header line followed by one
+/// `sha256  path` line per file, sorted by path. Signature files are excluded.
+pub fn manifest(entries: &[ManifestEntry]) -> String {
+    let mut covered: Vec<&ManifestEntry> = entries
+        .iter()
+        .filter(|(path, _)| path != SIGNATURE_FILE && path != SIGSTORE_BUNDLE_FILE)
+        .collect();
+    covered.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut out = String::from(MANIFEST_HEADER);
+    out.push('\n');
+    for (path, bytes) in covered {
+        let digest = Sha256::digest(bytes);
+        let hex: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+        out.push_str(&format!("{hex}  {path}\n"));
+    }
+    out
+}
+
+/// A trusted key, given either as a base64 minisign public key or as a path
+/// to a `minisign.pub` file.
+pub struct TrustedKey {
+    pub label: String,
+    key: PublicKey,
+}
+
+pub fn load_trusted_keys(specs: &[String]) -> Result<Vec<TrustedKey>, String> {
+    specs
+        .iter()
+        .map(|spec| {
+            let key = if Path::new(spec).is_file() {
+                PublicKey::from_file(spec)
+            } else {
+                PublicKey::from_base64(spec.trim())
+            }
+            .map_err(|e| format!("invalid trusted key '{spec}': {e}"))?;
+            Ok(TrustedKey {
+                label: spec.clone(),
+                key,
+            })
+        })
+        .collect()
+}
+
+pub fn verify(entries: &[ManifestEntry], keys: &[TrustedKey]) -> Verification {
+    let find = |name: &str| entries.iter().find(|(p, _)| p == name);
+
+    let Some((_, sig_bytes)) = find(SIGNATURE_FILE) else {
+        return if find(SIGSTORE_BUNDLE_FILE).is_some() {
+            Verification::SigstoreOnly
+        } else {
+            Verification::Unsigned
+        };
+    };
+
+    let signature = match std::str::from_utf8(sig_bytes)
+        .map_err(|e| e.to_string())
+        .and_then(|s| Signature::decode(s).map_err(|e| e.to_string()))
+    {
+        Ok(s) => s,
+        Err(e) => return Verification::Malformed(e),
+    };
+
+    let manifest = manifest(entries);
+    let mut key_matched = false;
+    for trusted in keys {
+        match trusted.key.verify(manifest.as_bytes(), &signature, false) {
+            Ok(()) => {
+                return Verification::Valid {
+                    key: trusted.label.clone(),
+                }
+            }
+            Err(minisign_verify::Error::UnexpectedKeyId) => {}
+            Err(_) => key_matched = true,
+        }
+    }
+
+    if key_matched {
+        Verification::Tampered
+    } else {
+        Verification::UntrustedSigner
+    }
+}
+
+/// Entries for the scan target: raw bytes from disk for local scans, fetched
+/// contents for remote ones.
+pub fn target_entries(
+    config: &Config,
+    files: &[ScannedFile],
+) -> Result<Vec<ManifestEntry>, String> {
+    if config.remote.is_some() {
+        Ok(scanned_entries(files))
+    } else {
+        local_entries(&config.path)
+    }
+}
+
+/// Run the signature policy for a scan target, returning findings to merge
+/// into the report.
+pub fn check(config: &Config, files: &[ScannedFile]) -> Result<Vec<Finding>, String> {
+    let keys = load_trusted_keys(&config.trusted_keys)?;
+    let entries = target_entries(config, files)?;
+    Ok(findings(&verify(&entries, &keys), config.require_signature))
+}
+
+/// Map a verification result to findings. Missing or untrusted signatures are
+/// errors only when a signature is required; a signature that fails to verify
+/// against a trusted key is always an error.
+pub fn findings(verification: &Verification, require: bool) -> Vec<Finding> {
+    let policy_severity = if require {
+        Severity::Error
+    } else {
+        Severity::Warning
+    };
+
+    let (rule_id, rule_name, severity, message) = match verification {
+        Verification::Valid { .. } => return Vec::new(),
+        Verification::Unsigned => (
+            "SL-SIG-001",
+            "Unsigned Skill",
+            policy_severity,
+            format!("Skill has no {SIGNATURE_FILE} signature"),
+        ),
+        Verification::SigstoreOnly => (
+            "SL-SIG-001",
+            "Unsigned Skill",
+            policy_severity,
+            format!(
+                "Skill ships a sigstore bundle, which cannot be verified here; provide a {SIGNATURE_FILE} minisign signature"
+            ),
+        ),
+        Verification::Malformed(e) => (
+            "SL-SIG-002",
+            "Invalid Signature",
+            Severity::Error,
+            format!("Signature file could not be decoded: {e}"),
+        ),
+        Verification::Tampered => (
+            "SL-SIG-002",
+            "Invalid Signature",
+            Severity::Error,
+            "Signature does not match skill contents — files were modified after signing"
+                .to_string(),
+        ),
+        Verification::UntrustedSigner => (
+            "SL-SIG-003",
+            "Untrusted Signer",
+            policy_severity,
+            "Skill is signed by a key that is not in the trusted key list".to_string(),
+        ),
+    };
+
+    vec![Finding {
+        rule_id: rule_id.to_string(),
+        rule_name: rule_name.to_string(),
+        severity,
+        message,
+        location: Location {
+            file: PathBuf::from(SIGNATURE_FILE),
+            line: 1,
+            column: 1,
+        },
+        matched_text: String::new(),
+        confidence: Confidence::High,
+        tags: Vec::new(),
+        domain: None,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(files: &[(&str, &str)]) -> Vec<ManifestEntry> {
+        files
+            .iter()
+            .map(|(p, c)| (p.to_string(), c.as_bytes().to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_manifest_is_sorted_and_excludes_signatures() {
+        let m = manifest(&entries(&[
+            ("scripts/run.sh", "echo hi"),
+            ("SKILL.md", "# Skill"),
+            (SIGNATURE_FILE, "sig"),
+        ]));
+        let lines: Vec<&str> = m.lines().collect();
+        assert_eq!(lines[0], MANIFEST_HEADER);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with("  SKILL.md"));
+        assert!(lines[2].ends_with("  scripts/run.sh"));
+    }
+
+    #[test]
+    fn test_verify_unsigned() {
+        let e = entries(&[("SKILL.md", "# Skill")]);
+        assert_eq!(verify(&e, &[]), Verification::Unsigned);
+
+        let e = entries(&[("SKILL.md", "# Skill"), (SIGSTORE_BUNDLE_FILE, "{}")]);
+        assert_eq!(verify(&e, &[]), Verification::SigstoreOnly);
+    }
+
+    #[test]
+    fn test_verify_malformed() {
+        let e = entries(&[("SKILL.md", "# Skill"), (SIGNATURE_FILE, "garbage")]);
+        assert!(matches!(verify(&e, &[]), Verification::Malformed(_)));
+    }
+
+    #[test]
+    fn test_findings_severity_follows_policy() {
+        assert_eq!(
+            findings(&Verification::Unsigned, true)[0].severity,
+            Severity::Error
+        );
+        assert_eq!(
+            findings(&Verification::Unsigned, false)[0].severity,
+            Severity::Warning
+        );
+        assert_eq!(
+            findings(&Verification::Tampered, false)[0].severity,
+            Severity::Error
+        );
+        assert!(findings(
+            &Verification::Valid {
+                key: "k".to_string()
+            },
+            true
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,356 @@
+use crate::config::{CliArgs, Config, ConfigFile};
+use crate::engine::Engine;
+use crate::finding::{report_path, Confidence, Finding, Severity};
+use crate::pipeline;
+use crate::scoring::{self, Grade};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Cell, Color, Table};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// A policy to try out with `--simulate-profile` before enforcing it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Profile {
+    /// Report everything, run every rule, de-obfuscate text, and fail on
+    /// warnings.
+    Strict,
+    /// Hide low-confidence findings and fail only on critical ones.
+    Lenient,
+    /// A proposed `.skill-issue.toml`, in place of the current one.
+    File(PathBuf),
+}
+
+impl FromStr for Profile {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(Profile::Strict),
+            "lenient" => Ok(Profile::Lenient),
+            "" => Err("expected strict, lenient or a config file path".to_string()),
+            path => Ok(Profile::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Profile::Strict => write!(f, "strict"),
+            Profile::Lenient => write!(f, "lenient"),
+            Profile::File(path) => write!(f, "{}", report_path(path)),
+        }
+    }
+}
+
+impl Profile {
+    /// The configuration the scan would run under. The built-in profiles
+    /// adjust the current configuration; a file replaces the current config
+    /// file, with the same command-line flags on top.
+    pub fn config(&self, args: &CliArgs, current: Option<ConfigFile>) -> Result<Config, String> {
+        let mut config = match self {
+            Profile::File(path) => {
+                if !path.is_file() {
+                    return Err(format!(
+                        "--simulate-profile {}: no such config file",
+                        path.display()
+                    ));
+                }
+                let file = pipeline::load_config_file(path).ok_or_else(|| {
+                    format!(
+                        "--simulate-profile {}: not a valid config file",
+                        path.display()
+                    )
+                })?;
+                Config::from_args_and_file(args.clone(), Some(file))
+            }
+            _ => Config::from_args_and_file(args.clone(), current),
+        };
+        match self {
+            Profile::Strict => {
+                config.min_severity = Severity::Info;
+                config.min_rule_severity = Severity::Info;
+                config.min_confidence = Confidence::Low;
+                config.error_on = Severity::Warning;
+                config.normalize = true;
+            }
+            Profile::Lenient => {
+                config.min_confidence = Confidence::Medium;
+                config.error_on = Severity::Critical;
+            }
+            Profile::File(_) => {}
+        }
+        // Only the real scan may write the baseline or history.
+        config.update_baseline = false;
+        config.history = false;
+        Ok(config)
+    }
+}
+
+/// One side of a simulation: how a policy treats the scanned files.
+#[derive(Debug, Serialize)]
+pub struct Outcome {
+    pub policy: String,
+    pub error_on: Severity,
+    pub findings: usize,
+    pub critical: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    pub grade: Grade,
+    pub exit_code: i32,
+}
+
+impl Outcome {
+    fn new(policy: String, error_on: Severity, findings: &[Finding]) -> Self {
+        let count = |s: Severity| findings.iter().filter(|f| f.severity == s).count();
+        Outcome {
+            policy,
+            error_on,
+            findings: findings.len(),
+            critical: count(Severity::Critical),
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            info: count(Severity::Info),
+            grade: scoring::score(findings).grade,
+            exit_code: Engine::exit_code(findings, error_on),
+        }
+    }
+}
+
+/// A finding both policies report, at different severities.
+#[derive(Debug, Serialize)]
+pub struct SeverityChange {
+    pub from: Severity,
+    #[serde(flatten)]
+    pub finding: Finding,
+}
+
+/// The current policy against a proposed one, over the same files.
+#[derive(Debug, Serialize)]
+pub struct Simulation {
+    pub current: Outcome,
+    pub proposed: Outcome,
+    /// Reported only under the proposed policy.
+    pub added: Vec<Finding>,
+    /// Reported only under the current policy.
+    pub removed: Vec<Finding>,
+    pub changed: Vec<SeverityChange>,
+}
+
+/// Match the two runs' findings by fingerprint, pairing identical
+/// fingerprints in order.
+pub fn compare(
+    current: &Config,
+    current_findings: Vec<Finding>,
+    profile: &Profile,
+    proposed: &Config,
+    proposed_findings: Vec<Finding>,
+) -> Simulation {
+    let mut simulation = Simulation {
+        current: Outcome::new("current".to_string(), current.error_on, &current_findings),
+        proposed: Outcome::new(profile.to_string(), proposed.error_on, &proposed_findings),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+    let mut remaining: HashMap<String, VecDeque<Finding>> = HashMap::new();
+    for f in current_findings {
+        remaining.entry(f.fingerprint()).or_default().push_back(f);
+    }
+    for f in proposed_findings {
+        match remaining
+            .get_mut(&f.fingerprint())
+            .and_then(|q| q.pop_front())
+        {
+            Some(old) if old.severity != f.severity => simulation.changed.push(SeverityChange {
+                from: old.severity,
+                finding: f,
+            }),
+            Some(_) => {}
+            None => simulation.added.push(f),
+        }
+    }
+    simulation.removed = remaining.into_values().flatten().collect();
+    simulation.added.sort_by_key(|f| f.sort_key());
+    simulation.removed.sort_by_key(|f| f.sort_key());
+    simulation.changed.sort_by_key(|c| c.finding.sort_key());
+    simulation
+}
+
+pub fn format_json(simulation: &Simulation) -> String {
+    serde_json::to_string_pretty(simulation).unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
+}
+
+/// The two policies side by side, then the findings that differ.
+pub fn format_table(simulation: &Simulation) -> String {
+    let (current, proposed) = (&simulation.current, &simulation.proposed);
+    let mut sides = Table::new();
+    sides
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            "".to_string(),
+            "Current".to_string(),
+            format!("Proposed ({})", proposed.policy),
+        ]);
+    let rows: [(&str, String, String); 8] = [
+        (
+            "Fails on",
+            current.error_on.to_string(),
+            proposed.error_on.to_string(),
+        ),
+        (
+            "Findings",
+            current.findings.to_string(),
+            proposed.findings.to_string(),
+        ),
+        (
+            "Critical",
+            current.critical.to_string(),
+            proposed.critical.to_string(),
+        ),
+        (
+            "Error",
+            current.errors.to_string(),
+            proposed.errors.to_string(),
+        ),
+        (
+            "Warning",
+            current.warnings.to_string(),
+            proposed.warnings.to_string(),
+        ),
+        ("Info", current.info.to_string(), proposed.info.to_string()),
+        (
+            "Grade",
+            format!("{:?}", current.grade),
+            format!("{:?}", proposed.grade),
+        ),
+        (
+            "Exit code",
+            current.exit_code.to_string(),
+            proposed.exit_code.to_string(),
+        ),
+    ];
+    for (label, now, then) in rows {
+        let then = if now == then {
+            Cell::new(then)
+        } else {
+            Cell::new(then).fg(Color::Yellow)
+        };
+        sides.add_row(vec![Cell::new(label), Cell::new(now), then]);
+    }
+
+    let totals = format!(
+        "Under {}: {} added, {} removed, {} changed severity; exit code {} -> {}",
+        proposed.policy,
+        simulation.added.len(),
+        simulation.removed.len(),
+        simulation.changed.len(),
+        current.exit_code,
+        proposed.exit_code
+    );
+    let entries: Vec<(String, &Finding)> = simulation
+        .added
+        .iter()
+        .map(|f| ("added".to_string(), f))
+        .chain(
+            simulation
+                .removed
+                .iter()
+                .map(|f| ("removed".to_string(), f)),
+        )
+        .chain(
+            simulation
+                .changed
+                .iter()
+                .map(|c| (format!("{} -> {}", c.from, c.finding.severity), &c.finding)),
+        )
+        .collect();
+    if entries.is_empty() {
+        return format!("{sides}\nNo findings differ.\n{totals}");
+    }
+    let mut differences = Table::new();
+    differences
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            "Change", "Severity", "Rule", "File", "Line", "Message",
+        ]);
+    for (label, f) in &entries {
+        let change = match label.as_str() {
+            "added" => Cell::new(label).fg(Color::Red),
+            "removed" => Cell::new(label).fg(Color::Green),
+            _ => Cell::new(label).fg(Color::Yellow),
+        };
+        differences.add_row(vec![
+            change,
+            Cell::new(f.severity),
+            Cell::new(&f.rule_id),
+            Cell::new(report_path(&f.location.file)),
+            Cell::new(format!("{}:{}", f.location.line, f.location.column)),
+            Cell::new(&f.message),
+        ]);
+    }
+    format!("{sides}\n{differences}\n{totals}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use clap::Parser;
+
+    #[test]
+    fn test_strict_profile_compares_findings_and_exit_code() {
+        let args = CliArgs::parse_from(["skill-issue", "--severity", "error"]);
+        let current = Config::from_args_and_file(args.clone(), None);
+        let proposed = Profile::Strict.config(&args, None).unwrap();
+        assert_eq!(proposed.min_severity, Severity::Info);
+        assert_eq!(proposed.error_on, Severity::Warning);
+
+        let simulation = compare(
+            &current,
+            vec![
+                Finding {
+                    matched_text: "eval(".into(),
+                    ..Finding::test("SL-EXEC-002", Severity::Error, "SKILL.md", 1)
+                },
+                Finding {
+                    matched_text: "curl".into(),
+                    ..Finding::test("SL-NET-001", Severity::Error, "SKILL.md", 1)
+                },
+            ],
+            &Profile::Strict,
+            &proposed,
+            vec![
+                Finding {
+                    matched_text: "eval(".into(),
+                    ..Finding::test("SL-EXEC-002", Severity::Error, "SKILL.md", 1)
+                },
+                Finding {
+                    matched_text: "curl".into(),
+                    ..Finding::test("SL-NET-001", Severity::Critical, "SKILL.md", 1)
+                },
+                Finding {
+                    matched_text: "wget".into(),
+                    ..Finding::test("SL-NET-002", Severity::Warning, "SKILL.md", 1)
+                },
+            ],
+        );
+        let rules = |fs: &[Finding]| fs.iter().map(|f| f.rule_id.clone()).collect::<Vec<_>>();
+        assert_eq!(rules(&simulation.added), ["SL-NET-002"]);
+        assert!(simulation.removed.is_empty());
+        assert_eq!(simulation.changed.len(), 1);
+        assert_eq!(simulation.changed[0].from, Severity::Error);
+        assert_eq!(simulation.current.exit_code, 2);
+        assert_eq!(simulation.proposed.exit_code, 2);
+        assert_eq!(simulation.proposed.policy, "strict");
+        assert!("".parse::<Profile>().is_err());
+        assert_eq!(
+            "team.toml".parse::<Profile>(),
+            Ok(Profile::File(PathBuf::from("team.toml")))
+        );
+    }
+}
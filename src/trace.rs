@@ -0,0 +1,98 @@
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Where `--trace-log` events go, once opened. Each line is written out
+/// whole, so the log is complete however the process exits.
+static SINK: OnceLock<Mutex<LineWriter<File>>> = OnceLock::new();
+
+/// Start writing events to `path`, one JSON object per line. Only the first
+/// call has any effect.
+pub fn open(path: &Path) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("--trace-log {}: {e}", path.display()))?;
+    let _ = SINK.set(Mutex::new(LineWriter::new(file)));
+    Ok(())
+}
+
+/// Whether events are being written, so callers can skip building them.
+pub fn enabled() -> bool {
+    SINK.get().is_some()
+}
+
+/// Write one event: `{"ts": ..., "event": name, ...fields}`.
+pub fn event(name: &str, fields: Value) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    let mut record = Map::new();
+    record.insert(
+        "ts".into(),
+        chrono::Utc::now()
+            .to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+            .into(),
+    );
+    record.insert("event".into(), name.into());
+    if let Value::Object(fields) = fields {
+        record.extend(fields);
+    }
+    let mut sink = sink.lock().unwrap_or_else(|e| e.into_inner());
+    let _ = writeln!(sink, "{}", Value::Object(record));
+}
+
+/// An HTTP request and how it ended. Headers are left out: they carry
+/// tokens.
+pub fn http(method: &str, url: &str, status: Option<u16>, elapsed: Duration) {
+    event(
+        "http_request",
+        serde_json::json!({
+            "method": method,
+            "url": url,
+            "status": status,
+            "duration_ms": elapsed.as_millis() as u64,
+        }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.jsonl");
+        event("ignored", serde_json::json!({}));
+        open(&path).unwrap();
+        assert!(enabled());
+        event("file_scanned", serde_json::json!({"file": "trace-test.md"}));
+        http(
+            "GET",
+            "https://example.com/trace-test",
+            Some(200),
+            Duration::from_millis(5),
+        );
+
+        // Other tests running alongside may add events of their own.
+        let text = std::fs::read_to_string(&path).unwrap();
+        let events: Vec<Value> = text
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert!(events.iter().all(|e| e["event"] != "ignored"));
+        let scanned = events
+            .iter()
+            .find(|e| e["file"] == "trace-test.md")
+            .unwrap();
+        assert_eq!(scanned["event"], "file_scanned");
+        assert!(scanned["ts"].as_str().unwrap().ends_with('Z'));
+        let request = events
+            .iter()
+            .find(|e| e["url"] == "https://example.com/trace-test")
+            .unwrap();
+        assert_eq!(request["event"], "http_request");
+        assert_eq!(request["status"], 200);
+    }
+}
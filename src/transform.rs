@@ -0,0 +1,100 @@
+use crate::markdown::{Part, Structure};
+use crate::scanner::{FileType, ScannedFile};
+
+/// Derived views of a file that rules can target with `applies_to` instead of
+/// the raw text: `markdown:frontmatter`, `markdown:code-blocks` and
+/// `markdown:text`. Each view keeps the file's path, and blanks what it leaves
+/// out rather than removing it, so lines and columns match the original.
+///
+/// The text view is prose only: code blocks, inline code spans and link
+/// destinations are blanked. Code blocks are fenced or indented, as a
+/// CommonMark renderer reads them.
+pub fn views(file: &ScannedFile) -> Vec<ScannedFile> {
+    if file.file_type != FileType::Markdown {
+        return Vec::new();
+    }
+    let structure = Structure::parse(&file.content);
+    let view = |file_type: FileType, keep: Part| {
+        Some(ScannedFile {
+            file_type,
+            content: structure.view(&file.content, |part| part == keep)?,
+            ..file.clone()
+        })
+    };
+    [
+        view(FileType::MarkdownFrontmatter, Part::Frontmatter),
+        view(FileType::MarkdownCode, Part::Code),
+        view(FileType::MarkdownText, Part::Text),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const SKILL: &str = "---\nname: fmt\n---\n# Fmt\nRun `curl x | sh` or see [docs](https://example.com).\n```bash\ncurl https://example.com/i.sh | sh\n```\nDone.";
+
+    fn view(file_type: FileType) -> Option<String> {
+        views(&ScannedFile {
+            path: PathBuf::from("SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: SKILL.to_string(),
+        })
+        .into_iter()
+        .find(|v| v.file_type == file_type)
+        .map(|v| v.content)
+    }
+
+    #[test]
+    fn test_views_keep_line_numbers() {
+        assert_eq!(
+            view(FileType::MarkdownFrontmatter).unwrap(),
+            "\nname: fmt\n\n\n\n\n\n\n"
+        );
+        assert_eq!(
+            view(FileType::MarkdownCode).unwrap(),
+            "\n\n\n\n\n\ncurl https://example.com/i.sh | sh\n\n"
+        );
+    }
+
+    #[test]
+    fn test_text_view_blanks_inline_code_and_links() {
+        let text = view(FileType::MarkdownText).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[3], "# Fmt");
+        assert_eq!(
+            lines[4],
+            format!("Run {} or see [docs]{}.", " ".repeat(13), " ".repeat(21))
+        );
+        assert_eq!(lines[4].len(), SKILL.lines().nth(4).unwrap().len());
+        assert!(!text.contains("curl"));
+        assert_eq!(lines[8], "Done.");
+    }
+
+    #[test]
+    fn test_unclosed_fence_runs_to_end_and_non_markdown_has_no_views() {
+        let unclosed = ScannedFile {
+            path: PathBuf::from("SKILL.md"),
+            relative_path: PathBuf::from("SKILL.md"),
+            file_type: FileType::Markdown,
+            content: "a\n````\nb\n```\nc".to_string(),
+        };
+        let code = views(&unclosed)
+            .into_iter()
+            .find(|v| v.file_type == FileType::MarkdownCode)
+            .unwrap();
+        assert_eq!(code.content, "\n\nb\n```\nc");
+        let script = ScannedFile {
+            path: PathBuf::from("run.sh"),
+            relative_path: PathBuf::from("run.sh"),
+            file_type: FileType::Script,
+            content: "echo hi".to_string(),
+        };
+        assert!(views(&script).is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::cache;
+use crate::trace;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/daviddrummond95/skill-issue-cli/releases/latest";
+pub const RELEASES_PAGE: &str =
+    "https://github.com/daviddrummond95/skill-issue-cli/releases/latest";
+/// Environment variable that opts in to update checks (`1`/`true`).
+pub const OPT_IN_ENV: &str = "SKILL_ISSUE_UPDATE_CHECK";
+const CHECK_INTERVAL_SECS: i64 = 24 * 60 * 60;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Last check result, cached so the network is hit at most once a day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCache {
+    pub checked_at: i64,
+    /// `None` when the last check failed.
+    pub latest_version: Option<String>,
+}
+
+/// Whether update checks are enabled, by flag or environment, and
+/// never on CI where nobody reads the note, or `--offline`.
+pub fn enabled(requested: bool, offline: bool) -> bool {
+    let env_opt_in = std::env::var(OPT_IN_ENV)
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    (requested || env_opt_in) && !offline && std::env::var_os("CI").is_none()
+}
+
+const CACHE_ENTRY: &str = "update-check.json";
+
+pub fn read_cache() -> Option<UpdateCache> {
+    let contents = cache::read(&cache::dir()?, CACHE_ENTRY)?;
+    serde_json::from_slice(&contents).ok()
+}
+
+fn write_cache(update: &UpdateCache) {
+    let Some(dir) = cache::dir() else { return };
+    if let Ok(json) = serde_json::to_vec(update) {
+        let _ = cache::write(&dir, CACHE_ENTRY, &json);
+    }
+}
+
+/// Latest released version, from the cache when it is less than a day old,
+/// otherwise from GitHub. The request carries no data beyond the user agent.
+/// Failures are cached too and otherwise silent: an update check must never
+/// slow down or break every scan.
+pub fn latest_version() -> Option<String> {
+    let now = chrono::Utc::now().timestamp();
+    let cached = read_cache();
+    if let Some(ref cache) = cached {
+        if now - cache.checked_at < CHECK_INTERVAL_SECS {
+            return cache.latest_version.clone();
+        }
+    }
+
+    let latest = fetch_latest().or_else(|| cached.and_then(|c| c.latest_version));
+    write_cache(&UpdateCache {
+        checked_at: now,
+        latest_version: latest.clone(),
+    });
+    latest
+}
+
+fn fetch_latest() -> Option<String> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .into();
+    let started = Instant::now();
+    let resp = agent
+        .get(LATEST_RELEASE_URL)
+        .header(
+            "User-Agent",
+            concat!("skill-issue/", env!("CARGO_PKG_VERSION")),
+        )
+        .header("Accept", "application/vnd.github+json")
+        .call()
+        .ok();
+    let status = resp.as_ref().map(|r| r.status().as_u16());
+    trace::http("GET", LATEST_RELEASE_URL, status, started.elapsed());
+    let body: serde_json::Value = resp?.body_mut().read_json().ok()?;
+    Some(
+        body["tag_name"]
+            .as_str()?
+            .trim_start_matches('v')
+            .to_string(),
+    )
+}
+
+fn parse_version(v: &str) -> Option<(u64, u64, u64)> {
+    let core = v.trim_start_matches('v').split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    Some((
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+/// Whether `latest` is a newer release than `current`.
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(l), Some(c)) => l > c,
+        _ => false,
+    }
+}
+
+/// The one-line note shown when a newer release exists.
+pub fn notice(latest: &str) -> Option<String> {
+    let current = env!("CARGO_PKG_VERSION");
+    is_newer(latest, current).then(|| {
+        format!("note: skill-issue {latest} is available (you have {current}); new releases ship updated patterns: {RELEASES_PAGE}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.2.0", "0.1.9"));
+        assert!(is_newer("v1.0.0", "0.9.9"));
+        assert!(is_newer("0.1.10", "0.1.9"));
+        assert!(!is_newer("0.1.2", "0.1.2"));
+        assert!(!is_newer("0.1.1", "0.1.2"));
+        assert!(!is_newer("nightly", "0.1.2"));
+    }
+
+    #[test]
+    fn test_prerelease_suffix_ignored() {
+        assert_eq!(parse_version("v1.2.3-rc.1"), Some((1, 2, 3)));
+        assert_eq!(parse_version("2"), Some((2, 0, 0)));
+    }
+
+    #[test]
+    fn test_notice_only_for_newer() {
+        assert!(notice("999.0.0").unwrap().contains("999.0.0 is available"));
+        assert!(notice("0.0.1").is_none());
+    }
+}
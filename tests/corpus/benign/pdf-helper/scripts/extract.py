@@ -0,0 +1,13 @@
+import sys
+
+from pypdf import PdfReader
+
+
+def main(path):
+    reader = PdfReader(path)
+    for page in reader.pages:
+        print(page.extract_text())
+
+
+if __name__ == "__main__":
+    main(sys.argv[1])
@@ -12,6 +12,10 @@ fn test_all_patterns_parse_and_unique_ids() {
         ("injection", include_str!("../patterns/injection.toml")),
         ("social", include_str!("../patterns/social.toml")),
         ("metadata", include_str!("../patterns/metadata.toml")),
+        ("container", include_str!("../patterns/container.toml")),
+        ("cloud", include_str!("../patterns/cloud.toml")),
+        ("keys", include_str!("../patterns/keys.toml")),
+        ("bypass", include_str!("../patterns/bypass.toml")),
     ];
 
     let mut all_ids = HashSet::new();
@@ -48,6 +52,29 @@ fn test_all_patterns_parse_and_unique_ids() {
                     .unwrap_or_else(|e| panic!("{name}.toml: rule {id} regex error: {e}"));
             }
 
+            // Placeholders must be declared params; any value stands in.
+            if let Some(exclude) = rule.get("exclude").and_then(|v| v.as_str()) {
+                let mut expanded = exclude.to_string();
+                if let Some(params) = rule.get("params").and_then(|v| v.as_table()) {
+                    for name in params.keys() {
+                        expanded = expanded.replace(&format!("{{{{{name}}}}}"), "(?:x)");
+                    }
+                }
+                assert!(
+                    !expanded.contains("{{"),
+                    "{name}.toml: rule {id} exclude uses an undeclared parameter"
+                );
+                regex::Regex::new(&expanded)
+                    .unwrap_or_else(|e| panic!("{name}.toml: rule {id} exclude error: {e}"));
+            }
+
+            if let Some(confidence) = rule.get("confidence") {
+                assert!(
+                    matches!(confidence.as_str(), Some("low" | "medium" | "high")),
+                    "{name}.toml: rule {id} has unknown confidence {confidence}"
+                );
+            }
+
             // Verify unique ID
             assert!(
                 all_ids.insert(id.to_string()),
@@ -63,3 +90,51 @@ fn test_all_patterns_parse_and_unique_ids() {
         all_ids.len()
     );
 }
+
+/// Every pattern rule is documented, and its examples are flagged by the rule
+/// itself, so the generated rule docs cannot drift from the patterns.
+#[test]
+fn test_all_patterns_documented_with_matching_examples() {
+    let pattern_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("patterns");
+    for entry in std::fs::read_dir(pattern_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let file: toml::Value = toml::from_str(&content).unwrap();
+
+        for rule in file["rules"].as_array().unwrap() {
+            let id = rule["id"].as_str().unwrap();
+            for field in ["description", "remediation"] {
+                assert!(
+                    rule.get(field)
+                        .and_then(|v| v.as_str())
+                        .is_some_and(|s| !s.trim().is_empty()),
+                    "rule {id} has no {field}"
+                );
+            }
+
+            let multiline = rule
+                .get("multiline")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let regex = regex::RegexBuilder::new(rule["pattern"].as_str().unwrap())
+                .multi_line(multiline)
+                .dot_matches_new_line(multiline)
+                .build()
+                .unwrap();
+            let examples = rule
+                .get("examples")
+                .and_then(|v| v.as_array())
+                .unwrap_or_else(|| panic!("rule {id} has no examples"));
+            assert!(!examples.is_empty(), "rule {id} has no examples");
+            for example in examples {
+                let example = example.as_str().unwrap();
+                let matched = if multiline {
+                    regex.is_match(example)
+                } else {
+                    example.lines().any(|line| regex.is_match(line))
+                };
+                assert!(matched, "rule {id} does not match its example: {example}");
+            }
+        }
+    }
+}